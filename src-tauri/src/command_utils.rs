@@ -1,4 +1,9 @@
-use std::process::Command;
+use std::{
+  fmt,
+  process::{Command, Output},
+};
+
+use serde::{Deserialize, Serialize};
 
 #[cfg(windows)]
 use winreg::{
@@ -6,6 +11,63 @@ use winreg::{
   enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE},
 };
 
+/// A git or pnpm invocation that failed, kept structured so the UI and run
+/// records can present the exit details consistently. `Display` prints the
+/// human-readable `message`, which is what `String` errors carry.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+  pub program: String,
+  pub args: Box<[String]>,
+  /// `None` when the process could not be started or was killed by a signal.
+  pub status_code: Option<i32>,
+  pub stdout: String,
+  pub stderr: String,
+  pub message: String,
+}
+
+impl CommandError {
+  pub fn from_output(program: &str, args: &[&str], output: &Output, message: impl Into<String>) -> Self {
+    Self {
+      program: program.to_string(),
+      args: args.iter().map(|arg| arg.to_string()).collect(),
+      status_code: output.status.code(),
+      stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+      stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+      message: message.into(),
+    }
+  }
+
+  pub fn not_started(program: &str, args: &[&str], message: impl Into<String>) -> Self {
+    Self {
+      program: program.to_string(),
+      args: args.iter().map(|arg| arg.to_string()).collect(),
+      status_code: None,
+      stdout: String::new(),
+      stderr: String::new(),
+      message: message.into(),
+    }
+  }
+
+  /// Prefixes the message with what the command was for, keeping the details.
+  pub fn with_context(mut self, context: impl fmt::Display) -> Self {
+    self.message = format!("{context}: {}", self.message);
+    self
+  }
+}
+
+impl fmt::Display for CommandError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.message)
+  }
+}
+
+impl From<CommandError> for String {
+  fn from(err: CommandError) -> Self {
+    err.message
+  }
+}
+
 #[cfg(windows)]
 pub fn command_candidates(command: &str) -> [String; 3] {
   [
@@ -49,37 +111,57 @@ pub fn build_command(command: &str) -> Command {
   cmd
 }
 
+// Spawning a login shell is slow, so its PATH is kept until `refresh_path`.
+#[cfg(not(windows))]
+static SHELL_PATH_CACHE: std::sync::Mutex<Option<Option<String>>> = std::sync::Mutex::new(None);
+
+/// Forgets the cached login-shell PATH so the next command picks up tools
+/// installed since, e.g. right after a dependency install. Windows already
+/// re-reads PATH from the registry for every command.
+pub fn refresh_path() {
+  #[cfg(not(windows))]
+  {
+    *SHELL_PATH_CACHE.lock().unwrap_or_else(|err| err.into_inner()) = None;
+  }
+}
+
 #[cfg(not(windows))]
 fn shell_resolved_path() -> Option<String> {
-  use std::sync::OnceLock;
-  static CACHE: OnceLock<Option<String>> = OnceLock::new();
-
-  CACHE
-    .get_or_init(|| {
-      for shell in ["bash", "zsh", "sh"] {
-        if let Ok(output) = std::process::Command::new(shell)
-          .args(["-lc", "echo $PATH"])
-          .output()
-        {
-          if output.status.success() {
-            let path = String::from_utf8_lossy(&output.stdout)
-              .trim()
-              .to_string();
-
-            if !path.is_empty() {
-              log::debug!(
-                "Resolved login-shell PATH via {shell}: {path}"
-              );
-              return Some(path);
-            }
-          }
+  let mut cache = SHELL_PATH_CACHE.lock().unwrap_or_else(|err| err.into_inner());
+
+  if let Some(path) = cache.as_ref() {
+    return path.clone();
+  }
+
+  let path = login_shell_path();
+  *cache = Some(path.clone());
+  path
+}
+
+#[cfg(not(windows))]
+fn login_shell_path() -> Option<String> {
+  for shell in ["bash", "zsh", "sh"] {
+    if let Ok(output) = std::process::Command::new(shell)
+      .args(["-lc", "echo $PATH"])
+      .output()
+    {
+      if output.status.success() {
+        let path = String::from_utf8_lossy(&output.stdout)
+          .trim()
+          .to_string();
+
+        if !path.is_empty() {
+          log::debug!(
+            "Resolved login-shell PATH via {shell}: {path}"
+          );
+          return Some(path);
         }
       }
+    }
+  }
 
-      log::debug!("Could not resolve PATH from any login shell; falling back to inherited PATH");
-      None
-    })
-    .clone()
+  log::debug!("Could not resolve PATH from any login shell; falling back to inherited PATH");
+  None
 }
 
 #[cfg(not(windows))]
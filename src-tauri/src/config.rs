@@ -1,15 +1,192 @@
-use std::{fs, io, path::PathBuf};
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+  sync::OnceLock,
+};
+
+const APP_DIR_NAME: &str = "vencord-installer-gui";
+
+#[cfg(target_os = "windows")]
+fn env_dir(var: &str) -> Option<PathBuf> {
+  std::env::var_os(var)
+    .filter(|value| !value.is_empty())
+    .map(PathBuf::from)
+}
+
+// Per-user configuration root shared by every path the installer touches:
+// %APPDATA% on Windows, $XDG_CONFIG_HOME (or ~/.config) on Linux and
+// ~/Library/Application Support on macOS.
+pub fn config_base_dir() -> Option<PathBuf> {
+  #[cfg(target_os = "windows")]
+  {
+    if let Some(dir) = env_dir("APPDATA") {
+      return Some(dir);
+    }
+  }
+
+  dirs::config_dir()
+}
+
+// Per-user local data root: %LOCALAPPDATA% on Windows, the XDG data dir on
+// Linux and ~/Library/Application Support on macOS.
+pub fn local_data_base_dir() -> Option<PathBuf> {
+  #[cfg(target_os = "windows")]
+  {
+    if let Some(dir) = env_dir("LOCALAPPDATA") {
+      return Some(dir);
+    }
+  }
+
+  dirs::data_local_dir()
+}
+
+/// The directory the installer keeps its files in, decided once per run.
+pub struct ConfigDirState {
+  pub path: PathBuf,
+  /// The usual location, when it could not be used.
+  pub unwritable: Option<PathBuf>,
+}
+
+fn preferred_app_config_dir() -> Option<PathBuf> {
+  config_base_dir()
+    .or_else(dirs::home_dir)
+    .map(|base| base.join(APP_DIR_NAME))
+}
+
+fn usable_dir(dir: &Path) -> bool {
+  fs::create_dir_all(dir).is_ok() && is_writable_dir(dir)
+}
+
+// The shared temp dir is writable by every user, so the fallback there is
+// named per user and only used if it is a private folder we own; otherwise
+// another account could pre-create it and read or plant the installer's files.
+#[cfg(unix)]
+fn usable_private_dir(dir: &Path) -> bool {
+  use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+  let _ = fs::DirBuilder::new().mode(0o700).create(dir);
+
+  let Ok(meta) = fs::symlink_metadata(dir) else {
+    return false;
+  };
+
+  // SAFETY: getuid has no preconditions and cannot fail.
+  if !meta.is_dir() || meta.uid() != unsafe { libc::getuid() } {
+    return false;
+  }
+
+  if meta.permissions().mode() & 0o077 != 0
+    && fs::set_permissions(dir, fs::Permissions::from_mode(0o700)).is_err()
+  {
+    return false;
+  }
+
+  is_writable_dir(dir)
+}
+
+// %TEMP% is already inside the user's profile on Windows.
+#[cfg(not(unix))]
+fn usable_private_dir(dir: &Path) -> bool {
+  usable_dir(dir)
+}
+
+fn temp_fallback_dir() -> PathBuf {
+  #[cfg(unix)]
+  {
+    // SAFETY: getuid has no preconditions and cannot fail.
+    let uid = unsafe { libc::getuid() };
+    std::env::temp_dir().join(format!("{APP_DIR_NAME}-{uid}"))
+  }
+
+  #[cfg(not(unix))]
+  {
+    std::env::temp_dir().join(APP_DIR_NAME)
+  }
+}
+
+// Locked-down machines can have a config dir the user cannot write to; the
+// other per-user folders, then a private temp folder, keep the installer
+// working for the session instead of failing every command.
+fn resolve_config_dir() -> Result<ConfigDirState, String> {
+  let preferred = preferred_app_config_dir();
+
+  if let Some(dir) = preferred.as_ref().filter(|dir| usable_dir(dir)) {
+    return Ok(ConfigDirState {
+      path: dir.clone(),
+      unwritable: None,
+    });
+  }
+
+  let per_user = [local_data_base_dir(), dirs::cache_dir()]
+    .into_iter()
+    .flatten()
+    .map(|base| base.join(APP_DIR_NAME))
+    .filter(|dir| Some(dir) != preferred.as_ref());
+
+  for dir in per_user {
+    if usable_dir(&dir) {
+      return Ok(ConfigDirState {
+        path: dir,
+        unwritable: preferred,
+      });
+    }
+  }
+
+  let fallback = temp_fallback_dir();
+
+  if usable_private_dir(&fallback) {
+    return Ok(ConfigDirState {
+      path: fallback,
+      unwritable: preferred,
+    });
+  }
+
+  Err(match preferred {
+    Some(dir) => format!(
+      "Config directory not writable: {} (the fallbacks, including {}, are not usable either). Check the folders' permissions",
+      dir.display(),
+      fallback.display()
+    ),
+    None => format!(
+      "Config directory not found and the fallbacks, including {}, are not usable. Check the folders' permissions",
+      fallback.display()
+    ),
+  })
+}
+
+pub fn config_dir_state() -> Result<&'static ConfigDirState, String> {
+  static STATE: OnceLock<Result<ConfigDirState, String>> = OnceLock::new();
+
+  STATE.get_or_init(resolve_config_dir).as_ref().map_err(Clone::clone)
+}
 
 pub fn app_config_dir() -> io::Result<PathBuf> {
-  let base_dir = dirs::config_dir().or_else(dirs::home_dir).ok_or_else(|| {
-    io::Error::new(
-      io::ErrorKind::NotFound,
-      "Could not determine configuration directory",
-    )
-  })?;
+  let state = config_dir_state()
+    .map_err(|message| io::Error::new(io::ErrorKind::PermissionDenied, message))?;
+
+  // Recreated in case it was removed since startup (e.g. by a factory reset).
+  fs::create_dir_all(&state.path)?;
+
+  Ok(state.path.clone())
+}
+
+pub fn is_writable_dir(path: &Path) -> bool {
+  if !path.is_dir() {
+    return false;
+  }
 
-  let app_dir = base_dir.join("vencord-installer-gui");
-  fs::create_dir_all(&app_dir)?;
+  let probe = path.join(".vencord_installer_write_test");
 
-  Ok(app_dir)
+  match fs::OpenOptions::new()
+    .create(true)
+    .truncate(true)
+    .write(true)
+    .open(&probe)
+  {
+    Ok(_) => {
+      let _ = fs::remove_file(&probe);
+      true
+    }
+    Err(_) => false,
+  }
 }
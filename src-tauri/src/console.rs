@@ -0,0 +1,44 @@
+#[cfg(target_os = "windows")]
+const CONSOLE_ARG: &str = "--console";
+#[cfg(target_os = "windows")]
+const CONSOLE_ENV: &str = "VENCORD_INSTALLER_CONSOLE";
+
+#[cfg(target_os = "windows")]
+fn console_requested() -> bool {
+  std::env::args().skip(1).any(|arg| arg == CONSOLE_ARG)
+    || std::env::var(CONSOLE_ENV).map_or(false, |value| !value.is_empty() && value != "0")
+}
+
+/// Release builds on Windows use the GUI subsystem and start without a
+/// console, so output from before logging is set up (including panics) is
+/// lost. When `--console` or `VENCORD_INSTALLER_CONSOLE` is set, attach to the
+/// launching terminal or open a new console window for debugging.
+#[cfg(target_os = "windows")]
+pub fn attach_if_requested() {
+  const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn AttachConsole(process_id: u32) -> i32;
+    fn AllocConsole() -> i32;
+  }
+
+  if !console_requested() {
+    return;
+  }
+
+  // Safety: both calls take no pointers and only fail if a console is already
+  // attached, in which case output already has somewhere to go.
+  unsafe {
+    if AttachConsole(ATTACH_PARENT_PROCESS) == 0 {
+      AllocConsole();
+    }
+  }
+
+  eprintln!("[console] Debug console attached");
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn attach_if_requested() {
+  // Other platforms keep stdout/stderr attached to the launching terminal.
+}
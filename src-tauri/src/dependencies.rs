@@ -3,10 +3,12 @@ use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::io::ErrorKind;
+use std::time::Duration;
 use tauri::async_runtime::spawn_blocking;
 use tauri::Emitter;
 
-use crate::command_utils::{build_command, command_candidates};
+use crate::command_utils::{build_command, command_candidates, refresh_path};
+use crate::error::InstallerError;
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +18,8 @@ struct InstallCommand {
   args: Vec<String>,
   #[serde(default)]
   display_label: Option<String>,
+  #[serde(default)]
+  requires_elevation: bool,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -35,6 +39,8 @@ struct DependencySpec {
   args: Vec<String>,
   recommended_version: String,
   #[serde(default)]
+  minimum_version: Option<String>,
+  #[serde(default)]
   install_commands: Option<HashMap<String, InstallCommandEntry>>,
 }
 
@@ -44,6 +50,8 @@ pub struct DependencyStatus {
   pub id: String,
   pub name: String,
   pub recommended_version: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub minimum_version: Option<String>,
   pub installed_version: Option<String>,
   pub status: String,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,6 +69,9 @@ struct DependencyInstallEvent {
   message: Option<String>,
 }
 
+const VERIFY_ATTEMPTS: u32 = 3;
+const VERIFY_DELAY: Duration = Duration::from_millis(1500);
+
 static DEPENDENCIES: Lazy<Vec<DependencySpec>> = Lazy::new(|| {
   serde_json::from_str(include_str!("dependencies.json"))
     .expect("Failed to parse dependencies.json")
@@ -76,28 +87,68 @@ fn current_platform_key() -> &'static str {
   }
 }
 
-fn extract_version(output: &str) -> Option<String> {
-  for token in output.split_whitespace() {
-    let mut cleaned = String::new();
-    let mut seen_digit = false;
-
-    for ch in token.trim_start_matches("v").chars() {
-      if ch.is_ascii_digit() {
-        cleaned.push(ch);
-        seen_digit = true;
-      } else if ch == '.' && seen_digit {
-        cleaned.push(ch);
-      } else if seen_digit {
-        break;
+/// Parses a single token such as `v22.14.0`, `2.39.2.windows.1` or
+/// `9.1.0-rc.1` into its leading dotted numeric part.
+fn parse_version_token(token: &str) -> Option<String> {
+  let token = token
+    .trim_matches(|ch: char| matches!(ch, ',' | ';' | '(' | ')' | '"' | '\''))
+    .trim_start_matches(['v', 'V']);
+
+  if !token.starts_with(|ch: char| ch.is_ascii_digit()) {
+    return None;
+  }
+
+  let version: String = token
+    .chars()
+    .take_while(|ch| ch.is_ascii_digit() || *ch == '.')
+    .collect();
+  let version = version.trim_end_matches('.');
+
+  version.contains('.').then(|| version.to_string())
+}
+
+/// Picks the version of `command` out of its `--version` output. Prefers the
+/// token right after the command's own name (`pnpm: 9.1.0`, `git version
+/// 2.43.0`), then a line that is nothing but a version, and finally the last
+/// version on the first line that has one. Banners printed by wrappers such as
+/// Corepack would otherwise be mistaken for the tool's version.
+fn extract_version(output: &str, command: &str) -> Option<String> {
+  let lines: Vec<&str> = output.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+
+  for line in &lines {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+
+    for (index, token) in tokens.iter().enumerate() {
+      if !token.trim_end_matches(':').eq_ignore_ascii_case(command) {
+        continue;
       }
-    }
 
-    if cleaned.contains('.') {
-      return Some(cleaned.trim_end_matches('.').to_string());
+      let version = tokens[index + 1..]
+        .iter()
+        .find(|next| !next.eq_ignore_ascii_case("version"))
+        .and_then(|next| parse_version_token(next));
+
+      if version.is_some() {
+        return version;
+      }
     }
   }
 
-  None
+  if let Some(version) = lines
+    .iter()
+    .rev()
+    .find(|line| !line.contains(char::is_whitespace))
+    .and_then(|line| parse_version_token(line))
+  {
+    return Some(version);
+  }
+
+  lines.iter().find_map(|line| {
+    line
+      .split_whitespace()
+      .rev()
+      .find_map(parse_version_token)
+  })
 }
 
 #[cfg(target_os = "linux")]
@@ -217,6 +268,84 @@ fn shell_escape(arg: &str) -> String {
   format!("'{}'", arg.replace('\'', "'\"'\"'"))
 }
 
+#[cfg(windows)]
+fn elevated_invocation(command: &str, args: &[String]) -> (String, Vec<String>) {
+  fn ps_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+  }
+
+  let argument_list = if args.is_empty() {
+    String::new()
+  } else {
+    format!(
+      " -ArgumentList @({})",
+      args.iter().map(|arg| ps_quote(arg)).collect::<Vec<_>>().join(",")
+    )
+  };
+
+  let script = format!(
+    "$p = Start-Process -FilePath {}{argument_list} -Verb RunAs -Wait -PassThru -WindowStyle Hidden; exit $p.ExitCode",
+    ps_quote(command)
+  );
+
+  (
+    "powershell".to_string(),
+    vec!["-NoProfile".to_string(), "-Command".to_string(), script],
+  )
+}
+
+#[cfg(target_os = "macos")]
+fn elevated_invocation(command: &str, args: &[String]) -> (String, Vec<String>) {
+  let shell_command = std::iter::once(command)
+    .chain(args.iter().map(String::as_str))
+    .map(shell_escape)
+    .collect::<Vec<_>>()
+    .join(" ");
+
+  let script = format!(
+    "do shell script \"{}\" with administrator privileges",
+    shell_command.replace('\\', "\\\\").replace('"', "\\\"")
+  );
+
+  ("osascript".to_string(), vec!["-e".to_string(), script])
+}
+
+#[cfg(all(not(windows), not(target_os = "macos")))]
+fn elevated_invocation(command: &str, args: &[String]) -> (String, Vec<String>) {
+  let mut pkexec_args = vec![command.to_string()];
+  pkexec_args.extend(args.iter().cloned());
+
+  ("pkexec".to_string(), pkexec_args)
+}
+
+/// Whether `program` failed because the user dismissed its prompt. Each
+/// elevator reports that differently, and 126/127 from anything but pkexec
+/// mean the command itself could not run.
+fn is_elevation_declined(program: &str, err: &str) -> bool {
+  let lower = err.to_lowercase();
+
+  match program {
+    // Dismissed (126) or not authorized (127).
+    "pkexec" => lower.contains("exit status: 126") || lower.contains("exit status: 127"),
+    // "User canceled." (-128).
+    "osascript" => lower.contains("user canceled") || lower.contains("(-128)"),
+    // UAC cancellation (ERROR_CANCELLED).
+    _ => lower.contains("canceled by the user"),
+  }
+}
+
+fn run_elevated(command: &str, args: &[String]) -> Result<String, String> {
+  let (program, program_args) = elevated_invocation(command, args);
+
+  run_command(&program, &program_args).map_err(|err| {
+    if is_elevation_declined(&program, &err) {
+      "Administrator permission was declined; the install was not run.".to_string()
+    } else {
+      err
+    }
+  })
+}
+
 #[cfg(not(windows))]
 fn detect_with_nvm(spec: &DependencySpec) -> Result<Option<String>, String> {
   if !matches!(spec.command.as_str(), "node" | "npm" | "pnpm") {
@@ -249,7 +378,7 @@ fi
       Ok(output) => {
         if output.status.success() {
           let stdout = String::from_utf8_lossy(&output.stdout);
-          return Ok(extract_version(&stdout));
+          return Ok(extract_version(&stdout, &spec.command));
         }
 
         if output.status.code() == Some(127) {
@@ -294,7 +423,7 @@ fn detect_installed_version(spec: &DependencySpec) -> Result<Option<String>, Str
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let version = extract_version(&stdout);
+        let version = extract_version(&stdout, &spec.command);
         return Ok(version);
       }
       Err(err) => {
@@ -323,18 +452,43 @@ fn detect_installed_version(spec: &DependencySpec) -> Result<Option<String>, Str
   }
 }
 
+fn is_below_minimum(spec: &DependencySpec, installed: &str) -> bool {
+  spec
+    .minimum_version
+    .as_deref()
+    .and_then(|minimum| compare_versions(installed, minimum))
+    .map_or(false, |ordering| ordering == Ordering::Less)
+}
 
 fn build_status(spec: &DependencySpec) -> DependencyStatus {
   let install_cmd = resolve_install_command(spec);
 
   match detect_installed_version(spec) {
     Ok(Some(installed)) => {
+      if is_below_minimum(spec, &installed) {
+        return DependencyStatus {
+          id: spec.id.clone(),
+          name: spec.name.clone(),
+          recommended_version: spec.recommended_version.clone(),
+          minimum_version: spec.minimum_version.clone(),
+          installed_version: Some(installed.clone()),
+          status: "unsupported".to_string(),
+          message: Some(format!(
+            "Version {installed} is below the minimum supported version {}",
+            spec.minimum_version.as_deref().unwrap_or_default()
+          )),
+          can_install: install_cmd.is_some(),
+          install_label: install_cmd.and_then(|cmd| cmd.display_label.clone()),
+        };
+      }
+
       if let Some(ordering) = compare_versions(&installed, &spec.recommended_version) {
         if ordering == Ordering::Less {
           return DependencyStatus {
             id: spec.id.clone(),
             name: spec.name.clone(),
             recommended_version: spec.recommended_version.clone(),
+            minimum_version: spec.minimum_version.clone(),
             installed_version: Some(installed.clone()),
             status: "outdated".to_string(),
             message: Some(format!(
@@ -351,6 +505,7 @@ fn build_status(spec: &DependencySpec) -> DependencyStatus {
         id: spec.id.clone(),
         name: spec.name.clone(),
         recommended_version: spec.recommended_version.clone(),
+        minimum_version: spec.minimum_version.clone(),
         installed_version: Some(installed),
         status: "installed".to_string(),
         message: None,
@@ -362,6 +517,7 @@ fn build_status(spec: &DependencySpec) -> DependencyStatus {
       id: spec.id.clone(),
       name: spec.name.clone(),
       recommended_version: spec.recommended_version.clone(),
+      minimum_version: spec.minimum_version.clone(),
       installed_version: None,
       status: "missing".to_string(),
       message: Some("Not detected in PATH".to_string()),
@@ -372,6 +528,7 @@ fn build_status(spec: &DependencySpec) -> DependencyStatus {
       id: spec.id.clone(),
       name: spec.name.clone(),
       recommended_version: spec.recommended_version.clone(),
+      minimum_version: spec.minimum_version.clone(),
       installed_version: None,
       status: "error".to_string(),
       message: Some(err),
@@ -381,6 +538,36 @@ fn build_status(spec: &DependencySpec) -> DependencyStatus {
   }
 }
 
+fn meets_recommended(spec: &DependencySpec, installed: &str) -> bool {
+  compare_versions(installed, &spec.recommended_version)
+    .map_or(false, |ordering| ordering != Ordering::Less)
+}
+
+// Package managers can finish before the new binary is visible on PATH, so
+// re-detect a few times before reporting what was found.
+fn wait_for_updated_version(spec: &DependencySpec) -> Option<String> {
+  let mut detected = None;
+
+  for attempt in 0..VERIFY_ATTEMPTS {
+    if attempt > 0 {
+      std::thread::sleep(VERIFY_DELAY);
+    }
+
+    // The install may have added a new PATH entry, e.g. a fresh pnpm home.
+    refresh_path();
+    detected = detect_installed_version(spec).ok().flatten();
+
+    if detected
+      .as_deref()
+      .map_or(false, |version| meets_recommended(spec, version))
+    {
+      break;
+    }
+  }
+
+  detected
+}
+
 fn render_install_args(template_args: &[String], version: &str) -> Vec<String> {
   template_args
     .iter()
@@ -388,8 +575,37 @@ fn render_install_args(template_args: &[String], version: &str) -> Vec<String> {
     .collect()
 }
 
+pub fn ensure_minimum_versions() -> Result<(), String> {
+  let unsupported: Vec<String> = DEPENDENCIES
+    .iter()
+    .filter(|spec| spec.minimum_version.is_some())
+    .filter_map(|spec| {
+      let installed = detect_installed_version(spec).ok().flatten()?;
+
+      if is_below_minimum(spec, &installed) {
+        Some(format!(
+          "{} {installed} (minimum {})",
+          spec.name,
+          spec.minimum_version.as_deref().unwrap_or_default()
+        ))
+      } else {
+        None
+      }
+    })
+    .collect();
+
+  if unsupported.is_empty() {
+    Ok(())
+  } else {
+    Err(format!(
+      "Installed tool version is below the minimum supported: {}. Update it from the Dependencies panel before building.",
+      unsupported.join(", ")
+    ))
+  }
+}
+
 #[tauri::command]
-pub fn list_dependencies() -> Result<Vec<DependencyStatus>, String> {
+pub fn list_dependencies() -> Result<Vec<DependencyStatus>, InstallerError> {
   Ok(DEPENDENCIES.iter().map(build_status).collect())
 }
 
@@ -397,18 +613,20 @@ pub fn list_dependencies() -> Result<Vec<DependencyStatus>, String> {
 pub async fn install_dependency(
   app: tauri::AppHandle,
   id: String,
-) -> Result<DependencyStatus, String> {
+) -> Result<DependencyStatus, InstallerError> {
   let spec = DEPENDENCIES
     .iter()
     .find(|entry| entry.id == id)
     .cloned()
-    .ok_or_else(|| format!("Unknown dependency {id}"))?;
+    .ok_or_else(|| InstallerError::not_found(format!("Unknown dependency {id}")))?;
 
-  let install = resolve_install_command(&spec)
-    .ok_or_else(|| format!("No automated install configured for {}", spec.name))?;
+  let install = resolve_install_command(&spec).ok_or_else(|| {
+    InstallerError::dependency(format!("No automated install configured for {}", spec.name))
+  })?;
 
   let args = render_install_args(&install.args, &spec.recommended_version);
   let command = install.command.clone();
+  let requires_elevation = install.requires_elevation;
 
   app
     .emit(
@@ -421,9 +639,31 @@ pub async fn install_dependency(
     )
     .ok();
 
-  let run_result = spawn_blocking(move || run_command(&command, &args))
-    .await
-    .map_err(|err| err.to_string())?;
+  if requires_elevation {
+    app
+      .emit(
+        "dependency-install",
+        DependencyInstallEvent {
+          id: spec.id.clone(),
+          status: "elevation".to_string(),
+          message: Some(format!(
+            "Approve the administrator prompt to continue installing {}",
+            spec.name
+          )),
+        },
+      )
+      .ok();
+  }
+
+  let run_result = spawn_blocking(move || {
+    if requires_elevation {
+      run_elevated(&command, &args)
+    } else {
+      run_command(&command, &args)
+    }
+  })
+  .await
+  .map_err(|err| err.to_string())?;
 
   if let Err(err) = run_result {
     app
@@ -437,11 +677,41 @@ pub async fn install_dependency(
       )
       .ok();
 
-    return Err(err);
+    return Err(InstallerError::dependency(err));
   }
 
+  let detected = spawn_blocking({
+    let spec = spec.clone();
+    move || wait_for_updated_version(&spec)
+  })
+  .await
+  .map_err(|err| err.to_string())?;
+
   let status = build_status(&spec);
 
+  if !detected
+    .as_deref()
+    .map_or(false, |version| meets_recommended(&spec, version))
+  {
+    app
+      .emit(
+        "dependency-install",
+        DependencyInstallEvent {
+          id: spec.id.clone(),
+          status: "warning".to_string(),
+          message: Some(format!(
+            "Install finished but detected {} version {} (expected {}). Restart your shell or the installer so the updated PATH is picked up.",
+            spec.name,
+            detected.as_deref().unwrap_or("none"),
+            spec.recommended_version
+          )),
+        },
+      )
+      .ok();
+
+    return Ok(status);
+  }
+
   app
     .emit(
       "dependency-install",
@@ -454,4 +724,52 @@ pub async fn install_dependency(
     .ok();
 
   Ok(status)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_version_tokens() {
+    assert_eq!(parse_version_token("v22.14.0").as_deref(), Some("22.14.0"));
+    assert_eq!(parse_version_token("2.39.2.windows.1").as_deref(), Some("2.39.2"));
+    assert_eq!(parse_version_token("9.1.0-rc.1").as_deref(), Some("9.1.0"));
+    assert_eq!(parse_version_token("(v18.19.1)").as_deref(), Some("18.19.1"));
+    assert_eq!(parse_version_token("version"), None);
+    assert_eq!(parse_version_token("22"), None);
+  }
+
+  #[test]
+  fn extracts_plain_tool_output() {
+    assert_eq!(extract_version("v22.14.0\n", "node").as_deref(), Some("22.14.0"));
+    assert_eq!(
+      extract_version("git version 2.43.0\n", "git").as_deref(),
+      Some("2.43.0")
+    );
+    assert_eq!(
+      extract_version("git version 2.39.2.windows.1\r\n", "git").as_deref(),
+      Some("2.39.2")
+    );
+    assert_eq!(extract_version("9.15.4\n", "pnpm").as_deref(), Some("9.15.4"));
+  }
+
+  #[test]
+  fn ignores_wrapper_banners() {
+    let corepack = "! Corepack is about to download https://registry.npmjs.org/pnpm/-/pnpm-9.15.4.tgz\n9.15.4\n";
+    assert_eq!(extract_version(corepack, "pnpm").as_deref(), Some("9.15.4"));
+
+    let nvm = "Now using node v20.11.1 (npm v10.2.4)\nv20.11.1\n";
+    assert_eq!(extract_version(nvm, "node").as_deref(), Some("20.11.1"));
+
+    let labelled = "Using Corepack 0.31.0\npnpm: 8.15.9\n";
+    assert_eq!(extract_version(labelled, "pnpm").as_deref(), Some("8.15.9"));
+  }
+
+  #[test]
+  fn only_pkexec_exit_codes_count_as_declined() {
+    assert!(is_elevation_declined("pkexec", "pkexec exited with exit status: 126"));
+    assert!(!is_elevation_declined("bash", "bash exited with exit status: 127"));
+    assert!(is_elevation_declined("osascript", "execution error: User canceled. (-128)"));
+    assert!(!is_elevation_declined("osascript", "exit status: 126"));
+  }
+}
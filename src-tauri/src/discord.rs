@@ -1,11 +1,45 @@
+use chrono::{DateTime, Local};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
+use crate::{config, options};
+
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DiscordInstall {
   pub id: String,
   pub name: String,
   pub path: String,
+  /// RFC 3339 time the client files last changed, to spot a Discord update
+  /// that replaced a patched install.
+  pub last_updated: Option<String>,
+}
+
+/// The newest `app-<version>` folder for Squirrel installs (Windows), the
+/// bundle itself on macOS and `resources` on Linux, falling back to the
+/// install folder.
+fn last_updated(path: &Path) -> Option<String> {
+  let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+
+  let newest_app_dir = std::fs::read_dir(path).ok().and_then(|entries| {
+    entries
+      .filter_map(Result::ok)
+      .filter(|entry| entry.file_name().to_string_lossy().starts_with("app-"))
+      .filter_map(|entry| modified(&entry.path()))
+      .max()
+  });
+
+  let time = newest_app_dir
+    .or_else(|| {
+      if path.extension().is_some_and(|ext| ext == "app") {
+        modified(path)
+      } else {
+        modified(&path.join("resources"))
+      }
+    })
+    .or_else(|| modified(path))?;
+
+  Some(DateTime::<Local>::from(time).to_rfc3339())
 }
 
 fn resolve_candidate_path(path: &Path) -> Option<PathBuf> {
@@ -28,7 +62,7 @@ fn resolve_candidate_path(path: &Path) -> Option<PathBuf> {
   None
 }
 
-fn add_candidates(installs: &mut Vec<DiscordInstall>, candidates: &[(&str, &str, PathBuf)]) {
+fn add_candidates<S: AsRef<str>>(installs: &mut Vec<DiscordInstall>, candidates: &[(S, S, PathBuf)]) {
   for (id, name, path) in candidates {
     if let Some(resolved_path) = resolve_candidate_path(path) {
       let resolved = resolved_path.to_string_lossy().into_owned();
@@ -38,15 +72,16 @@ fn add_candidates(installs: &mut Vec<DiscordInstall>, candidates: &[(&str, &str,
       }
 
       installs.push(DiscordInstall {
-        id: (*id).to_string(),
-        name: (*name).to_string(),
+        id: id.as_ref().to_string(),
+        name: name.as_ref().to_string(),
+        last_updated: last_updated(&resolved_path),
         path: resolved,
       });
     }
   }
 }
 
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "windows"))]
 fn parse_version_tuple(name: &str) -> Option<Vec<u32>> {
   let mut parts = Vec::new();
 
@@ -66,8 +101,10 @@ fn parse_version_tuple(name: &str) -> Option<Vec<u32>> {
   Some(parts)
 }
 
-#[cfg(target_os = "linux")]
-fn latest_versioned_subdir(base: &Path) -> Option<PathBuf> {
+/// The subdirectory of `base` named `<prefix><version>` with the highest
+/// version, e.g. `0.0.76` (`prefix` empty) or `app-1.0.9` (`prefix` `app-`).
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+pub fn latest_versioned_subdir(base: &Path, prefix: &str) -> Option<PathBuf> {
   let entries = std::fs::read_dir(base).ok()?;
 
   entries
@@ -78,7 +115,7 @@ fn latest_versioned_subdir(base: &Path) -> Option<PathBuf> {
         return None;
       }
 
-      let name = path.file_name()?.to_str()?;
+      let name = path.file_name()?.to_str()?.strip_prefix(prefix)?;
       let version = parse_version_tuple(name)?;
 
       Some((version, path))
@@ -87,17 +124,107 @@ fn latest_versioned_subdir(base: &Path) -> Option<PathBuf> {
     .map(|(_, path)| path)
 }
 
+/// Wine prefixes that may hold a Windows Discord: `$WINEPREFIX`, `~/.wine`,
+/// Bottles (native and Flatpak) and Lutris' default `~/Games` folder.
+#[cfg(target_os = "linux")]
+fn wine_prefixes() -> Vec<PathBuf> {
+  let mut prefixes: Vec<PathBuf> = std::env::var_os("WINEPREFIX")
+    .map(PathBuf::from)
+    .into_iter()
+    .collect();
+
+  let Some(home) = dirs::home_dir() else {
+    return prefixes;
+  };
+
+  prefixes.push(home.join(".wine"));
+
+  let prefix_parents = [
+    home.join(".local/share/bottles/bottles"),
+    home.join(".var/app/com.usebottles.bottles/data/bottles/bottles"),
+    home.join("Games"),
+  ];
+
+  for parent in prefix_parents {
+    if let Ok(entries) = std::fs::read_dir(&parent) {
+      prefixes.extend(entries.filter_map(Result::ok).map(|entry| entry.path()));
+    }
+  }
+
+  prefixes.retain(|prefix| prefix.join("drive_c").is_dir());
+  prefixes.dedup();
+  prefixes
+}
+
+#[cfg(target_os = "linux")]
+fn id_slug(value: &str) -> String {
+  value
+    .to_lowercase()
+    .split(|ch: char| !ch.is_ascii_alphanumeric())
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join("-")
+}
+
+/// Windows Discord installs inside Wine prefixes. Each path is the install
+/// folder, as on Windows. Ids are built from the prefix path (relative to the
+/// home folder), plus the Windows user when one prefix has several, so every
+/// install can be listed and selected on its own.
+#[cfg(target_os = "linux")]
+fn wine_candidates() -> Vec<(String, String, PathBuf)> {
+  const CLIENTS: &[(&str, &str, &str)] = &[
+    ("stable", "Discord Stable", "Discord"),
+    ("ptb", "Discord PTB", "DiscordPTB"),
+    ("canary", "Discord Canary", "DiscordCanary"),
+  ];
+
+  let mut candidates = Vec::new();
+
+  for prefix in wine_prefixes() {
+    let Ok(users) = std::fs::read_dir(prefix.join("drive_c/users")) else {
+      continue;
+    };
+    let relative = dirs::home_dir()
+      .and_then(|home| prefix.strip_prefix(home).ok().map(Path::to_path_buf))
+      .unwrap_or_else(|| prefix.clone());
+    let slug = id_slug(&relative.to_string_lossy());
+    let label = prefix
+      .file_name()
+      .map(|name| name.to_string_lossy().into_owned())
+      .unwrap_or_else(|| prefix.display().to_string());
+
+    for user in users.filter_map(Result::ok) {
+      let local_app_data = user.path().join("AppData/Local");
+
+      for (id, name, folder) in CLIENTS {
+        let base = local_app_data.join(folder);
+
+        let has_app = latest_versioned_subdir(&base, "app-")
+          .is_some_and(|app_dir| app_dir.join("resources").is_dir());
+        if !has_app {
+          continue;
+        }
+
+        let mut install_id = format!("{id}-wine-{slug}");
+        if candidates.iter().any(|(existing, _, _)| existing == &install_id) {
+          install_id = format!("{install_id}-{}", id_slug(&user.file_name().to_string_lossy()));
+        }
+
+        candidates.push((install_id, format!("{name} (Wine: {label})"), base));
+      }
+    }
+  }
+
+  candidates
+}
+
 fn detect_discord_installs() -> Vec<DiscordInstall> {
   let mut installs = Vec::new();
 
   #[cfg(target_os = "windows")]
   {
-    use std::env;
-
-    let add_from_env = |var: &str, installs: &mut Vec<DiscordInstall>| {
-      if let Ok(path) = env::var(var) {
-        let base = PathBuf::from(path);
-
+    let add_from_base = |base: Option<PathBuf>, installs: &mut Vec<DiscordInstall>| {
+      if let Some(base) = base {
         let candidates = [
           ("stable", "Discord Stable", base.join("Discord")),
           ("ptb", "Discord PTB", base.join("DiscordPTB")),
@@ -108,10 +235,10 @@ fn detect_discord_installs() -> Vec<DiscordInstall> {
       }
     };
 
-    add_from_env("LOCALAPPDATA", &mut installs);
+    add_from_base(config::local_data_base_dir(), &mut installs);
 
     if installs.is_empty() {
-      add_from_env("APPDATA", &mut installs);
+      add_from_base(config::config_base_dir(), &mut installs);
     }
   }
 
@@ -181,8 +308,7 @@ fn detect_discord_installs() -> Vec<DiscordInstall> {
 
     add_candidates(&mut installs, &system_candidates);
 
-    if let Some(home) = dirs::home_dir() {
-      let config = home.join(".config");
+    if let Some(config) = config::config_base_dir() {
       let stable_base = config.join("discord");
       let ptb_base = config.join("discordptb");
       let canary_base = config.join("discordcanary");
@@ -191,22 +317,24 @@ fn detect_discord_installs() -> Vec<DiscordInstall> {
         (
           "stable",
           "Discord Stable",
-          latest_versioned_subdir(&stable_base).unwrap_or(stable_base),
+          latest_versioned_subdir(&stable_base, "").unwrap_or(stable_base),
         ),
         (
           "ptb",
           "Discord PTB",
-          latest_versioned_subdir(&ptb_base).unwrap_or(ptb_base),
+          latest_versioned_subdir(&ptb_base, "").unwrap_or(ptb_base),
         ),
         (
           "canary",
           "Discord Canary",
-          latest_versioned_subdir(&canary_base).unwrap_or(canary_base),
+          latest_versioned_subdir(&canary_base, "").unwrap_or(canary_base),
         ),
       ];
 
       add_candidates(&mut installs, &config_candidates);
+    }
 
+    if let Some(home) = dirs::home_dir() {
       let flatpak_stable_base = home.join(".var/app/com.discordapp.Discord/config/discord");
       let flatpak_ptb_base = home.join(".var/app/com.discordapp.DiscordPTB/config/discordptb");
       let flatpak_canary_base =
@@ -216,28 +344,32 @@ fn detect_discord_installs() -> Vec<DiscordInstall> {
         (
           "stable",
           "Discord Stable (Flatpak)",
-          latest_versioned_subdir(&flatpak_stable_base).unwrap_or(flatpak_stable_base),
+          latest_versioned_subdir(&flatpak_stable_base, "").unwrap_or(flatpak_stable_base),
         ),
         (
           "ptb",
           "Discord PTB (Flatpak)",
-          latest_versioned_subdir(&flatpak_ptb_base).unwrap_or(flatpak_ptb_base),
+          latest_versioned_subdir(&flatpak_ptb_base, "").unwrap_or(flatpak_ptb_base),
         ),
         (
           "canary",
           "Discord Canary (Flatpak)",
-          latest_versioned_subdir(&flatpak_canary_base).unwrap_or(flatpak_canary_base),
+          latest_versioned_subdir(&flatpak_canary_base, "").unwrap_or(flatpak_canary_base),
         ),
       ];
 
       add_candidates(&mut installs, &flatpak_candidates);
     }
+
+    // Walking every prefix is slow, so this only runs when enabled.
+    if options::read_user_options().is_ok_and(|options| options.detect_wine_installs) {
+      add_candidates(&mut installs, &wine_candidates());
+    }
   }
 
   #[cfg(target_os = "macos")]
   {
-    if let Some(home) = dirs::home_dir() {
-      let app_support = home.join("Library").join("Application Support");
+    if let Some(app_support) = config::config_base_dir() {
       let candidates = [
         ("stable", "Discord Stable", app_support.join("discord")),
         ("ptb", "Discord PTB", app_support.join("discordptb")),
@@ -255,8 +387,84 @@ fn detect_discord_installs() -> Vec<DiscordInstall> {
   installs
 }
 
+// Client executables, which must be files so a folder merely named
+// `Discord` is not taken for an install.
+const INSTALL_EXECUTABLES: &[&str] = &[
+  "Discord.exe",
+  "DiscordPTB.exe",
+  "DiscordCanary.exe",
+  "Discord",
+  "DiscordPTB",
+  "DiscordCanary",
+];
+
+const INSTALL_ASARS: &[&str] = &["resources/app.asar", "Contents/Resources/app.asar"];
+
+pub fn looks_like_discord_install(path: &Path) -> bool {
+  if !path.is_dir() {
+    return false;
+  }
+
+  if INSTALL_EXECUTABLES.iter().any(|exe| path.join(exe).is_file())
+    || INSTALL_ASARS.iter().any(|asar| path.join(asar).is_file())
+  {
+    return true;
+  }
+
+  // Squirrel installs keep the app in versioned `app-x.y.z` folders.
+  std::fs::read_dir(path)
+    .map(|entries| {
+      entries.filter_map(Result::ok).any(|entry| {
+        entry.file_name().to_string_lossy().starts_with("app-")
+          && entry.path().join("resources").is_dir()
+      })
+    })
+    .unwrap_or(false)
+}
+
+/// User-configured installs that pass `looks_like_discord_install`.
+pub fn custom_installs() -> Vec<DiscordInstall> {
+  let Ok(options) = options::read_user_options() else {
+    return Vec::new();
+  };
+
+  options
+    .custom_discord_installs
+    .into_iter()
+    .filter_map(|custom| {
+      let path = PathBuf::from(custom.path.trim());
+
+      if !looks_like_discord_install(&path) {
+        log::warn!(
+          "[discord] Ignoring custom install {} at {}: not a Discord install directory",
+          custom.id,
+          path.display()
+        );
+        return None;
+      }
+
+      let resolved = dunce::canonicalize(&path).unwrap_or(path);
+
+      Some(DiscordInstall {
+        id: custom.id,
+        name: custom.name,
+        path: resolved.to_string_lossy().into_owned(),
+        last_updated: last_updated(&resolved),
+      })
+    })
+    .collect()
+}
+
 pub fn detect_all_installs() -> Vec<DiscordInstall> {
-  detect_discord_installs()
+  let mut installs = detect_discord_installs();
+
+  for custom in custom_installs() {
+    if !installs.iter().any(|install| install.path == custom.path) {
+      installs.push(custom);
+    }
+  }
+
+  installs
 }
 
 #[tauri::command]
@@ -275,3 +483,34 @@ pub fn get_discord_installs() -> Vec<DiscordInstall> {
     })
     .collect()
 }
+
+/// Directories holding a client's user data (`settings.json` and friends),
+/// most likely first. These differ from the install paths on Windows and for
+/// system packages on Linux. Empty for ids other than stable, ptb and canary.
+pub fn user_data_dirs(id: &str) -> Vec<PathBuf> {
+  let folder = match id {
+    "stable" => "discord",
+    "ptb" => "discordptb",
+    "canary" => "discordcanary",
+    _ => return Vec::new(),
+  };
+
+  let mut dirs_found = Vec::new();
+
+  if let Some(base) = config::config_base_dir() {
+    dirs_found.push(base.join(folder));
+  }
+
+  #[cfg(target_os = "linux")]
+  if let Some(home) = dirs::home_dir() {
+    let app_id = match id {
+      "ptb" => "com.discordapp.DiscordPTB",
+      "canary" => "com.discordapp.DiscordCanary",
+      _ => "com.discordapp.Discord",
+    };
+
+    dirs_found.push(home.join(".var/app").join(app_id).join("config").join(folder));
+  }
+
+  dirs_found
+}
@@ -0,0 +1,156 @@
+use serde::Serialize;
+
+use crate::{
+  config::{app_config_dir, config_dir_state, is_writable_dir},
+  flows::{
+    backup, discord_clients,
+    repo::{self, BuildTools},
+    sources::{self, SourceKind},
+    themes,
+  },
+  error::InstallerError,
+  logging, options,
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentCheck {
+  pub check: String,
+  pub ok: bool,
+  pub detail: String,
+}
+
+impl EnvironmentCheck {
+  fn from_result(check: impl Into<String>, result: Result<String, String>) -> Self {
+    let (ok, detail) = match result {
+      Ok(detail) => (true, detail),
+      Err(detail) => (false, detail),
+    };
+
+    Self {
+      check: check.into(),
+      ok,
+      detail,
+    }
+  }
+}
+
+fn check_config_dir() -> Result<String, String> {
+  let dir = app_config_dir().map_err(|err| format!("Failed to resolve config directory: {err}"))?;
+
+  if is_writable_dir(&dir) {
+    Ok(dir.display().to_string())
+  } else {
+    Err(format!("{} is not writable", dir.display()))
+  }
+}
+
+fn check_repo_location(options: &options::UserOptions) -> Result<String, String> {
+  match repo::cloud_sync_warning(&options.vencord_repo_dir) {
+    Some(warning) => Err(warning),
+    None => Ok(options.vencord_repo_dir.clone()),
+  }
+}
+
+fn check_discord_installs() -> Result<String, String> {
+  let statuses = discord_clients::get_discord_status();
+  let installed: Vec<String> = statuses
+    .iter()
+    .filter(|status| status.installed)
+    .map(|status| {
+      let state = if status.running { "running" } else { "not running" };
+      format!("{} ({state})", status.name)
+    })
+    .collect();
+
+  if installed.is_empty() {
+    Err("No Discord installations found".to_string())
+  } else {
+    Ok(installed.join(", "))
+  }
+}
+
+/// Probes everything the patch flow depends on without changing anything, so
+/// the report can be attached to bug reports.
+#[tauri::command]
+pub fn run_environment_check() -> Vec<EnvironmentCheck> {
+  let options = options::read_user_options();
+  let tools = options
+    .as_ref()
+    .map(BuildTools::from_options)
+    .unwrap_or_default();
+
+  let mut checks = vec![
+    EnvironmentCheck::from_result("Config directory writable", check_config_dir()),
+    EnvironmentCheck::from_result("Git available", repo::ensure_git_available()),
+    EnvironmentCheck::from_result("Node.js available", repo::tool_version(&tools, "node", "Node.js")),
+    EnvironmentCheck::from_result("pnpm available", repo::tool_version(&tools, "pnpm", "pnpm")),
+    EnvironmentCheck::from_result("Discord installs found", check_discord_installs()),
+  ];
+
+  match &options {
+    Ok(options) => checks.push(EnvironmentCheck::from_result(
+      "Vencord folder not cloud-synced",
+      check_repo_location(options),
+    )),
+    Err(err) => checks.push(EnvironmentCheck::from_result("Options readable", Err(err.clone()))),
+  }
+
+  match sources::test_sources() {
+    Ok(results) => checks.extend(results.into_iter().map(|source| {
+      let kind = match source.kind {
+        SourceKind::Repository => "Repository reachable",
+        SourceKind::Theme => "Theme host reachable",
+      };
+      let detail = match source.error {
+        Some(err) => format!("{}: {err}", source.url),
+        None => source.url,
+      };
+
+      EnvironmentCheck {
+        check: kind.to_string(),
+        ok: source.reachable,
+        detail,
+      }
+    })),
+    Err(err) => checks.push(EnvironmentCheck::from_result("Sources reachable", Err(err.to_string()))),
+  }
+
+  checks
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPaths {
+  pub config_dir: String,
+  /// Set when the usual config directory is not writable and `config_dir`
+  /// is a temporary fallback.
+  pub unwritable_config_dir: Option<String>,
+  pub backups_dir: String,
+  pub logs_dir: String,
+  pub theme_dir: String,
+  pub repo_dir: String,
+}
+
+/// The directories the installer actually uses, after applying the user's
+/// overrides.
+#[tauri::command]
+pub fn get_paths() -> Result<AppPaths, InstallerError> {
+  let display = |path: std::path::PathBuf| path.to_string_lossy().into_owned();
+
+  Ok(AppPaths {
+    config_dir: display(app_config_dir().map_err(|err| {
+      InstallerError::filesystem(format!("Failed to resolve config directory: {err}"))
+    })?),
+    unwritable_config_dir: config_dir_state()
+      .ok()
+      .and_then(|state| state.unwritable.clone())
+      .map(display),
+    backups_dir: display(backup::backups_root().map_err(InstallerError::filesystem)?),
+    logs_dir: display(logging::installer_logs_dir().map_err(|err| {
+      InstallerError::filesystem(format!("Failed to resolve logs directory: {err}"))
+    })?),
+    theme_dir: display(themes::theme_dir().map_err(InstallerError::filesystem)?),
+    repo_dir: options::read_user_options().map_err(InstallerError::filesystem)?.vencord_repo_dir,
+  })
+}
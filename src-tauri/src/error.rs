@@ -0,0 +1,143 @@
+use serde::Serialize;
+use std::{fmt, io};
+
+use crate::{command_utils::CommandError, flows::repo::RepoError};
+
+/// Error returned by commands. Serialized with a `kind` tag so the UI can show
+/// guidance for the category; `message` is always the text to display.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum InstallerError {
+  Network { message: String },
+  Filesystem { message: String },
+  NotFound { message: String },
+  InvalidInput { message: String },
+  /// A git invocation failed; `details` has its exit status and output.
+  Git {
+    message: String,
+    details: Option<Box<CommandError>>,
+  },
+  /// pnpm or Node.js failed while installing, building or injecting.
+  Build {
+    message: String,
+    details: Option<Box<CommandError>>,
+  },
+  /// A required tool is missing, too old, or could not be installed.
+  Dependency { message: String },
+  Other { message: String },
+}
+
+impl InstallerError {
+  pub fn network(message: impl Into<String>) -> Self {
+    Self::Network { message: message.into() }
+  }
+
+  pub fn filesystem(message: impl Into<String>) -> Self {
+    Self::Filesystem { message: message.into() }
+  }
+
+  pub fn not_found(message: impl Into<String>) -> Self {
+    Self::NotFound { message: message.into() }
+  }
+
+  pub fn invalid_input(message: impl Into<String>) -> Self {
+    Self::InvalidInput { message: message.into() }
+  }
+
+  pub fn dependency(message: impl Into<String>) -> Self {
+    Self::Dependency { message: message.into() }
+  }
+
+  pub fn message(&self) -> &str {
+    match self {
+      Self::Network { message }
+      | Self::Filesystem { message }
+      | Self::NotFound { message }
+      | Self::InvalidInput { message }
+      | Self::Git { message, .. }
+      | Self::Build { message, .. }
+      | Self::Dependency { message }
+      | Self::Other { message } => message,
+    }
+  }
+
+  /// Keeps the category and details but replaces the displayed message, e.g.
+  /// with the friendlier text the patch flow shows.
+  pub fn with_message(mut self, text: impl Into<String>) -> Self {
+    match &mut self {
+      Self::Network { message }
+      | Self::Filesystem { message }
+      | Self::NotFound { message }
+      | Self::InvalidInput { message }
+      | Self::Git { message, .. }
+      | Self::Build { message, .. }
+      | Self::Dependency { message }
+      | Self::Other { message } => *message = text.into(),
+    }
+    self
+  }
+}
+
+impl fmt::Display for InstallerError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.message())
+  }
+}
+
+impl std::error::Error for InstallerError {}
+
+impl From<String> for InstallerError {
+  fn from(message: String) -> Self {
+    Self::Other { message }
+  }
+}
+
+impl From<&str> for InstallerError {
+  fn from(message: &str) -> Self {
+    Self::Other { message: message.to_string() }
+  }
+}
+
+impl From<io::Error> for InstallerError {
+  fn from(err: io::Error) -> Self {
+    if err.kind() == io::ErrorKind::NotFound {
+      Self::not_found(err.to_string())
+    } else {
+      Self::filesystem(err.to_string())
+    }
+  }
+}
+
+impl From<reqwest::Error> for InstallerError {
+  fn from(err: reqwest::Error) -> Self {
+    Self::network(err.to_string())
+  }
+}
+
+impl From<CommandError> for InstallerError {
+  fn from(err: CommandError) -> Self {
+    let message = err.message.clone();
+    let details = Some(Box::new(err));
+
+    if details.as_ref().is_some_and(|err| err.program == "git") {
+      Self::Git { message, details }
+    } else {
+      Self::Build { message, details }
+    }
+  }
+}
+
+impl From<RepoError> for InstallerError {
+  fn from(err: RepoError) -> Self {
+    match err {
+      RepoError::Command(err) => (*err).into(),
+      RepoError::Other(message) => Self::Other { message },
+    }
+  }
+}
+
+impl From<InstallerError> for String {
+  fn from(err: InstallerError) -> Self {
+    err.to_string()
+  }
+}
@@ -1,13 +1,21 @@
-use chrono::{DateTime,  Local};
-use serde::Serialize;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
 use std::{
   cmp::Ordering,
+  collections::HashMap,
+  fmt::Write as _,
   fs, io,
   path::{Path, PathBuf},
+  sync::atomic::{self, AtomicBool},
   time::SystemTime,
 };
 
-use crate::{config::app_config_dir, options};
+use crate::{
+  config::{app_config_dir, config_base_dir},
+  discord,
+  error::InstallerError,
+  options,
+};
 
 use super::{discord_clients, themes};
 
@@ -19,6 +27,8 @@ pub struct BackupResult {
   pub closed_clients: Vec<String>,
   pub restarted_clients: Vec<String>,
   pub closing_skipped: bool,
+  /// Old backups removed afterwards to stay within the configured limits.
+  pub pruned_backups: usize,
 }
 
 #[derive(Serialize, Clone)]
@@ -30,6 +40,31 @@ pub struct BackupInfo {
   pub created_at: Option<String>,
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupDeleteResult {
+  pub name: String,
+  pub deleted: bool,
+  pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsRestoreResult {
+  pub restored: Vec<String>,
+  /// Subfolders of the backup that are neither a known client nor `vencord`.
+  pub skipped: Vec<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupsDiskUsage {
+  pub total_bytes: u64,
+  pub count: usize,
+  pub oldest: Option<String>,
+  pub newest: Option<String>,
+}
+
 #[derive(Clone)]
 struct BackupEntry {
   name: String,
@@ -38,7 +73,7 @@ struct BackupEntry {
   size_bytes: u64,
 }
 
-fn backups_root() -> Result<PathBuf, String> {
+pub fn backups_root() -> Result<PathBuf, String> {
   let dir = app_config_dir().map_err(|err| format!("Failed to get config directory: {err}"))?;
   let backups = dir.join("backups");
 
@@ -52,12 +87,121 @@ fn backups_root() -> Result<PathBuf, String> {
   Ok(backups)
 }
 
-fn backup_destination() -> Result<PathBuf, String> {
-  let backups = backups_root()?;
+const MAX_BACKUP_LABEL_LEN: usize = 40;
+
+/// Reduces a user label to characters that are safe in a folder name on every
+/// platform. Returns `None` when nothing usable is left.
+fn sanitize_backup_label(label: &str) -> Option<String> {
+  let mut sanitized = String::new();
+
+  for ch in label.trim().chars() {
+    if ch.is_ascii_alphanumeric() || ch == '_' {
+      sanitized.push(ch);
+    } else if !sanitized.ends_with('-') {
+      sanitized.push('-');
+    }
+  }
+
+  let sanitized: String = sanitized
+    .trim_matches(['-', '_'])
+    .chars()
+    .take(MAX_BACKUP_LABEL_LEN)
+    .collect();
+  let sanitized = sanitized.trim_end_matches(['-', '_']);
+
+  (!sanitized.is_empty()).then(|| sanitized.to_string())
+}
+
+const DEFAULT_BACKUP_NAME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
 
-  let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+/// How backup folders are timestamped.
+#[derive(Clone, Debug, Default)]
+pub struct BackupNaming {
+  /// strftime format; `None` uses `DEFAULT_BACKUP_NAME_FORMAT`.
+  pub format: Option<String>,
+  pub use_utc: bool,
+}
+
+impl BackupNaming {
+  /// A hand-edited options file can hold a format the settings page would
+  /// have rejected, so it is checked again here.
+  pub fn from_options(options: &options::UserOptions) -> Self {
+    let format = options
+      .backup_name_format
+      .clone()
+      .filter(|format| match options::check_backup_name_format(format) {
+        Ok(()) => true,
+        Err(err) => {
+          log::warn!("[backup] Backup name format {format:?} is unusable because {err}; using the default");
+          false
+        }
+      });
+
+    Self {
+      format,
+      use_utc: options.backup_use_utc,
+    }
+  }
 
-  let destination = backups.join(format!("{timestamp}"));
+  fn current() -> Self {
+    options::read_user_options()
+      .map(|options| Self::from_options(&options))
+      .unwrap_or_default()
+  }
+
+  fn format(&self) -> &str {
+    self.format.as_deref().unwrap_or(DEFAULT_BACKUP_NAME_FORMAT)
+  }
+
+  // `to_string` on a bad format panics, so this writes it out and falls back
+  // to the default format instead.
+  fn timestamp(&self) -> String {
+    let render = |format: &str| {
+      let mut rendered = String::new();
+      let result = if self.use_utc {
+        write!(rendered, "{}", Utc::now().format(format))
+      } else {
+        write!(rendered, "{}", Local::now().format(format))
+      };
+      result.ok().map(|_| rendered)
+    };
+
+    render(self.format()).unwrap_or_else(|| {
+      log::warn!("[backup] Backup name format {:?} failed to render; using the default", self.format());
+      render(DEFAULT_BACKUP_NAME_FORMAT).unwrap_or_default()
+    })
+  }
+
+  /// Reads the creation time back out of a backup folder name. Falls back to
+  /// the default format, which backups made before a format change still use.
+  fn parse_timestamp(&self, name: &str) -> Option<DateTime<Local>> {
+    let parse = |format: &str, utc: bool| {
+      let (naive, _) = NaiveDateTime::parse_and_remainder(name, format).ok()?;
+
+      if utc {
+        Some(Utc.from_utc_datetime(&naive).with_timezone(&Local))
+      } else {
+        Local.from_local_datetime(&naive).earliest()
+      }
+    };
+
+    parse(self.format(), self.use_utc).or_else(|| parse(DEFAULT_BACKUP_NAME_FORMAT, false))
+  }
+}
+
+fn backup_dir_name(label: Option<&str>, naming: &BackupNaming) -> String {
+  let timestamp = naming.timestamp();
+
+  match label.and_then(sanitize_backup_label) {
+    Some(label) => format!("{timestamp}_{label}"),
+    None => timestamp,
+  }
+}
+
+fn backup_destination(label: Option<&str>, naming: &BackupNaming) -> Result<PathBuf, String> {
+  let backups = backups_root()?;
+
+  let destination = backups.join(backup_dir_name(label, naming));
 
   fs::create_dir_all(&destination).map_err(|err| {
     format!(
@@ -69,7 +213,119 @@ fn backup_destination() -> Result<PathBuf, String> {
   Ok(destination)
 }
 
-fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<(), String> {
+#[derive(Clone, Debug, Default)]
+pub struct BackupSettings {
+  pub include_node_modules: bool,
+  pub exclude: Vec<String>,
+  /// Client ids whose Discord settings are copied into the backup.
+  pub discord_settings_clients: Vec<String>,
+  pub backup_user_themes: bool,
+  /// Appended to the backup folder name after sanitizing.
+  pub label: Option<String>,
+  /// Copy instead of move, leaving the install and its themes in place.
+  pub keep_source: bool,
+  pub naming: BackupNaming,
+}
+
+impl BackupSettings {
+  pub fn from_options(options: &options::UserOptions) -> Self {
+    Self {
+      include_node_modules: options.backup_include_node_modules,
+      // Without `include_node_modules` node_modules is stripped up front, so
+      // the pattern would only force a slow copy instead of a rename. With it,
+      // an explicit `node_modules` exclusion still wins and is kept.
+      exclude: options
+        .backup_exclude
+        .iter()
+        .map(|pattern| pattern.trim().replace('\\', "/"))
+        .filter(|pattern| {
+          !pattern.is_empty() && (options.backup_include_node_modules || pattern != "node_modules")
+        })
+        .collect(),
+      discord_settings_clients: if options.backup_discord_settings {
+        options.selected_discord_clients.clone()
+      } else {
+        Vec::new()
+      },
+      backup_user_themes: options.backup_user_themes,
+      label: None,
+      keep_source: false,
+      naming: BackupNaming::from_options(options),
+    }
+  }
+}
+
+// Iterative glob match: on a mismatch only the most recent `*` takes one more
+// byte, which keeps it linear-ish instead of exponential in the stars. `*`
+// and `?` never match `/`.
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+  let (mut p, mut t) = (0, 0);
+  // The last `*` seen and where in `text` its match currently ends.
+  let mut star: Option<(usize, usize)> = None;
+
+  while t < text.len() {
+    match pattern.get(p) {
+      Some(b'*') => {
+        star = Some((p, t));
+        p += 1;
+      }
+      Some(b'?') if text[t] != b'/' => {
+        p += 1;
+        t += 1;
+      }
+      Some(ch) if *ch != b'?' && ch.eq_ignore_ascii_case(&text[t]) => {
+        p += 1;
+        t += 1;
+      }
+      _ => match star {
+        Some((star_p, star_t)) if text[star_t] != b'/' => {
+          star = Some((star_p, star_t + 1));
+          p = star_p + 1;
+          t = star_t + 1;
+        }
+        _ => return false,
+      },
+    }
+  }
+
+  pattern[p..].iter().all(|ch| *ch == b'*')
+}
+
+fn is_excluded(relative: &Path, patterns: &[String]) -> bool {
+  if patterns.is_empty() {
+    return false;
+  }
+
+  let relative = relative.to_string_lossy().replace('\\', "/");
+  let name = relative.rsplit('/').next().unwrap_or(&relative);
+
+  patterns.iter().any(|pattern| {
+    let pattern = pattern.trim_matches('/');
+
+    if pattern.contains('/') {
+      wildcard_match(pattern.as_bytes(), relative.as_bytes())
+    } else {
+      wildcard_match(pattern.as_bytes(), name.as_bytes())
+    }
+  })
+}
+
+pub fn copy_dir_filtered(
+  source: &Path,
+  destination: &Path,
+  relative: &Path,
+  exclude: &[String],
+) -> Result<(), String> {
+  copy_dir_entries(source, destination, relative, exclude, None)
+}
+
+fn copy_dir_entries(
+  source: &Path,
+  destination: &Path,
+  relative: &Path,
+  exclude: &[String],
+  cancel: Option<&AtomicBool>,
+) -> Result<(), String> {
   fs::create_dir(destination).map_err(|err| {
     format!(
       "Failed to create backup directory {}: {err}",
@@ -80,6 +336,10 @@ fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<(), String> {
   for entry in fs::read_dir(source)
     .map_err(|err| format!("failed to read directory {}: {err}", source.display()))?
   {
+    if cancel.is_some_and(|cancel| cancel.load(atomic::Ordering::SeqCst)) {
+      return Err(BACKUP_CANCELLED.to_string());
+    }
+
     let entry = entry.map_err(|err| {
       format!(
         "Failed to read directory entry in {}: {err}",
@@ -88,9 +348,14 @@ fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<(), String> {
     })?;
     let path = entry.path();
     let dest_path = destination.join(entry.file_name());
+    let entry_relative = relative.join(entry.file_name());
+
+    if is_excluded(&entry_relative, exclude) {
+      continue;
+    }
 
     if path.is_dir() {
-      copy_dir_recursive(&path, &dest_path)?;
+      copy_dir_entries(&path, &dest_path, &entry_relative, exclude, cancel)?;
     } else {
       fs::copy(&path, &dest_path).map_err(|err| {
         format!(
@@ -112,7 +377,7 @@ fn is_cross_device_link(err: &io::Error) -> bool {
   { err.raw_os_error() == Some(0x11) }
 }
 
-fn dir_size(path: &Path) -> Result<u64, String> {
+pub fn dir_size(path: &Path) -> Result<u64, String> {
   let mut total: u64 = 0;
   let mut stack = vec![path.to_path_buf()];
 
@@ -139,9 +404,53 @@ fn dir_size(path: &Path) -> Result<u64, String> {
   Ok(total)
 }
 
+const SIZE_INDEX_FILE: &str = ".size-index.json";
+
+/// Cached `dir_size` result for one backup, valid while its mtime is unchanged.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct SizeIndexEntry {
+  modified_nanos: u128,
+  size_bytes: u64,
+}
+
+type SizeIndex = HashMap<String, SizeIndexEntry>;
+
+fn read_size_index(backups_dir: &Path) -> SizeIndex {
+  fs::read_to_string(backups_dir.join(SIZE_INDEX_FILE))
+    .ok()
+    .and_then(|content| serde_json::from_str(&content).ok())
+    .unwrap_or_default()
+}
+
+fn write_size_index(backups_dir: &Path, index: &SizeIndex) {
+  let path = backups_dir.join(SIZE_INDEX_FILE);
+
+  let result = serde_json::to_string(index)
+    .map_err(|err| err.to_string())
+    .and_then(|json| fs::write(&path, json).map_err(|err| err.to_string()));
+
+  if let Err(err) = result {
+    log::warn!("[backup] Failed to write size index {}: {err}", path.display());
+  }
+}
+
+fn mtime_nanos(modified: SystemTime) -> u128 {
+  modified
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .map(|duration| duration.as_nanos())
+    .unwrap_or(0)
+}
+
 fn collect_backups() -> Result<Vec<BackupEntry>, String> {
+  collect_backups_page(0, None).map(|(backups, _)| backups)
+}
+
+/// Lists backups newest first, computing sizes only for the requested page.
+/// Returns the page together with the total number of backups.
+fn collect_backups_page(offset: usize, limit: Option<usize>) -> Result<(Vec<BackupEntry>, usize), String> {
   let backups_dir = backups_root()?;
-  let mut backups = Vec::new();
+  let mut found: Vec<(String, PathBuf, SystemTime)> = Vec::new();
 
   for entry in
     fs::read_dir(&backups_dir).map_err(|err| format!("Failed to read backups directory: {err}"))?
@@ -161,7 +470,47 @@ fn collect_backups() -> Result<Vec<BackupEntry>, String> {
     let metadata = fs::metadata(&path)
       .map_err(|err| format!("Failed to read metadata for {}: {err}", path.display()))?;
     let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-    let size_bytes = dir_size(&path)?;
+
+    found.push((name, path, modified));
+  }
+
+  found.sort_by(|a, b| match a.2.cmp(&b.2) {
+    Ordering::Less => Ordering::Greater,
+    Ordering::Greater => Ordering::Less,
+    Ordering::Equal => a.0.cmp(&b.0),
+  });
+
+  let total = found.len();
+  let cached_sizes = read_size_index(&backups_dir);
+  // Keep cached sizes for backups outside this page; entries for backups
+  // that no longer exist are dropped.
+  let mut size_index: SizeIndex = cached_sizes
+    .iter()
+    .filter(|(key, _)| found.iter().any(|(_, path, _)| path.to_string_lossy() == key.as_str()))
+    .map(|(key, entry)| (key.clone(), *entry))
+    .collect();
+  let mut backups = Vec::new();
+
+  for (name, path, modified) in found
+    .into_iter()
+    .skip(offset)
+    .take(limit.unwrap_or(usize::MAX))
+  {
+    let key = path.to_string_lossy().into_owned();
+    let modified_nanos = mtime_nanos(modified);
+
+    let size_bytes = match cached_sizes.get(&key) {
+      Some(entry) if entry.modified_nanos == modified_nanos => entry.size_bytes,
+      _ => dir_size(&path)?,
+    };
+
+    size_index.insert(
+      key,
+      SizeIndexEntry {
+        modified_nanos,
+        size_bytes,
+      },
+    );
 
     backups.push(BackupEntry {
       name,
@@ -171,32 +520,28 @@ fn collect_backups() -> Result<Vec<BackupEntry>, String> {
     });
   }
 
-  backups.sort_by(|a, b| match a.modified.cmp(&b.modified) {
-    Ordering::Less => Ordering::Greater,
-    Ordering::Greater => Ordering::Less,
-    Ordering::Equal => a.name.cmp(&b.name),
-  });
+  if size_index != cached_sizes {
+    write_size_index(&backups_dir, &size_index);
+  }
 
-  Ok(backups)
+  Ok((backups, total))
 }
 
-pub fn apply_backup_limits(max_count: Option<u32>, max_size_mb: Option<u64>) -> Result<(), String> {
+/// Removes the oldest backups beyond the limits and returns how many it removed.
+pub fn apply_backup_limits(max_count: Option<u32>, max_size_mb: Option<u64>) -> Result<usize, String> {
   if max_count.is_none() && max_size_mb.is_none() {
-    return Ok(());
+    return Ok(0);
   }
 
   let mut backups = collect_backups()?;
+  let mut pruned = 0;
 
   if let Some(limit) = max_count {
     if backups.len() > limit as usize {
       let mut to_remove = backups.split_off(limit as usize);
       for entry in to_remove.drain(..) {
-        fs::remove_dir_all(&entry.path).map_err(|err| {
-          format!(
-            "Failed to remove old backup {}: {err}",
-            entry.path.display()
-          )
-        })?;
+        remove_dir_tree(&entry.path)?;
+        pruned += 1;
       }
     }
   }
@@ -207,37 +552,116 @@ pub fn apply_backup_limits(max_count: Option<u32>, max_size_mb: Option<u64>) ->
     let max_bytes = max_mb.saturating_mul(1024 * 1024);
     let mut total: u64 = size_backups.iter().map(|entry| entry.size_bytes).sum();
 
-    if total <= max_bytes {
-      return Ok(());
-    }
-
     while total > max_bytes {
       if let Some(oldest) = size_backups.pop() {
-        fs::remove_dir_all(&oldest.path)
-          .map_err(|err| format!("Failed to remove backup {}: {err}", oldest.path.display()))?;
+        remove_dir_tree(&oldest.path)?;
         total = total.saturating_sub(oldest.size_bytes);
+        pruned += 1;
       } else {
         break;
       }
     }
   }
 
-  Ok(())
+  if pruned > 0 {
+    log::info!("[backup] Pruned {pruned} old backup(s) to stay within the backup limits");
+  }
+
+  Ok(pruned)
+}
+
+const BACKUP_CANCELLED: &str = "Backup cancelled";
+// Written when a backup starts and removed once it is complete, so a backup
+// left behind by a failed or interrupted copy can be told apart.
+const INCOMPLETE_MARKER: &str = ".incomplete";
+
+static BACKUP_RUNNING: AtomicBool = AtomicBool::new(false);
+static BACKUP_CANCEL: AtomicBool = AtomicBool::new(false);
+
+/// Marks a backup as running for `cancel_backup`, clearing both flags again
+/// when the backup ends either way.
+struct RunningBackup;
+
+impl RunningBackup {
+  fn start() -> Self {
+    BACKUP_CANCEL.store(false, atomic::Ordering::SeqCst);
+    BACKUP_RUNNING.store(true, atomic::Ordering::SeqCst);
+    Self
+  }
+}
+
+impl Drop for RunningBackup {
+  fn drop(&mut self) {
+    BACKUP_RUNNING.store(false, atomic::Ordering::SeqCst);
+    BACKUP_CANCEL.store(false, atomic::Ordering::SeqCst);
+  }
+}
+
+/// Stops the copy of a running backup. Returns whether one was running.
+#[tauri::command]
+pub fn cancel_backup() -> bool {
+  if !BACKUP_RUNNING.load(atomic::Ordering::SeqCst) {
+    return false;
+  }
+
+  log::info!("[backup] Cancellation requested");
+  BACKUP_CANCEL.store(true, atomic::Ordering::SeqCst);
+  true
+}
+
+/// Copies a directory into a backup, stopping early once `cancel_backup` is
+/// called. The source is only removed after a copy completes, so after a
+/// cancel it is intact and the partial backup is deleted.
+fn copy_into_backup(
+  source: &Path,
+  destination: &Path,
+  exclude: &[String],
+  destination_root: &Path,
+) -> Result<(), String> {
+  let result = copy_dir_entries(source, destination, Path::new(""), exclude, Some(&BACKUP_CANCEL));
+
+  if result.is_err() && BACKUP_CANCEL.load(atomic::Ordering::SeqCst) {
+    discard_partial_backup(destination_root);
+  }
+
+  result
+}
+
+fn discard_partial_backup(destination_root: &Path) {
+  let confined = backups_root()
+    .map(|root| destination_root != root && destination_root.starts_with(&root))
+    .unwrap_or(false);
+
+  if !confined {
+    log::warn!(
+      "[backup] Not removing partial backup {} outside the backups directory",
+      destination_root.display()
+    );
+    return;
+  }
+
+  match remove_dir_tree(destination_root) {
+    Ok(()) => log::info!("[backup] Removed partial backup {}", destination_root.display()),
+    Err(err) => log::warn!("[backup] Failed to remove partial backup: {err}"),
+  }
 }
 
 pub fn move_vencord_install(
   source: &Path,
   themes: &[options::ProvidedThemeInfo],
-) -> Result<PathBuf, String> {
+  settings: &BackupSettings,
+) -> Result<Option<PathBuf>, String> {
   if !source.exists() {
-    return Err(format!("Vencord install not found at {}", source.display()));
+    return Ok(None);
   }
 
-  if let Err(err) = remove_node_modules(source) {
-    return Err(err);
+  let _running = RunningBackup::start();
+
+  if !settings.include_node_modules && !settings.keep_source {
+    remove_node_modules(source)?;
   }
 
-  let destination_root = backup_destination()?;
+  let destination_root = backup_destination(settings.label.as_deref(), &settings.naming)?;
   let destination = destination_root.join("vencord");
 
   fs::create_dir_all(&destination_root).map_err(|err| {
@@ -247,7 +671,39 @@ pub fn move_vencord_install(
     )
   })?;
 
-  if let Err(err) = fs::rename(source, &destination) {
+  let marker = destination_root.join(INCOMPLETE_MARKER);
+  fs::write(&marker, b"")
+    .map_err(|err| format!("Failed to write {}: {err}", marker.display()))?;
+
+  if settings.keep_source {
+    if source.is_dir() {
+      // node_modules cannot be stripped from a source that stays in use, so
+      // leave it out of the copy instead.
+      let mut exclude = settings.exclude.clone();
+      if !settings.include_node_modules {
+        exclude.push("node_modules".to_string());
+      }
+      copy_into_backup(source, &destination, &exclude, &destination_root)?;
+    } else {
+      fs::copy(source, &destination).map_err(|err| {
+        format!(
+          "Failed to copy {} to {}: {err}",
+          source.display(),
+          destination.display()
+        )
+      })?;
+    }
+  } else if !settings.exclude.is_empty() && source.is_dir() {
+    // A rename would carry excluded entries along, so copy what should be kept
+    // and drop the rest together with the original directory.
+    copy_into_backup(source, &destination, &settings.exclude, &destination_root)?;
+    fs::remove_dir_all(source).map_err(|err| {
+      format!(
+        "Failed to remove original directory {}: {err}",
+        source.display()
+      )
+    })?;
+  } else if let Err(err) = fs::rename(source, &destination) {
     if !is_cross_device_link(&err) {
       return Err(format!(
         "Failed to move Vencord install from {} to {}: {err}",
@@ -257,7 +713,7 @@ pub fn move_vencord_install(
     }
 
     if source.is_dir() {
-      copy_dir_recursive(source, &destination)?;
+      copy_into_backup(source, &destination, &[], &destination_root)?;
       fs::remove_dir_all(source).map_err(|err| {
         format!(
           "Failed to remove original directory {}: {err}",
@@ -277,9 +733,163 @@ pub fn move_vencord_install(
     }
   }
 
-  themes::move_themes_to_backup(&destination_root, themes)?;
+  let themes: Vec<options::ProvidedThemeInfo> = themes
+    .iter()
+    .filter(|theme| settings.backup_user_themes || !theme.id.starts_with(options::USER_THEME_ID_PREFIX))
+    .cloned()
+    .collect();
+
+  themes::move_themes_to_backup(&destination_root, &themes, settings.keep_source)?;
+
+  if !settings.discord_settings_clients.is_empty() {
+    backup_discord_settings(&destination_root, &settings.discord_settings_clients);
+  }
+
+  fs::remove_file(&marker)
+    .map_err(|err| format!("Failed to remove {}: {err}", marker.display()))?;
+
+  Ok(Some(destination_root))
+}
+
+/// Backups whose copy failed or was interrupted before it completed. Empty
+/// while a backup is running, since its folder is still being written.
+pub fn incomplete_backups() -> Vec<PathBuf> {
+  if BACKUP_RUNNING.load(atomic::Ordering::SeqCst) {
+    return Vec::new();
+  }
+
+  let Ok(root) = backups_root() else {
+    return Vec::new();
+  };
+
+  fs::read_dir(&root)
+    .map(|entries| {
+      entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.join(INCOMPLETE_MARKER).exists())
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+const DISCORD_SETTINGS_DIR: &str = "discord-settings";
+const DISCORD_SETTINGS_FILES: &[&str] = &["settings.json", "quickCss.css"];
+
+fn vencord_settings_dir() -> Option<PathBuf> {
+  config_base_dir().map(|config| config.join("Vencord").join("settings"))
+}
+
+/// Source directories for each settings snapshot, keyed by the subfolder name
+/// used under `discord-settings/`.
+fn settings_sources(client_ids: &[String]) -> Vec<(String, PathBuf)> {
+  let mut sources: Vec<(String, PathBuf)> = client_ids
+    .iter()
+    .filter_map(|id| {
+      discord::user_data_dirs(id)
+        .into_iter()
+        .find(|dir| dir.is_dir())
+        .map(|dir| (id.clone(), dir))
+    })
+    .collect();
+
+  if let Some(dir) = vencord_settings_dir().filter(|dir| dir.is_dir()) {
+    sources.push(("vencord".to_string(), dir));
+  }
+
+  sources
+}
+
+fn copy_settings_files(source: &Path, destination: &Path) -> Result<Vec<PathBuf>, String> {
+  let mut copied = Vec::new();
+
+  for file in DISCORD_SETTINGS_FILES {
+    let from = source.join(file);
+
+    if !from.is_file() {
+      continue;
+    }
+
+    fs::create_dir_all(destination)
+      .map_err(|err| format!("Failed to create {}: {err}", destination.display()))?;
+
+    let to = destination.join(file);
+    fs::copy(&from, &to)
+      .map_err(|err| format!("Failed to copy {} to {}: {err}", from.display(), to.display()))?;
+    copied.push(to);
+  }
+
+  Ok(copied)
+}
+
+/// Copies (never moves) the live settings files of each client into the
+/// backup. Failures are logged so they do not abort the patch.
+fn backup_discord_settings(destination_root: &Path, client_ids: &[String]) {
+  let settings_root = destination_root.join(DISCORD_SETTINGS_DIR);
 
-  Ok(destination_root)
+  for (name, source) in settings_sources(client_ids) {
+    match copy_settings_files(&source, &settings_root.join(&name)) {
+      Ok(copied) => log::info!(
+        "[backup] Copied {} settings file(s) for {name} from {}",
+        copied.len(),
+        source.display()
+      ),
+      Err(err) => log::warn!("[backup] Failed to back up settings for {name}: {err}"),
+    }
+  }
+}
+
+#[tauri::command]
+pub fn restore_discord_settings(backup_name: String) -> Result<SettingsRestoreResult, InstallerError> {
+  if !is_valid_backup_name(&backup_name) {
+    return Err(InstallerError::invalid_input(format!("Invalid backup name: {backup_name}")));
+  }
+
+  let settings_root = backups_root()
+    .map_err(InstallerError::filesystem)?
+    .join(&backup_name)
+    .join(DISCORD_SETTINGS_DIR);
+
+  if !settings_root.is_dir() {
+    return Err(InstallerError::not_found(format!(
+      "Backup {backup_name} does not contain Discord settings"
+    )));
+  }
+
+  let entries = fs::read_dir(&settings_root).map_err(|err| {
+    InstallerError::filesystem(format!("Failed to read directory {}: {err}", settings_root.display()))
+  })?;
+  let mut restored = Vec::new();
+  let mut skipped = Vec::new();
+
+  for entry in entries.filter_map(Result::ok) {
+    let name = entry.file_name().to_string_lossy().into_owned();
+
+    let target = if name == "vencord" {
+      vencord_settings_dir()
+    } else {
+      let candidates = discord::user_data_dirs(&name);
+      candidates
+        .iter()
+        .find(|dir| dir.is_dir())
+        .or_else(|| candidates.first())
+        .cloned()
+    };
+
+    let Some(target) = target else {
+      log::warn!("[backup] No settings directory known for {name}; skipping restore");
+      skipped.push(name);
+      continue;
+    };
+
+    for path in copy_settings_files(&entry.path(), &target).map_err(InstallerError::filesystem)? {
+      restored.push(path.to_string_lossy().into_owned());
+    }
+  }
+
+  log::info!("[backup] Restored {} settings file(s) from {backup_name}", restored.len());
+
+  Ok(SettingsRestoreResult { restored, skipped })
 }
 
 fn remove_node_modules(source: &Path) -> Result<(), String> {
@@ -329,37 +939,65 @@ fn remove_node_modules(source: &Path) -> Result<(), String> {
   Ok(())
 }
 
-#[tauri::command]
-pub fn backup_vencord_install(source_path: String) -> Result<BackupResult, String> {
-  let options = options::read_user_options()?;
+// Runs off the main thread so `cancel_backup` can be handled meanwhile.
+#[tauri::command(async)]
+pub fn backup_vencord_install(
+  source_path: String,
+  label: Option<String>,
+) -> Result<BackupResult, InstallerError> {
+  let options = options::read_user_options().map_err(InstallerError::filesystem)?;
   let theme_sources = options::resolve_themes(&options);
 
-  let discord_state = discord_clients::close_discord_clients(options.close_discord_on_backup);
+  let discord_state = discord_clients::close_discord_clients(
+    options.close_discord_on_backup,
+    &discord_clients::close_scope(&options),
+  );
+  let restart_enabled = !discord_state.closing_skipped && options.reopen_discord_after_patch;
 
-  let backup_path = match move_vencord_install(Path::new(&source_path), &theme_sources) {
-    Ok(path) => path,
+  let settings = BackupSettings {
+    label,
+    ..BackupSettings::from_options(&options)
+  };
+
+  let backup_path = match move_vencord_install(Path::new(&source_path), &theme_sources, &settings) {
+    Ok(Some(path)) => path,
+    Ok(None) => {
+      if restart_enabled {
+        let _ = discord_clients::restart_processes(&discord_state.processes);
+      }
+      return Err(InstallerError::not_found(format!(
+        "Vencord install not found at {source_path}"
+      )));
+    }
     Err(err) => {
-      if !discord_state.closing_skipped {
+      if restart_enabled {
         let _ = discord_clients::restart_processes(&discord_state.processes);
       }
-      return Err(err);
+      return Err(InstallerError::filesystem(err));
     }
   };
 
-  apply_backup_limits(options.max_backup_count, options.max_backup_size_mb)?;
+  let pruned_backups = apply_backup_limits(options.max_backup_count, options.max_backup_size_mb)
+    .map_err(InstallerError::filesystem)?;
 
-  if let Err(err) = themes::download_themes(&theme_sources) {
-    if !discord_state.closing_skipped {
+  if let Err(err) = themes::theme_dirs().and_then(|dirs| themes::download_themes(&theme_sources, &dirs, None)) {
+    if restart_enabled {
       let _ = discord_clients::restart_processes(&discord_state.processes);
     }
 
-    return Err(err);
+    return Err(InstallerError::network(err));
   }
 
-  let restarted = if discord_state.closing_skipped {
-    Vec::new()
+  let restarted = if restart_enabled {
+    discord_clients::restarted_names(&discord_clients::restart_processes(
+      &discord_state.processes,
+    ))
   } else {
-    discord_clients::restart_processes(&discord_state.processes)
+    if !discord_state.closing_skipped {
+      log::info!("[backup] Automatic restart disabled; leaving Discord closed");
+      discord_clients::take_last_closed_state();
+    }
+    Vec::new()
   };
 
   Ok(BackupResult {
@@ -368,69 +1006,332 @@ pub fn backup_vencord_install(source_path: String) -> Result<BackupResult, Strin
     closed_clients: discord_state.closed_clients,
     restarted_clients: restarted,
     closing_skipped: discord_state.closing_skipped,
+    pruned_backups,
   })
 }
 
-fn to_backup_info(entries: Vec<BackupEntry>) -> Vec<BackupInfo> {
+/// The folder name a backup started now with `label` would get, so the UI
+/// can show it before the user confirms.
+#[tauri::command]
+pub fn next_backup_name(label: Option<String>) -> String {
+  backup_dir_name(label.as_deref(), &BackupNaming::current())
+}
+
+fn to_backup_info(entries: Vec<BackupEntry>, naming: &BackupNaming) -> Vec<BackupInfo> {
   entries
     .into_iter()
-    .map(|entry| BackupInfo {
-      name: entry.name,
-      path: entry.path.to_string_lossy().into_owned(),
-      size_bytes: entry.size_bytes,
-      created_at: Some(DateTime::<Local>::from(entry.modified).to_rfc3339()),
+    .map(|entry| {
+      let created_at = naming
+        .parse_timestamp(&entry.name)
+        .unwrap_or_else(|| DateTime::<Local>::from(entry.modified));
+
+      BackupInfo {
+        name: entry.name,
+        path: entry.path.to_string_lossy().into_owned(),
+        size_bytes: entry.size_bytes,
+        created_at: Some(created_at.to_rfc3339()),
+      }
     })
     .collect()
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupPage {
+  pub backups: Vec<BackupInfo>,
+  pub total: usize,
+}
+
 #[tauri::command]
-pub fn list_backups() -> Result<Vec<BackupInfo>, String> {
-  let backups = collect_backups()?;
-  Ok(to_backup_info(backups))
+pub fn list_backups(
+  offset: Option<usize>,
+  limit: Option<usize>,
+) -> Result<BackupPage, InstallerError> {
+  let (backups, total) =
+    collect_backups_page(offset.unwrap_or(0), limit).map_err(InstallerError::filesystem)?;
+
+  Ok(BackupPage {
+    backups: to_backup_info(backups, &BackupNaming::current()),
+    total,
+  })
+}
+
+#[tauri::command]
+pub fn backups_disk_usage() -> Result<BackupsDiskUsage, InstallerError> {
+  // `collect_backups` sorts newest first.
+  let backups = collect_backups().map_err(InstallerError::filesystem)?;
+
+  Ok(BackupsDiskUsage {
+    total_bytes: backups.iter().map(|entry| entry.size_bytes).sum(),
+    count: backups.len(),
+    oldest: backups.last().map(|entry| entry.name.clone()),
+    newest: backups.first().map(|entry| entry.name.clone()),
+  })
+}
+
+fn remove_symlink(path: &Path) -> io::Result<()> {
+  #[cfg(target_os = "windows")]
+  {
+    // Directory symlinks and junctions on Windows must be removed as directories.
+    fs::remove_dir(path).or_else(|_| fs::remove_file(path))
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    fs::remove_file(path)
+  }
+}
+
+fn unlink_symlinks(root: &Path) -> Result<(), String> {
+  let mut stack = vec![root.to_path_buf()];
+
+  while let Some(dir) = stack.pop() {
+    let entries = fs::read_dir(&dir)
+      .map_err(|err| format!("Failed to read directory {}: {err}", dir.display()))?;
+
+    for entry in entries {
+      let entry =
+        entry.map_err(|err| format!("Failed to read entry in {}: {err}", dir.display()))?;
+      let path = entry.path();
+      let metadata = fs::symlink_metadata(&path)
+        .map_err(|err| format!("Failed to read metadata for {}: {err}", path.display()))?;
+
+      if metadata.file_type().is_symlink() {
+        remove_symlink(&path)
+          .map_err(|err| format!("Failed to remove symlink {}: {err}", path.display()))?;
+      } else if metadata.is_dir() {
+        stack.push(path);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+pub fn remove_dir_tree(path: &Path) -> Result<(), String> {
+  let metadata = fs::symlink_metadata(path)
+    .map_err(|err| format!("Failed to read metadata for {}: {err}", path.display()))?;
+
+  if metadata.file_type().is_symlink() {
+    return remove_symlink(path)
+      .map_err(|err| format!("Failed to delete {}: {err}", path.display()));
+  }
+
+  // Unlink every symlink first so nothing outside the tree being removed can
+  // be reached through one.
+  unlink_symlinks(path)?;
+
+  fs::remove_dir_all(path).map_err(|err| format!("Failed to delete {}: {err}", path.display()))
 }
 
 fn is_valid_backup_name(name: &str) -> bool {
   !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..")
 }
 
+/// Resolves `name` inside the backups directory, refusing anything that
+/// escapes it. `None` when the backup does not exist.
+fn confined_backup_path(root: &Path, name: &str) -> Result<Option<PathBuf>, String> {
+  if !is_valid_backup_name(name) {
+    return Err(format!("Invalid backup name: {name}"));
+  }
+
+  let target = root.join(name);
+
+  if !target.exists() {
+    return Ok(None);
+  }
+
+  let canonical_root = dunce::canonicalize(root)
+    .map_err(|err| format!("Failed to resolve backup directory: {err}"))?;
+  let canonical_target = dunce::canonicalize(&target)
+    .map_err(|err| format!("Failed to resolve backup path {}: {err}", target.display()))?;
+
+  if !canonical_target.starts_with(&canonical_root) {
+    return Err(format!(
+      "Refusing to use path outside backups directory: {}",
+      target.display()
+    ));
+  }
+
+  Ok(Some(canonical_target))
+}
+
+fn delete_backup(root: &Path, name: &str) -> Result<bool, String> {
+  let Some(target) = confined_backup_path(root, name)? else {
+    return Ok(false);
+  };
+
+  remove_dir_tree(&target)?;
+
+  Ok(true)
+}
+
+/// Listing is capped so a backup that kept `node_modules` stays responsive.
+const MAX_INSPECTED_FILES: usize = 10_000;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFile {
+  /// Relative to the backup folder, with `/` separators.
+  pub path: String,
+  pub size_bytes: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupContents {
+  pub files: Vec<BackupFile>,
+  pub total_bytes: u64,
+  pub has_themes: bool,
+  /// Set when more than `MAX_INSPECTED_FILES` files exist; `total_bytes`
+  /// still covers all of them.
+  pub truncated: bool,
+}
+
+/// Lists what a backup holds so it can be checked before restoring.
 #[tauri::command]
-pub fn delete_backups(names: Vec<String>) -> Result<(), String> {
+pub fn inspect_backup(name: String) -> Result<BackupContents, InstallerError> {
+  let root = backups_root().map_err(InstallerError::filesystem)?;
+  let backup = confined_backup_path(&root, &name)
+    .map_err(InstallerError::invalid_input)?
+    .ok_or_else(|| InstallerError::not_found(format!("Backup {name} not found")))?;
+
+  let mut contents = BackupContents {
+    files: Vec::new(),
+    total_bytes: 0,
+    has_themes: false,
+    truncated: false,
+  };
+  let mut stack = vec![backup.clone()];
+
+  while let Some(dir) = stack.pop() {
+    let entries = fs::read_dir(&dir).map_err(|err| {
+      InstallerError::filesystem(format!("Failed to read directory {}: {err}", dir.display()))
+    })?;
+
+    for entry in entries.filter_map(Result::ok) {
+      let path = entry.path();
+      let Ok(metadata) = entry.metadata() else {
+        continue;
+      };
+
+      if metadata.is_dir() {
+        stack.push(path);
+        continue;
+      }
+
+      let relative = path
+        .strip_prefix(&backup)
+        .unwrap_or(&path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+      contents.has_themes |= relative.starts_with("themes/");
+      contents.total_bytes = contents.total_bytes.saturating_add(metadata.len());
+
+      if contents.files.len() < MAX_INSPECTED_FILES {
+        contents.files.push(BackupFile {
+          path: relative,
+          size_bytes: metadata.len(),
+        });
+      } else {
+        contents.truncated = true;
+      }
+    }
+  }
+
+  contents.files.sort_by(|a, b| a.path.cmp(&b.path));
+
+  Ok(contents)
+}
+
+#[tauri::command]
+pub fn delete_backups(names: Vec<String>) -> Result<Vec<BackupDeleteResult>, InstallerError> {
   if names.is_empty() {
-    return Ok(());
+    return Ok(Vec::new());
   }
 
-  let root = backups_root()?;
+  let root = backups_root().map_err(InstallerError::filesystem)?;
 
-  for name in names {
-    if !is_valid_backup_name(&name) {
-      return Err(format!("Invalid backup name: {name}"));
-    }
+  let results = names
+    .into_iter()
+    .map(|name| match delete_backup(&root, &name) {
+      Ok(deleted) => BackupDeleteResult {
+        name,
+        deleted,
+        error: None,
+      },
+      Err(err) => {
+        log::warn!("[backup] {err}");
+        BackupDeleteResult {
+          name,
+          deleted: false,
+          error: Some(err),
+        }
+      }
+    })
+    .collect();
 
-    let target = root.join(&name);
+  Ok(results)
+}
 
-    if !target.exists() {
-      continue;
-    }
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-    let canonical_root = dunce::canonicalize(&root)
-      .map_err(|err| format!("Failed to resolve backup directory: {err}"))?;
-    let canonical_target = dunce::canonicalize(&target)
-      .map_err(|err| format!("Failed to resolve backup path {}: {err}", target.display()))?;
+  fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vig-test-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+  }
 
-    if !canonical_target.starts_with(&canonical_root) {
-      return Err(format!(
-        "Refusing to delete path outside backups directory: {}",
-        target.display()
-      ));
-    }
+  #[cfg(unix)]
+  #[test]
+  fn remove_dir_tree_unlinks_symlinks_without_following_them() {
+    let root = scratch_dir("remove-dir-tree");
+    let sentinel = root.join("sentinel");
+    let tree = root.join("tree");
 
-    fs::remove_dir_all(&canonical_target).map_err(|err| {
-      format!(
-        "Failed to delete backup {}: {err}",
-        canonical_target.display()
-      )
-    })?;
+    fs::create_dir_all(&sentinel).unwrap();
+    fs::write(sentinel.join("keep.txt"), b"keep").unwrap();
+    fs::create_dir_all(tree.join("nested")).unwrap();
+    std::os::unix::fs::symlink(&sentinel, tree.join("nested").join("link")).unwrap();
+
+    remove_dir_tree(&tree).unwrap();
+
+    assert!(!tree.exists());
+    assert!(sentinel.join("keep.txt").exists());
+
+    fs::remove_dir_all(&root).unwrap();
   }
 
-  Ok(())
-}
\ No newline at end of file
+  #[test]
+  fn wildcard_patterns_match_within_one_segment() {
+    assert!(wildcard_match(b"*.log", b"Build.LOG"));
+    assert!(wildcard_match(b"dist/*", b"dist/file.js"));
+    assert!(wildcard_match(b"a?c", b"abc"));
+    assert!(!wildcard_match(b"*", b"dist/file.js"));
+    assert!(!wildcard_match(b"a?c", b"a/c"));
+    assert!(!wildcard_match(b"*.log", b"build.txt"));
+  }
+
+  #[test]
+  fn many_stars_do_not_backtrack_exponentially() {
+    let pattern = format!("{}b", "a*".repeat(30));
+    let text = "a".repeat(200);
+
+    assert!(!wildcard_match(pattern.as_bytes(), text.as_bytes()));
+  }
+
+  #[test]
+  fn invalid_name_format_falls_back_to_the_default() {
+    let naming = BackupNaming {
+      format: Some("%Y-%Q".to_string()),
+      use_utc: true,
+    };
+
+    let name = naming.timestamp();
+
+    assert!(NaiveDateTime::parse_from_str(&name, DEFAULT_BACKUP_NAME_FORMAT).is_ok(), "{name}");
+  }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use crate::{config::app_config_dir, options};
+
+use super::{discord_clients::DiscordProcess, repo};
+
+const CHECKPOINT_FILE: &str = "flow-checkpoint.json";
+
+/// What a failed patch flow had already done, so `resume_patch_flow` can
+/// continue from the failed step instead of starting over. Also written as
+/// each step starts, so a run cut short by a crash is found on next launch.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowCheckpoint {
+  pub run_id: String,
+  /// `RunStep` id of the step that failed, or of the running step while
+  /// `in_progress` is set.
+  pub failed_step: String,
+  /// Set while the flow is running. Still set on startup means the app
+  /// exited mid-flow.
+  #[serde(default)]
+  pub in_progress: bool,
+  pub repo_dir: String,
+  pub plugin_urls: Vec<String>,
+  pub sync_path: Option<String>,
+  /// Restarted on resume from their command and working directory. Their
+  /// session variables are not saved, so a resumed restart uses the
+  /// installer's own environment for those.
+  pub closed_processes: Vec<DiscordProcess>,
+  pub closed_clients: Vec<String>,
+  /// The `offline` override the run was started with, reused on resume.
+  #[serde(default)]
+  pub offline: Option<bool>,
+  /// Whether the run skips closing Discord and injecting, reused on resume.
+  #[serde(default)]
+  pub build_only: bool,
+}
+
+impl FlowCheckpoint {
+  fn failed_index(&self) -> usize {
+    step_index(&self.failed_step)
+  }
+
+  /// Whether `step` finished before the failure and can be skipped.
+  pub fn completed(&self, step: &str) -> bool {
+    step_index(step) < self.failed_index()
+  }
+
+  /// Checks the checkpoint still describes the current setup. Returns why it
+  /// does not, in which case the flow runs from the start.
+  pub fn validate(&self, options: &options::UserOptions) -> Result<(), String> {
+    if self.repo_dir != options.vencord_repo_dir {
+      return Err("the Vencord repository folder changed".to_string());
+    }
+
+    if self.plugin_urls != options::resolve_plugin_repositories(options) {
+      return Err("the plugin repositories changed".to_string());
+    }
+
+    if self.completed("syncRepo") {
+      let sync_path = self
+        .sync_path
+        .as_deref()
+        .ok_or_else(|| "the synced repository path was not recorded".to_string())?;
+
+      if !repo::is_git_repo(sync_path).unwrap_or(false) {
+        return Err(format!("{sync_path} is no longer a git repository"));
+      }
+
+      if self.completed("build") && !repo::missing_build_artifacts(Path::new(sync_path)).is_empty() {
+        return Err("build artifacts are missing".to_string());
+      }
+    }
+
+    Ok(())
+  }
+}
+
+fn step_index(step: &str) -> usize {
+  crate::run_log::FLOW_STEPS
+    .iter()
+    .position(|(id, _)| *id == step)
+    .unwrap_or(0)
+}
+
+fn checkpoint_path() -> Result<PathBuf, String> {
+  app_config_dir()
+    .map(|dir| dir.join(CHECKPOINT_FILE))
+    .map_err(|err| format!("Failed to get config directory: {err}"))
+}
+
+pub fn read_checkpoint() -> Option<FlowCheckpoint> {
+  let path = checkpoint_path().ok()?;
+  let content = fs::read_to_string(&path).ok()?;
+
+  match serde_json::from_str(&content) {
+    Ok(checkpoint) => Some(checkpoint),
+    Err(err) => {
+      log::warn!("[checkpoint] Ignoring unreadable {}: {err}", path.display());
+      None
+    }
+  }
+}
+
+pub fn write_checkpoint(checkpoint: &FlowCheckpoint) {
+  let result = checkpoint_path().and_then(|path| {
+    let json = serde_json::to_string_pretty(checkpoint).map_err(|err| err.to_string())?;
+    fs::write(&path, json).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+  });
+
+  if let Err(err) = result {
+    log::warn!("[checkpoint] {err}");
+  }
+}
+
+/// Records that `step` is starting.
+pub fn mark_step(checkpoint: &mut FlowCheckpoint, step: &str) {
+  checkpoint.failed_step = step.to_string();
+  checkpoint.in_progress = true;
+  write_checkpoint(checkpoint);
+}
+
+pub fn clear_checkpoint() {
+  if let Ok(path) = checkpoint_path() {
+    if path.exists() {
+      if let Err(err) = fs::remove_file(&path) {
+        log::warn!("[checkpoint] Failed to remove {}: {err}", path.display());
+      }
+    }
+  }
+}
@@ -1,18 +1,30 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-use serde::{Serialize, Serializer};
-use sysinfo::{Pid, Process, Signal, System};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
 
-#[derive(Clone, Debug, Serialize)]
+use crate::discord;
+use crate::error::InstallerError;
+use crate::options;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DiscordProcess {
-  #[serde(serialize_with = "serialize_pid")]
+  #[serde(serialize_with = "serialize_pid", deserialize_with = "deserialize_pid")]
   pub pid: Pid,
   pub name: String,
   pub exe: Option<PathBuf>,
   pub cmd: Vec<String>,
+  /// Working directory the process was started with, reused when relaunching
+  /// it.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub cwd: Option<PathBuf>,
+  /// Session variables the process was started with, reused when relaunching
+  /// it. Not serialized to keep env values private.
+  #[serde(skip)]
+  pub session_env: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -21,6 +33,28 @@ pub struct DiscordClientsState {
   pub closed_clients: Vec<String>,
   pub processes: Vec<DiscordProcess>,
   pub closing_skipped: bool,
+  /// Processes still alive once `CLOSE_TIMEOUT` ran out.
+  pub failed_to_close: Vec<DiscordProcess>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordClientStatus {
+  pub id: String,
+  pub name: String,
+  pub path: Option<String>,
+  pub installed: bool,
+  pub running: bool,
+  pub pid: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestartOutcome {
+  pub name: String,
+  pub restarted: bool,
+  pub command: String,
+  pub error: Option<String>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -59,10 +93,27 @@ pub fn take_last_closed_state() -> LastClosedState {
     })
 }
 
+/// Whether the installer closed Discord clients that have not been reopened
+/// yet. Unlike `take_last_closed_state` this leaves the cache intact, so the
+/// reopen step can still consume it afterwards.
+#[tauri::command]
+pub fn were_clients_closed_by_us() -> bool {
+  last_closed_cache()
+    .lock()
+    .map(|cache| !cache.closing_skipped && !cache.processes.is_empty())
+    .unwrap_or(false)
+}
+
 const DISCORD_PROCESSES: &[&str] = &["discord", "discordptb", "discordcanary"];
 
+// macOS names the PTB and Canary executables "Discord PTB" and
+// "Discord Canary", so spaces are ignored.
+fn normalized_process_name(name: &str) -> String {
+  name.to_lowercase().replace(' ', "")
+}
+
 fn matches_known_process_name(name: &str) -> bool {
-  let name = name.to_lowercase();
+  let name = normalized_process_name(name);
 
   DISCORD_PROCESSES
     .iter()
@@ -76,6 +127,15 @@ where
   serializer.collect_str(pid)
 }
 
+fn deserialize_pid<'de, D>(deserializer: D) -> Result<Pid, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  String::deserialize(deserializer)?
+    .parse()
+    .map_err(serde::de::Error::custom)
+}
+
 fn is_discord_process(process: &Process) -> bool {
   if let Some(exe) = process.exe() {
     if let Some(file_name) = exe.file_stem().and_then(|stem| stem.to_str()) {
@@ -98,6 +158,49 @@ fn is_discord_process(process: &Process) -> bool {
     .unwrap_or(false)
 }
 
+#[cfg(target_os = "macos")]
+const MACOS_APP_BUNDLES: &[&str] = &["Discord.app", "Discord PTB.app", "Discord Canary.app"];
+
+/// Bundles Discord runs from on macOS: the standard ones under /Applications
+/// and ~/Applications plus any detected install that is itself a bundle.
+#[cfg(target_os = "macos")]
+fn macos_discord_bundles() -> Vec<PathBuf> {
+  let mut roots = vec![PathBuf::from("/Applications")];
+  roots.extend(dirs::home_dir().map(|home| home.join("Applications")));
+
+  let mut bundles: Vec<PathBuf> = roots
+    .iter()
+    .flat_map(|root| MACOS_APP_BUNDLES.iter().map(move |bundle| root.join(bundle)))
+    .collect();
+
+  for install in discord::detect_all_installs() {
+    let path = PathBuf::from(&install.path);
+    if path.extension().is_some_and(|ext| ext == "app") {
+      bundles.push(path);
+    }
+  }
+
+  bundles
+    .into_iter()
+    .map(|bundle| dunce::canonicalize(&bundle).unwrap_or(bundle))
+    .collect()
+}
+
+/// Only the top-level app, `<bundle>/Contents/MacOS/<executable>`, counts.
+/// Renderer, GPU and other helpers live in nested bundles under
+/// `Contents/Frameworks` and exit with the main process.
+#[cfg(target_os = "macos")]
+fn is_macos_discord_app(process: &Process, bundles: &[PathBuf]) -> bool {
+  let Some(exe) = process.exe() else {
+    return is_discord_process(process);
+  };
+  let exe = dunce::canonicalize(exe).unwrap_or_else(|_| exe.to_path_buf());
+
+  bundles
+    .iter()
+    .any(|bundle| exe.parent() == Some(bundle.join("Contents").join("MacOS").as_path()))
+}
+
 fn process_identity(process: &DiscordProcess) -> (String, String) {
   let exe_name = process
     .exe
@@ -114,33 +217,93 @@ fn process_identity(process: &DiscordProcess) -> (String, String) {
     .map(|stem| stem.to_string())
     .unwrap_or_else(|| process.name.clone());
 
-  let key = stem.to_lowercase();
+  let key = normalized_process_name(&stem);
   let display = exe_name.unwrap_or_else(|| process.name.clone());
 
   (key, display)
 }
 
-fn dedupe_processes(processes: &[DiscordProcess]) -> (Vec<String>, Vec<DiscordProcess>) {
-  let mut unique = HashMap::<String, (String, DiscordProcess)>::new();
+/// Groups processes by client (exe stem), keeping every pid so multiple
+/// instances of the same client can all be closed.
+fn group_processes(processes: &[DiscordProcess]) -> Vec<(String, Vec<DiscordProcess>)> {
+  let mut groups: Vec<(String, String, Vec<DiscordProcess>)> = Vec::new();
 
   for proc in processes {
     let (key, display) = process_identity(proc);
 
-    unique.entry(key).or_insert((display, proc.clone()));
+    match groups.iter_mut().find(|(existing, _, _)| *existing == key) {
+      Some((_, _, members)) => members.push(proc.clone()),
+      None => groups.push((key, display, vec![proc.clone()])),
+    }
   }
 
+  groups
+    .into_iter()
+    .map(|(_, display, members)| (display, members))
+    .collect()
+}
+
+fn dedupe_processes(processes: &[DiscordProcess]) -> (Vec<String>, Vec<DiscordProcess>) {
   let mut names = Vec::new();
   let mut deduped = Vec::new();
 
-  for (display, proc) in unique.into_values() {
+  for (display, members) in group_processes(processes) {
     names.push(display);
-    deduped.push(proc);
+    deduped.extend(members.into_iter().next());
   }
 
   (names, deduped)
 }
 
+/// Variables Discord needs to reach the desktop session (display server, D-Bus,
+/// theming). Anything else is inherited from the installer as usual.
+const SESSION_ENV_VARS: &[&str] = &[
+  "DISPLAY",
+  "WAYLAND_DISPLAY",
+  "XAUTHORITY",
+  "DBUS_SESSION_BUS_ADDRESS",
+  "DESKTOP_SESSION",
+  "GTK_THEME",
+  "GDK_BACKEND",
+  "QT_QPA_PLATFORMTHEME",
+  "XCURSOR_THEME",
+  "XCURSOR_SIZE",
+];
+
+fn is_session_env_var(key: &str) -> bool {
+  SESSION_ENV_VARS.contains(&key) || key.starts_with("XDG_")
+}
+
+fn session_env_from(environ: &[std::ffi::OsString]) -> Vec<(String, String)> {
+  environ
+    .iter()
+    .filter_map(|entry| {
+      let (key, value) = entry.to_str()?.split_once('=')?;
+      is_session_env_var(key).then(|| (key.to_string(), value.to_string()))
+    })
+    .collect()
+}
+
+/// The captured session variables, falling back to the installer's own values
+/// for any the process did not expose (e.g. when its environ is unreadable).
+fn restart_env(proc: &DiscordProcess) -> Vec<(String, String)> {
+  let mut env = proc.session_env.clone();
+
+  for (key, value) in std::env::vars() {
+    if is_session_env_var(&key) && !env.iter().any(|(existing, _)| *existing == key) {
+      env.push((key, value));
+    }
+  }
+
+  env
+}
+
 fn capture_discord_processes_with_system(system: &System) -> Vec<DiscordProcess> {
+  #[cfg(target_os = "macos")]
+  let bundles = macos_discord_bundles();
+  #[cfg(target_os = "macos")]
+  let is_discord_process = |process: &Process| is_macos_discord_app(process, &bundles);
+
   system
     .processes()
     .iter()
@@ -154,14 +317,49 @@ fn capture_discord_processes_with_system(system: &System) -> Vec<DiscordProcess>
         .iter()
         .map(|arg| arg.to_string_lossy().into_owned())
         .collect(),
+      cwd: process.cwd().map(|path| path.to_path_buf()),
+      session_env: session_env_from(process.environ()),
     })
     .collect()
 }
 
+fn shared_system() -> &'static Mutex<System> {
+  static SHARED_SYSTEM: OnceLock<Mutex<System>> = OnceLock::new();
+
+  SHARED_SYSTEM.get_or_init(|| Mutex::new(System::new()))
+}
+
+fn refresh_all_processes(system: &mut System) {
+  system.refresh_processes_specifics(
+    ProcessesToUpdate::All,
+    true,
+    ProcessRefreshKind::everything(),
+  );
+}
+
+// Only rescans `pid`; with `remove_dead_processes` set, an exited pid is
+// dropped from the process list so `system.process(pid)` reports it gone.
+fn refresh_process(system: &mut System, pid: Pid) {
+  system.refresh_processes_specifics(
+    ProcessesToUpdate::Some(&[pid]),
+    true,
+    ProcessRefreshKind::nothing(),
+  );
+}
+
+// Holds the shared `System` for the duration of `operation` after a single
+// process refresh, so concurrent blocking tasks never interleave scans.
+fn with_refreshed_system<T>(operation: impl FnOnce(&mut System) -> T) -> T {
+  let mut system = shared_system()
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+  refresh_all_processes(&mut system);
+  operation(&mut system)
+}
+
 pub fn capture_discord_processes() -> Vec<DiscordProcess> {
-  let mut system = System::new_all();
-  system.refresh_all();
-  capture_discord_processes_with_system(&system)
+  with_refreshed_system(|system| capture_discord_processes_with_system(system))
 }
 
 #[tauri::command]
@@ -169,54 +367,175 @@ pub fn list_discord_processes() -> Vec<DiscordProcess> {
   capture_discord_processes()
 }
 
-pub fn close_processes(processes: &[DiscordProcess]) -> Vec<DiscordProcess> {
-  let mut system = System::new_all();
-  system.refresh_all();
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscordRunningStatus {
+  pub running: bool,
+  /// One display name per running client, however many processes it has.
+  pub clients: Vec<String>,
+}
 
-  let mut closed = Vec::new();
+/// Summary for a "Discord will be closed" banner before starting a flow.
+#[tauri::command]
+pub fn is_discord_running() -> DiscordRunningStatus {
+  let (clients, _) = dedupe_processes(&capture_discord_processes());
 
-  for proc in processes {
-    if let Some(process) = system.process(proc.pid) {
-      let killed = process
-        .kill_with(Signal::Kill)
-        .unwrap_or_else(|| process.kill());
+  DiscordRunningStatus {
+    running: !clients.is_empty(),
+    clients,
+  }
+}
 
-      system.refresh_all();
-      let still_running = system.process(proc.pid).is_some();
+fn variant_id_for_process(process: &DiscordProcess) -> &'static str {
+  let (key, _) = process_identity(process);
+
+  match key.as_str() {
+    "discordcanary" => "canary",
+    "discordptb" => "ptb",
+    _ => "stable",
+  }
+}
+
+/// Whether `process` was started from inside `install_path`, which should be
+/// canonical as install paths are.
+pub fn process_runs_from(process: &DiscordProcess, install_path: &Path) -> bool {
+  process
+    .exe
+    .as_ref()
+    .map(|exe| dunce::canonicalize(exe).unwrap_or_else(|_| exe.clone()))
+    .map_or(false, |exe| exe.starts_with(install_path))
+}
+
+#[tauri::command]
+pub fn get_discord_status() -> Vec<DiscordClientStatus> {
+  let installs = discord::detect_all_installs();
+  let processes = capture_discord_processes();
+  let mut claimed = vec![false; processes.len()];
+  let mut statuses: Vec<DiscordClientStatus> = Vec::new();
+
+  for install in &installs {
+    let install_path = Path::new(&install.path);
+    let matched = processes
+      .iter()
+      .enumerate()
+      .find(|(idx, proc)| !claimed[*idx] && process_runs_from(proc, install_path))
+      .map(|(idx, _)| idx);
+
+    if let Some(idx) = matched {
+      claimed[idx] = true;
+    }
+
+    statuses.push(DiscordClientStatus {
+      id: install.id.clone(),
+      name: install.name.clone(),
+      path: Some(install.path.clone()),
+      installed: true,
+      running: matched.is_some(),
+      pid: matched.map(|idx| processes[idx].pid.as_u32()),
+    });
+  }
 
-      if killed || !still_running {
+  // Installs found through user data directories (e.g. ~/.config/discord) do
+  // not contain the executable, so fall back to matching by client variant.
+  for (idx, proc) in processes.iter().enumerate() {
+    if claimed[idx] {
+      continue;
+    }
+
+    let id = variant_id_for_process(proc);
+
+    if let Some(status) = statuses
+      .iter_mut()
+      .find(|status| status.id == id && !status.running)
+    {
+      status.running = true;
+      status.pid = Some(proc.pid.as_u32());
+      claimed[idx] = true;
+    }
+  }
+
+  let mut seen_unclaimed: Vec<&'static str> = Vec::new();
+
+  for (idx, proc) in processes.iter().enumerate() {
+    let id = variant_id_for_process(proc);
+
+    if claimed[idx] || statuses.iter().any(|status| status.id == id) || seen_unclaimed.contains(&id) {
+      continue;
+    }
+
+    seen_unclaimed.push(id);
+    statuses.push(DiscordClientStatus {
+      id: id.to_string(),
+      name: process_identity(proc).1,
+      path: proc
+        .exe
+        .as_ref()
+        .and_then(|exe| exe.parent())
+        .map(|dir| dir.to_string_lossy().into_owned()),
+      installed: false,
+      running: true,
+      pid: Some(proc.pid.as_u32()),
+    });
+  }
+
+  statuses
+}
+
+pub fn close_processes(processes: &[DiscordProcess]) -> Vec<DiscordProcess> {
+  with_refreshed_system(|system| {
+    let mut closed = Vec::new();
+
+    for proc in processes {
+      if let Some(process) = system.process(proc.pid) {
+        let killed = process
+          .kill_with(Signal::Kill)
+          .unwrap_or_else(|| process.kill());
+
+        refresh_process(system, proc.pid);
+        let still_running = system.process(proc.pid).is_some();
+
+        if killed || !still_running {
+          closed.push(proc.clone());
+        }
+      } else {
         closed.push(proc.clone());
       }
-    } else {
-      closed.push(proc.clone());
     }
-  }
 
-  closed
+    closed
+  })
 }
 
-fn restart_process(proc: &DiscordProcess) -> Result<String, String> {
-  let program = if let Some(exe) = &proc.exe {
-    exe.clone()
-  } else if let Some(first) = proc.cmd.first() {
-    PathBuf::from(first)
-  } else {
-    return Err(format!(
+fn restart_process(proc: &DiscordProcess) -> Result<(), String> {
+  let program = restart_program(proc).ok_or_else(|| {
+    format!(
       "Could not determine restart command for Discord process {}",
       proc.name
-    ));
-  };
+    )
+  })?;
+
+  let cwd = proc.cwd.as_deref().filter(|dir| dir.is_dir());
 
+  spawn_detached(&program, &[], &restart_env(proc), cwd)
+    .map_err(|err| format!("Failed to restart {}: {err}", proc.name))
+}
+
+fn spawn_detached(
+  program: &Path,
+  args: &[String],
+  env: &[(String, String)],
+  cwd: Option<&Path>,
+) -> std::io::Result<()> {
   let mut command = Command::new(program);
 
   command
+    .args(args)
+    .envs(env.iter().map(|(key, value)| (key, value)))
     .stdin(Stdio::null())
     .stdout(Stdio::null())
     .stderr(Stdio::null());
 
-  let program_path = command.get_program().to_owned();
-
-  if let Some(dir) = std::path::Path::new(&program_path).parent() {
+  if let Some(dir) = cwd.or_else(|| program.parent().filter(|dir| dir.is_dir())) {
     command.current_dir(dir);
   }
 
@@ -237,29 +556,311 @@ fn restart_process(proc: &DiscordProcess) -> Result<String, String> {
     command.creation_flags(CREATE_NEW_PROCESS_GROUP | DETACHED_PROCESS);
   }
 
-  command
-    .spawn()
-    .map(|_| proc.name.clone())
-    .map_err(|err| format!("Failed to restart {}: {err}", proc.name))
+  command.spawn().map(|_| ())
+}
+
+fn restart_program(proc: &DiscordProcess) -> Option<PathBuf> {
+  proc
+    .exe
+    .clone()
+    .or_else(|| proc.cmd.first().map(PathBuf::from))
 }
 
-pub fn restart_processes(processes: &[DiscordProcess]) -> Vec<String> {
+pub fn restart_processes(processes: &[DiscordProcess]) -> Vec<RestartOutcome> {
   let (names, deduped) = dedupe_processes(processes);
-  let mut restarted = Vec::new();
+  let mut outcomes = Vec::new();
 
   for (proc, display) in deduped.iter().zip(names.iter()) {
-    match restart_process(proc) {
-      Ok(name) => restarted.push(name),
-      Err(err) => {
-        eprintln!("Failed to restart {display}: {err}");
+    let command = restart_program(proc)
+      .map(|program| program.to_string_lossy().into_owned())
+      .unwrap_or_default();
+
+    let result = restart_process(proc);
+
+    if let Err(err) = &result {
+      log::warn!("[discord] Failed to restart {display}: {err}");
+    }
+
+    outcomes.push(RestartOutcome {
+      name: proc.name.clone(),
+      restarted: result.is_ok(),
+      command,
+      error: result.err(),
+    });
+  }
+
+  forget_restarted(&deduped, &outcomes);
+  outcomes
+}
+
+// Clients that came back are no longer "closed by us", whichever path
+// restarted them, so `were_clients_closed_by_us` stops reporting them.
+fn forget_restarted(deduped: &[DiscordProcess], outcomes: &[RestartOutcome]) {
+  let restarted: Vec<String> = deduped
+    .iter()
+    .zip(outcomes)
+    .filter(|(_, outcome)| outcome.restarted)
+    .map(|(proc, _)| process_identity(proc).0)
+    .collect();
+
+  if restarted.is_empty() {
+    return;
+  }
+
+  if let Ok(mut cache) = last_closed_cache().lock() {
+    cache
+      .processes
+      .retain(|proc| !restarted.contains(&process_identity(proc).0));
+  }
+}
+
+#[cfg(target_os = "windows")]
+fn launch_command_for_install(install: &discord::DiscordInstall) -> Option<(PathBuf, Vec<String>)> {
+  let exe_name = match install.id.as_str() {
+    "ptb" => "DiscordPTB.exe",
+    "canary" => "DiscordCanary.exe",
+    _ => "Discord.exe",
+  };
+  let root = Path::new(&install.path);
+  let updater = root.join("Update.exe");
+
+  if updater.is_file() {
+    return Some((updater, vec!["--processStart".to_string(), exe_name.to_string()]));
+  }
+
+  // Without the Squirrel updater, start the newest `app-<version>` build.
+  discord::latest_versioned_subdir(root, "app-")
+    .map(|dir| dir.join(exe_name))
+    .filter(|exe| exe.is_file())
+    .map(|exe| (exe, Vec::new()))
+}
+
+#[cfg(target_os = "macos")]
+fn launch_command_for_install(install: &discord::DiscordInstall) -> Option<(PathBuf, Vec<String>)> {
+  let app_name = match install.id.as_str() {
+    "ptb" => "Discord PTB",
+    "canary" => "Discord Canary",
+    _ => "Discord",
+  };
+
+  let bundle = Path::new(&install.path);
+
+  // Custom installs can live outside /Applications, so open the bundle itself
+  // when the install path points at one.
+  if bundle.extension().map_or(false, |ext| ext == "app") && bundle.is_dir() {
+    return Some((
+      PathBuf::from("open"),
+      vec![bundle.to_string_lossy().into_owned()],
+    ));
+  }
+
+  Some((
+    PathBuf::from("open"),
+    vec!["-a".to_string(), app_name.to_string()],
+  ))
+}
+
+#[cfg(target_os = "linux")]
+fn launch_command_for_install(install: &discord::DiscordInstall) -> Option<(PathBuf, Vec<String>)> {
+  let (binary, command, flatpak_id) = match install.id.as_str() {
+    "ptb" => ("DiscordPTB", "discord-ptb", "com.discordapp.DiscordPTB"),
+    "canary" => ("DiscordCanary", "discord-canary", "com.discordapp.DiscordCanary"),
+    _ => ("Discord", "discord", "com.discordapp.Discord"),
+  };
+
+  if install.name.contains("(Flatpak)") {
+    return Some((
+      PathBuf::from("flatpak"),
+      vec!["run".to_string(), flatpak_id.to_string()],
+    ));
+  }
+
+  let bundled = Path::new(&install.path).join(binary);
+
+  if bundled.is_file() {
+    return Some((bundled, Vec::new()));
+  }
+
+  // Installs found through ~/.config only hold user data, so rely on the
+  // launcher the package puts on PATH.
+  Some((PathBuf::from(command), Vec::new()))
+}
+
+fn launch_install(install: &discord::DiscordInstall) -> RestartOutcome {
+  let Some((program, args)) = launch_command_for_install(install) else {
+    let err = format!("Could not determine launch command for {}", install.name);
+    log::warn!("[discord] {err}");
+    return RestartOutcome {
+      name: install.name.clone(),
+      restarted: false,
+      command: String::new(),
+      error: Some(err),
+    };
+  };
+
+  let command = std::iter::once(program.to_string_lossy().into_owned())
+    .chain(args.iter().cloned())
+    .collect::<Vec<_>>()
+    .join(" ");
+
+  let result = spawn_detached(&program, &args, &[], None)
+    .map_err(|err| format!("Failed to start {}: {err}", install.name));
+
+  if let Err(err) = &result {
+    log::warn!("[discord] {err}");
+  }
+
+  RestartOutcome {
+    name: install.name.clone(),
+    restarted: result.is_ok(),
+    command,
+    error: result.err(),
+  }
+}
+
+/// Starts a single installed client by id, independent of which clients the
+/// last patch run closed.
+#[tauri::command]
+pub fn restart_client(id: String) -> Result<RestartOutcome, InstallerError> {
+  let install = discord::detect_all_installs()
+    .into_iter()
+    .find(|install| install.id == id)
+    .ok_or_else(|| InstallerError::not_found(format!("No Discord installation found for {id}")))?;
+
+  if capture_discord_processes()
+    .iter()
+    .any(|process| variant_id_for_process(process) == install.id)
+  {
+    return Err(InstallerError::invalid_input(format!("{} is already running", install.name)));
+  }
+
+  Ok(launch_install(&install))
+}
+
+/// Starts the selected installed clients that are not already running. Used
+/// when closing was skipped, so there are no captured processes to restart.
+pub fn launch_installed_clients(selected_ids: &[String]) -> Vec<RestartOutcome> {
+  let running: Vec<&'static str> = capture_discord_processes()
+    .iter()
+    .map(variant_id_for_process)
+    .collect();
+  let mut seen_ids: Vec<String> = Vec::new();
+  let mut outcomes = Vec::new();
+
+  for install in discord::detect_all_installs() {
+    if !selected_ids.contains(&install.id) || seen_ids.contains(&install.id) {
+      continue;
+    }
+
+    seen_ids.push(install.id.clone());
+
+    if running.iter().any(|id| *id == install.id) {
+      log::info!("[discord] {} is already running; not starting it again", install.name);
+      continue;
+    }
+
+    outcomes.push(launch_install(&install));
+  }
+
+  outcomes
+}
+
+pub fn restarted_names(outcomes: &[RestartOutcome]) -> Vec<String> {
+  outcomes
+    .iter()
+    .filter(|outcome| outcome.restarted)
+    .map(|outcome| outcome.name.clone())
+    .collect()
+}
+
+const CLOSE_SWEEP_DELAY_MS: u64 = 250;
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Which running clients `close_discord_clients` is allowed to close.
+#[derive(Clone, Debug)]
+pub enum CloseScope {
+  All,
+  /// Only processes whose executable lives under one of these install dirs.
+  InstallDirs(Vec<PathBuf>),
+}
+
+impl CloseScope {
+  fn includes(&self, process: &DiscordProcess) -> bool {
+    match self {
+      CloseScope::All => true,
+      CloseScope::InstallDirs(dirs) => dirs.iter().any(|dir| process_runs_from(process, dir)),
+    }
+  }
+
+  fn capture(&self) -> Vec<DiscordProcess> {
+    capture_discord_processes()
+      .into_iter()
+      .filter(|process| self.includes(process))
+      .collect()
+  }
+}
+
+pub fn resolve_selected_discord_locations(selected_ids: &[String]) -> Result<Vec<String>, String> {
+  if selected_ids.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let installs = discord::detect_all_installs();
+  let mut locations = Vec::new();
+  let mut missing = Vec::new();
+
+  for id in selected_ids {
+    let matched: Vec<String> = installs
+      .iter()
+      .filter(|inst| &inst.id == id)
+      .map(|inst| inst.path.clone())
+      .collect();
+
+    if matched.is_empty() {
+      missing.push(id.clone());
+    } else {
+      for path in matched {
+        if !locations.contains(&path) {
+          locations.push(path);
+        }
       }
     }
   }
 
-  restarted
+  if !missing.is_empty() {
+    return Err(format!(
+      "The following Discord client selections are not installed: {}",
+      missing.join(", ")
+    ));
+  }
+
+  Ok(locations)
 }
 
-pub fn close_discord_clients(close_enabled: bool) -> DiscordClientsState {
+/// Limits closing to the selected clients' install dirs unless
+/// `aggressive_close` is set. Falls back to closing everything when the
+/// selection cannot be resolved, since patching a running client is worse.
+pub fn close_scope(options: &options::UserOptions) -> CloseScope {
+  if options.aggressive_close {
+    return CloseScope::All;
+  }
+
+  match resolve_selected_discord_locations(&options.selected_discord_clients) {
+    Ok(locations) if !locations.is_empty() => CloseScope::InstallDirs(
+      locations
+        .iter()
+        .map(|path| dunce::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path)))
+        .collect(),
+    ),
+    Ok(_) => CloseScope::All,
+    Err(err) => {
+      log::warn!("[discord] {err}; closing all Discord clients");
+      CloseScope::All
+    }
+  }
+}
+
+pub fn close_discord_clients(close_enabled: bool, scope: &CloseScope) -> DiscordClientsState {
   if !close_enabled {
     if let Ok(mut cache) = last_closed_cache().lock() {
       cache.processes.clear();
@@ -270,12 +871,55 @@ pub fn close_discord_clients(close_enabled: bool) -> DiscordClientsState {
       closed_clients: Vec::new(),
       processes: Vec::new(),
       closing_skipped: true,
+      failed_to_close: Vec::new(),
     };
   }
 
-  let captured_processes = capture_discord_processes();
+  let captured_processes = scope.capture();
   let (captured_labels, _captured_deduped) = dedupe_processes(&captured_processes);
-  let closed_processes: Vec<DiscordProcess> = close_processes(&captured_processes);
+
+  for (display, members) in group_processes(&captured_processes) {
+    log::info!("[discord] Closing {} process(es) for {display}", members.len());
+  }
+
+  let deadline = Instant::now() + CLOSE_TIMEOUT;
+  let mut closed_processes: Vec<DiscordProcess> = close_processes(&captured_processes);
+  let mut failed_to_close = Vec::new();
+
+  // Another instance (or the updater) can spawn new processes while the first
+  // batch is being killed, so sweep again until nothing is left. Processes
+  // stuck in an unkillable state are given up on once the deadline passes.
+  loop {
+    let remaining = scope.capture();
+
+    if remaining.is_empty() {
+      break;
+    }
+
+    if Instant::now() >= deadline {
+      log::warn!(
+        "[discord] {} Discord process(es) still running after {}s; giving up: {}",
+        remaining.len(),
+        CLOSE_TIMEOUT.as_secs(),
+        remaining
+          .iter()
+          .map(|proc| format!("{} ({})", proc.name, proc.pid))
+          .collect::<Vec<_>>()
+          .join(", ")
+      );
+      failed_to_close = remaining;
+      break;
+    }
+
+    log::info!(
+      "[discord] {} Discord process(es) still running; closing again",
+      remaining.len()
+    );
+    std::thread::sleep(Duration::from_millis(CLOSE_SWEEP_DELAY_MS));
+    closed_processes.extend(close_processes(&remaining));
+  }
+
+  closed_processes.retain(|proc| !failed_to_close.iter().any(|failed| failed.pid == proc.pid));
 
   let closed_snapshot: Vec<DiscordProcess> =
     if closed_processes.is_empty() && !captured_processes.is_empty() {
@@ -306,5 +950,40 @@ pub fn close_discord_clients(close_enabled: bool) -> DiscordClientsState {
     closed_clients,
     processes: cached_processes,
     closing_skipped: false,
+    failed_to_close,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn process(pid: usize, name: &str, exe: &str) -> DiscordProcess {
+    DiscordProcess {
+      pid: Pid::from(pid),
+      name: name.to_string(),
+      exe: Some(PathBuf::from(exe)),
+      cmd: vec![exe.to_string()],
+      cwd: None,
+      session_env: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn processes_sharing_an_exe_form_one_group() {
+    let processes = vec![
+      process(100, "Discord", "/opt/discord/Discord"),
+      process(101, "Discord", "/opt/discord/Discord"),
+    ];
+
+    let groups = group_processes(&processes);
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].0, "Discord");
+    assert_eq!(groups[0].1.len(), 2);
+
+    let (names, deduped) = dedupe_processes(&processes);
+    assert_eq!(names, vec!["Discord".to_string()]);
+    assert_eq!(deduped.len(), 1);
   }
 }
@@ -0,0 +1,117 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+use crate::{error::InstallerError, options};
+
+use super::{checkpoint, discord_clients};
+
+/// A patch flow that did not finish, found at startup.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptedFlow {
+  pub run_id: String,
+  /// Step that was running when the app exited, or the one that failed.
+  pub step: String,
+  /// The app exited mid-flow, as opposed to the flow reporting a failure.
+  pub crashed: bool,
+  /// Clients the run closed, which may still be closed.
+  pub closed_clients: Vec<String>,
+  /// Whether `resume_patch_flow` can continue from `step` with the current
+  /// options.
+  pub resumable: bool,
+}
+
+/// What startup found, kept for `get_interrupted_flow` since the
+/// `interrupted-flow` event can fire before the UI is listening.
+#[derive(Default)]
+pub struct InterruptedFlowState(Mutex<Option<InterruptedFlow>>);
+
+impl InterruptedFlowState {
+  fn set(&self, flow: Option<InterruptedFlow>) {
+    *self.0.lock().unwrap_or_else(|err| err.into_inner()) = flow;
+  }
+
+  fn get(&self) -> Option<InterruptedFlow> {
+    self.0.lock().unwrap_or_else(|err| err.into_inner()).clone()
+  }
+}
+
+fn detect_interrupted_flow() -> Option<InterruptedFlow> {
+  let checkpoint = checkpoint::read_checkpoint()?;
+  let resumable = options::read_user_options()
+    .map(|options| checkpoint.validate(&options).is_ok())
+    .unwrap_or(false);
+
+  Some(InterruptedFlow {
+    run_id: checkpoint.run_id,
+    step: checkpoint.failed_step,
+    crashed: checkpoint.in_progress,
+    closed_clients: checkpoint.closed_clients,
+    resumable,
+  })
+}
+
+/// Called from `setup`: reports a flow the previous session left unfinished,
+/// so the user can resume it, roll it back or dismiss it.
+pub fn report_interrupted_flow(app: &tauri::AppHandle) {
+  let flow = detect_interrupted_flow();
+
+  if let Some(flow) = &flow {
+    if flow.crashed {
+      log::warn!(
+        "[patch-flow] Run {} was interrupted during step {}",
+        flow.run_id,
+        flow.step
+      );
+    } else {
+      log::info!("[patch-flow] Run {} failed at step {} and can be resumed", flow.run_id, flow.step);
+    }
+
+    let _ = app.emit("interrupted-flow", flow);
+  }
+
+  app.state::<InterruptedFlowState>().set(flow);
+}
+
+#[tauri::command]
+pub fn get_interrupted_flow(state: tauri::State<'_, InterruptedFlowState>) -> Option<InterruptedFlow> {
+  state.get()
+}
+
+/// Forgets the interrupted flow without changing anything else.
+#[tauri::command]
+pub fn clear_interrupted_flow(state: tauri::State<'_, InterruptedFlowState>) {
+  checkpoint::clear_checkpoint();
+  state.set(None);
+}
+
+/// Reopens the Discord clients the interrupted run closed, then forgets it.
+/// The Vencord clone is left as it is; run the patch flow again to rebuild it.
+#[tauri::command(async)]
+pub fn rollback_interrupted_flow(
+  state: tauri::State<'_, InterruptedFlowState>,
+) -> Result<Vec<discord_clients::RestartOutcome>, InstallerError> {
+  let checkpoint = checkpoint::read_checkpoint()
+    .ok_or_else(|| InstallerError::not_found("No interrupted patch flow to roll back"))?;
+
+  // Clients the user already started again must not be launched twice.
+  let running = discord_clients::capture_discord_processes();
+  let closed: Vec<_> = checkpoint
+    .closed_processes
+    .into_iter()
+    .filter(|proc| !running.iter().any(|running| running.exe == proc.exe))
+    .collect();
+
+  let restarted = discord_clients::restart_processes(&closed);
+  log::info!(
+    "[patch-flow] Rolled back run {}: restarted {} client(s)",
+    checkpoint.run_id,
+    discord_clients::restarted_names(&restarted).len()
+  );
+
+  checkpoint::clear_checkpoint();
+  state.set(None);
+
+  Ok(restarted)
+}
@@ -1,5 +1,9 @@
 pub mod backup;
+pub mod checkpoint;
 pub mod discord_clients;
+pub mod interrupted;
 pub mod pipeline;
+pub mod plugin_cache;
 pub mod repo;
+pub mod sources;
 pub mod themes;
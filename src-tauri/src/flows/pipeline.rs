@@ -1,11 +1,13 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
 
-use crate::{command_utils, discord, options, run_log};
+use crate::{command_utils, dependencies, discord, error::InstallerError, logging, options, run_log};
 use crate::run_log::RunStep;
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
-use super::{backup, discord_clients, repo, themes};
+use super::{backup, checkpoint, discord_clients, repo, themes};
 
 #[derive(Serialize, Clone, Copy)]
 #[serde[rename_all = "camelCase"]]
@@ -39,6 +41,7 @@ pub enum StepStatus {
   Completed,
   Skipped,
   Pending,
+  Failed,
 }
 
 #[derive(Serialize)]
@@ -58,6 +61,49 @@ struct StepEventPayload {
   detail: Option<serde_json::Value>,
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FlowProgressPayload {
+  completed_steps: usize,
+  total_steps: usize,
+  percent: u8,
+}
+
+struct FlowProgress {
+  completed: usize,
+  total: usize,
+}
+
+impl FlowProgress {
+  fn new(total: usize) -> Self {
+    Self {
+      completed: 0,
+      total,
+    }
+  }
+
+  // Called once a step settles as completed or skipped, so skipped steps
+  // advance the bar the same as completed ones.
+  fn advance(&mut self, app: &tauri::AppHandle) {
+    self.completed = (self.completed + 1).min(self.total);
+
+    let percent = if self.total == 0 {
+      100
+    } else {
+      (self.completed * 100 / self.total) as u8
+    };
+
+    let _ = app.emit(
+      "patch-flow-progress",
+      FlowProgressPayload {
+        completed_steps: self.completed,
+        total_steps: self.total,
+        percent,
+      },
+    );
+  }
+}
+
 impl<T> StepResult<T> {
   pub fn completed(detail: T) -> Self {
     Self {
@@ -83,6 +129,14 @@ impl<T> StepResult<T> {
     }
   }
 
+  pub fn failed(message: impl Into<String>) -> Self {
+    Self {
+      status: StepStatus::Failed,
+      message: Some(message.into()),
+      detail: None,
+    }
+  }
+
   #[allow(dead_code)]
   pub fn pending(message: impl Into<String>) -> Self {
     Self {
@@ -93,14 +147,61 @@ impl<T> StepResult<T> {
   }
 }
 
+/// How long the flow waits for `confirm_reopen` before reopening Discord anyway.
+const REOPEN_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Managed state the patch flow blocks on while `confirm_before_reopen` is set.
+#[derive(Default)]
+pub struct ReopenConfirmation {
+  confirmed: Mutex<bool>,
+  signal: Condvar,
+}
+
+impl ReopenConfirmation {
+  fn reset(&self) {
+    *self.confirmed.lock().unwrap_or_else(|err| err.into_inner()) = false;
+  }
+
+  fn confirm(&self) {
+    *self.confirmed.lock().unwrap_or_else(|err| err.into_inner()) = true;
+    self.signal.notify_all();
+  }
+
+  /// Returns `false` when the timeout elapsed without a confirmation.
+  fn wait(&self, timeout: Duration) -> bool {
+    let guard = self.confirmed.lock().unwrap_or_else(|err| err.into_inner());
+    let (confirmed, _) = self
+      .signal
+      .wait_timeout_while(guard, timeout, |confirmed| !*confirmed)
+      .unwrap_or_else(|err| err.into_inner());
+    *confirmed
+  }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct AwaitingConfirmationPayload {
+  timeout_secs: u64,
+}
+
 async fn run_blocking<T, F>(task: F) -> Result<T, String>
 where
   T: Send + 'static,
   F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+  run_blocking_with(task).await
+}
+
+/// `run_blocking` for tasks with their own error type, such as `RepoError`.
+async fn run_blocking_with<T, E, F>(task: F) -> Result<T, E>
+where
+  T: Send + 'static,
+  E: From<String> + Send + 'static,
+  F: FnOnce() -> Result<T, E> + Send + 'static,
 {
   tauri::async_runtime::spawn_blocking(task)
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(|err| E::from(err.to_string()))?
 }
 
 fn emit_step_event<T: Serialize>(
@@ -123,41 +224,15 @@ fn emit_step_event<T: Serialize>(
   let _ = app.emit("patch-flow-step", payload);
 }
 
-fn resolve_selected_discord_locations(selected_ids: &[String]) -> Result<Vec<String>, String> {
+/// Whether any of the selected client ids is actually installed.
+fn has_installed_selection(selected_ids: &[String]) -> bool {
   if selected_ids.is_empty() {
-    return Ok(Vec::new());
-  }
-
-  let installs = discord::detect_all_installs();
-  let mut locations = Vec::new();
-  let mut missing = Vec::new();
-
-  for id in selected_ids {
-    let matched: Vec<String> = installs
-      .iter()
-      .filter(|inst| &inst.id == id)
-      .map(|inst| inst.path.clone())
-      .collect();
-
-    if matched.is_empty() {
-      missing.push(id.clone());
-    } else {
-      for path in matched {
-        if !locations.contains(&path) {
-          locations.push(path);
-        }
-      }
-    }
-  }
-
-  if !missing.is_empty() {
-    return Err(format!(
-      "The following Discord client selections are not installed: {}",
-      missing.join(", ")
-    ));
+    return false;
   }
 
-  Ok(locations)
+  discord::detect_all_installs()
+    .iter()
+    .any(|install| selected_ids.contains(&install.id))
 }
 
 fn variant_id_from_cli_path(path: &str) -> Option<&'static str> {
@@ -221,20 +296,48 @@ fn resolve_inject_locations(selected_ids: &[String], repo_dir: &str) -> Result<V
 
   if cli_detected.is_empty() {
     log::info!("[inject] CLI detection returned no results, falling back to static detection");
-    return resolve_selected_discord_locations(selected_ids);
+    return discord_clients::resolve_selected_discord_locations(selected_ids);
   }
 
   log::info!("[inject] CLI detected {} install(s)", cli_detected.len());
 
+  merge_inject_locations(
+    selected_ids,
+    &cli_detected,
+    &discord::custom_installs(),
+    &discord::detect_all_installs(),
+  )
+}
+
+/// Paths to inject for `selected_ids`, preferring what the CLI reported. The
+/// CLI only knows the standard locations, so custom installs are always added
+/// from the options, and ids it did not report at all (e.g. Wine installs)
+/// come from the installer's own detection in `installs`.
+fn merge_inject_locations(
+  selected_ids: &[String],
+  cli_detected: &[(String, String)],
+  custom: &[discord::DiscordInstall],
+  installs: &[discord::DiscordInstall],
+) -> Result<Vec<String>, String> {
   let mut locations = Vec::new();
   let mut missing = Vec::new();
 
   for id in selected_ids {
-    let matched: Vec<String> = cli_detected
+    let from_cli: Vec<String> = cli_detected
       .iter()
       .filter(|(cid, _)| cid == id)
       .map(|(_, path)| path.clone())
       .collect();
+    let fallback = if from_cli.is_empty() { installs } else { custom };
+    let matched: Vec<String> = from_cli
+      .into_iter()
+      .chain(
+        fallback
+          .iter()
+          .filter(|install| &install.id == id)
+          .map(|install| install.path.clone()),
+      )
+      .collect();
 
     if matched.is_empty() {
       missing.push(id.clone());
@@ -263,10 +366,73 @@ pub struct PatchFlowResult {
   pub close_discord: StepResult<Vec<String>>,
   pub backup: StepResult<backup::BackupResult>,
   pub sync_repo: StepResult<String>,
-  pub build: StepResult<String>,
+  pub build: StepResult<repo::BuildOutcome>,
   pub inject: StepResult<String>,
-  pub download_themes: StepResult<String>,
-  pub reopen_discord: StepResult<Vec<String>>,
+  pub download_themes: StepResult<Vec<themes::ThemeDownloadResult>>,
+  pub reopen_discord: StepResult<Vec<discord_clients::RestartOutcome>>,
+  pub run_log_path: Option<String>,
+  pub reopen_confirmation_timed_out: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct PatchFlowCompletePayload<'a> {
+  result: &'a PatchFlowResult,
+  succeeded: bool,
+  duration_ms: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct PatchFlowFailedPayload {
+  step: Option<String>,
+  message: String,
+  duration_ms: u64,
+  command_error: Option<command_utils::CommandError>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemesFlowResult {
+  pub download_themes: StepResult<Vec<themes::ThemeDownloadResult>>,
+  pub removed_themes: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupOnlyFlowResult {
+  pub close_discord: StepResult<Vec<String>>,
+  pub backup: StepResult<backup::BackupResult>,
+  pub reopen_discord: StepResult<Vec<discord_clients::RestartOutcome>>,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+enum FactoryResetStep {
+  CloseDiscord,
+  Unpatch,
+  Backup,
+  DeleteClone,
+  RemoveThemes,
+  ReopenDiscord,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FactoryResetStepPayload {
+  step: FactoryResetStep,
+  status: StepStatus,
+  message: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactoryResetResult {
+  pub unpatched: Vec<String>,
+  pub backup_path: Option<String>,
+  pub deleted_clone: Option<repo::RepoCloneDeletion>,
+  pub removed_themes: Vec<String>,
+  pub restarted: Vec<discord_clients::RestartOutcome>,
 }
 
 #[derive(Serialize)]
@@ -290,10 +456,10 @@ pub enum DevTestResult {
     message: String,
   },
   DownloadThemes {
-    message: String,
+    results: Vec<themes::ThemeDownloadResult>,
   },
   ReopenDiscord {
-    restarted: Vec<String>,
+    restarted: Vec<discord_clients::RestartOutcome>,
     closed_clients: Vec<String>,
     closing_skipped: bool,
   },
@@ -302,15 +468,22 @@ pub enum DevTestResult {
 fn friendly_step_error(step_id: &str, raw_err: &str) -> String {
   let lower = raw_err.to_lowercase();
   match step_id {
+    "closeDiscord" => {
+      "Some Discord processes could not be closed. Close Discord manually (or end it from the task manager) and try again.".to_string()
+    }
     "backup" => {
-      if lower.contains("permission") || lower.contains("access denied") {
+      if lower.contains("backup cancelled") {
+        "The backup was cancelled. Your Vencord installation was left in place.".to_string()
+      } else if lower.contains("permission") || lower.contains("access denied") {
         "Could not back up Vencord - permission denied. Try running as administrator.".to_string()
       } else {
         "Failed to back up your Vencord installation. See the log file for details.".to_string()
       }
     }
     "syncRepo" => {
-      if lower.contains("git") && (lower.contains("not found") || lower.contains("no such file")) {
+      if let Some(guidance) = repo::git_failure_guidance(raw_err) {
+        guidance.to_string()
+      } else if lower.contains("git") && (lower.contains("not found") || lower.contains("no such file")) {
         "Could not find git. Please ensure git is installed and available on your PATH.".to_string()
       } else if lower.contains("network")
         || lower.contains("connection")
@@ -322,19 +495,10 @@ fn friendly_step_error(step_id: &str, raw_err: &str) -> String {
         "Failed to sync the Vencord repository. See the log file for details.".to_string()
       }
     }
-    "build" => {
-      if (lower.contains("pnpm") || lower.contains("npm"))
-        && (lower.contains("not found") || lower.contains("no such file"))
-      {
-        "pnpm could not be found. Please install pnpm and try again.".to_string()
-      } else if lower.contains("exit status") || lower.contains("exited with") {
-        "The build failed. See the log file for details.".to_string()
-      } else {
-        "Failed to build Vencord. See the log file for details.".to_string()
-      }
-    }
     "inject" => {
-      if lower.contains("permission") || lower.contains("access denied") {
+      if lower.contains("no vencord build found") {
+        "No Vencord build was found - run the Build step first.".to_string()
+      } else if lower.contains("permission") || lower.contains("access denied") {
         "Failed to inject Vencord - permission denied. Try running as administrator.".to_string()
       } else if lower.contains("not found") || lower.contains("no such file") {
         "Failed to inject Vencord - could not find the Discord installation.".to_string()
@@ -343,7 +507,10 @@ fn friendly_step_error(step_id: &str, raw_err: &str) -> String {
       }
     }
     "downloadThemes" => {
-      if lower.contains("network")
+      // Already says when to retry, which the generic text would lose.
+      if lower.contains("rate limit reached") {
+        raw_err.to_string()
+      } else if lower.contains("network")
         || lower.contains("connection")
         || lower.contains("timed out")
         || lower.contains("resolve")
@@ -357,32 +524,403 @@ fn friendly_step_error(step_id: &str, raw_err: &str) -> String {
   }
 }
 
+/// Like `friendly_step_error` for the build step, keyed on the phase that was
+/// running (`None` when it failed before installing anything).
+fn friendly_build_error(phase: Option<repo::BuildPhase>, raw_err: &str) -> String {
+  let lower = raw_err.to_lowercase();
+
+  if (lower.contains("pnpm") || lower.contains("npm"))
+    && (lower.contains("not found") || lower.contains("no such file"))
+  {
+    return "pnpm could not be found. Please install pnpm and try again.".to_string();
+  }
+
+  match phase {
+    Some(repo::BuildPhase::Install) => {
+      "Installing Vencord's dependencies failed (pnpm install). Check your internet connection and see the log file for details.".to_string()
+    }
+    Some(repo::BuildPhase::Build) => {
+      "Dependencies installed, but building Vencord failed (pnpm build). See the log file for details.".to_string()
+    }
+    None if lower.contains("below the minimum supported") => {
+      "A required tool is too old to build Vencord. Update it from the Dependencies panel and try again.".to_string()
+    }
+    None => "Failed to build Vencord. See the log file for details.".to_string(),
+  }
+}
+
+/// Settles the reopen step from the restart outcomes: completed with
+/// `success_message` when every client came back, failed naming the clients
+/// that did not otherwise. The flow itself still succeeds either way.
+fn reopen_step_result(
+  record: &mut run_log::RunRecord,
+  outcomes: Result<Vec<discord_clients::RestartOutcome>, String>,
+  success_message: String,
+) -> StepResult<Vec<discord_clients::RestartOutcome>> {
+  let outcomes = match outcomes {
+    Ok(outcomes) => outcomes,
+    Err(err) => {
+      log::error!("[patch-flow] Step: reopen-discord - failed: {err}");
+      let message = "Could not restart Discord. Start it manually.".to_string();
+      record.steps.push(RunStep {
+        id: "reopenDiscord".to_string(),
+        title: "Reopen Discord".to_string(),
+        status: "failed".to_string(),
+        friendly_message: message.clone(),
+        verbose_detail: Some(err),
+      });
+      return StepResult::failed(message);
+    }
+  };
+
+  let failed: Vec<&discord_clients::RestartOutcome> =
+    outcomes.iter().filter(|outcome| !outcome.restarted).collect();
+
+  if failed.is_empty() {
+    log::info!("[patch-flow] Step: reopen-discord - completed");
+    record.steps.push(RunStep {
+      id: "reopenDiscord".to_string(),
+      title: "Reopen Discord".to_string(),
+      status: "completed".to_string(),
+      friendly_message: success_message,
+      verbose_detail: None,
+    });
+    return StepResult::completed(outcomes);
+  }
+
+  let failed_names: Vec<&str> = failed.iter().map(|outcome| outcome.name.as_str()).collect();
+  let restarted = discord_clients::restarted_names(&outcomes);
+  let message = if restarted.is_empty() {
+    format!("Could not restart {}. Start it manually.", failed_names.join(", "))
+  } else {
+    format!(
+      "Restarted {}, but could not restart {}. Start it manually.",
+      restarted.join(", "),
+      failed_names.join(", ")
+    )
+  };
+  let detail = failed
+    .iter()
+    .map(|outcome| {
+      format!(
+        "{} ({}): {}",
+        outcome.name,
+        outcome.command,
+        outcome.error.as_deref().unwrap_or("did not start")
+      )
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+  log::warn!("[patch-flow] Step: reopen-discord - failed for {}", failed_names.join(", "));
+  record.steps.push(RunStep {
+    id: "reopenDiscord".to_string(),
+    title: "Reopen Discord".to_string(),
+    status: "failed".to_string(),
+    friendly_message: message.clone(),
+    verbose_detail: Some(detail),
+  });
+  StepResult {
+    status: StepStatus::Failed,
+    message: Some(message),
+    detail: Some(outcomes),
+  }
+}
+
+/// `offline` overrides the `offline_mode` option for this run. `build_only`
+/// builds Vencord without closing Discord or injecting, so it also runs with
+/// no Discord clients selected.
 #[tauri::command]
-pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, String> {
-  log::info!("[patch-flow] Starting install workflow");
+pub async fn run_patch_flow(
+  app: tauri::AppHandle,
+  offline: Option<bool>,
+  build_only: Option<bool>,
+) -> Result<PatchFlowResult, InstallerError> {
+  run_patch_flow_from(app, None, offline, build_only.unwrap_or(false)).await
+}
+
+/// Continues the last failed patch flow from the step that failed. Runs the
+/// whole flow when there is no checkpoint or it no longer applies.
+#[tauri::command]
+pub async fn resume_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, InstallerError> {
+  let resume = run_blocking(|| {
+    let options = options::read_user_options()?;
+
+    Ok(checkpoint::read_checkpoint().filter(|checkpoint| {
+      match checkpoint.validate(&options) {
+        Ok(()) => true,
+        Err(reason) => {
+          log::warn!(
+            "[patch-flow] Cannot resume run {} ({reason}); running the full flow",
+            checkpoint.run_id
+          );
+          false
+        }
+      }
+    }))
+  })
+  .await?;
 
+  if let Some(checkpoint) = &resume {
+    log::info!(
+      "[patch-flow] Resuming run {} from step {}",
+      checkpoint.run_id,
+      checkpoint.failed_step
+    );
+  }
+
+  let offline = resume.as_ref().and_then(|checkpoint| checkpoint.offline);
+  let build_only = resume.as_ref().is_some_and(|checkpoint| checkpoint.build_only);
+  run_patch_flow_from(app, resume, offline, build_only).await
+}
+
+async fn run_patch_flow_from(
+  app: tauri::AppHandle,
+  resume: Option<checkpoint::FlowCheckpoint>,
+  offline: Option<bool>,
+  build_only: bool,
+) -> Result<PatchFlowResult, InstallerError> {
+  let started = Instant::now();
   let mut record = run_log::new_record();
+  let mut flow_checkpoint = checkpoint::FlowCheckpoint {
+    run_id: record.id.clone(),
+    ..Default::default()
+  };
+  let result = execute_patch_flow(
+    app.clone(),
+    &mut record,
+    &mut flow_checkpoint,
+    resume.as_ref(),
+    offline,
+    build_only,
+  )
+  .await;
+  let duration_ms = started.elapsed().as_millis() as u64;
+
+  // Always finish with exactly one terminal event so the UI never waits on a
+  // flow that already returned.
+  match result {
+    Ok(flow) => {
+      checkpoint::clear_checkpoint();
+      let _ = app.emit(
+        "patch-flow-complete",
+        PatchFlowCompletePayload {
+          result: &flow,
+          succeeded: true,
+          duration_ms,
+        },
+      );
+
+      Ok(flow)
+    }
+    Err(message) => {
+      let step = record
+        .steps
+        .iter()
+        .find(|step| step.status == "failed")
+        .map(|step| step.id.clone());
+
+      if let Some(step) = &step {
+        flow_checkpoint.failed_step = step.clone();
+        flow_checkpoint.in_progress = false;
+        checkpoint::write_checkpoint(&flow_checkpoint);
+      } else if flow_checkpoint.in_progress {
+        // Failed outside any step, so there is nothing to resume from; do not
+        // leave it looking like an interrupted run.
+        checkpoint::clear_checkpoint();
+      }
+
+      let error = flow_error(step.as_deref(), &message, record.command_error.as_ref());
+
+      let _ = app.emit(
+        "patch-flow-failed",
+        PatchFlowFailedPayload {
+          step,
+          message,
+          duration_ms,
+          command_error: record.command_error.clone(),
+        },
+      );
+
+      Err(error)
+    }
+  }
+}
+
+/// Categorizes a failed patch flow by the step that failed, keeping the
+/// git/pnpm output when the step recorded one.
+fn flow_error(
+  step: Option<&str>,
+  message: &str,
+  command_error: Option<&command_utils::CommandError>,
+) -> InstallerError {
+  match (step, command_error) {
+    (Some("syncRepo" | "build" | "inject"), Some(err)) => {
+      InstallerError::from(err.clone()).with_message(message)
+    }
+    (Some("backup"), _) => InstallerError::filesystem(message),
+    (Some("downloadThemes"), _) => InstallerError::network(message),
+    _ => InstallerError::from(message),
+  }
+}
+
+async fn execute_patch_flow(
+  app: tauri::AppHandle,
+  record: &mut run_log::RunRecord,
+  flow_checkpoint: &mut checkpoint::FlowCheckpoint,
+  resume: Option<&checkpoint::FlowCheckpoint>,
+  offline: Option<bool>,
+  build_only: bool,
+) -> Result<PatchFlowResult, String> {
+  let resumed_past = |step: &str| resume.is_some_and(|checkpoint| checkpoint.completed(step));
+
+  let mut progress = FlowProgress::new(run_log::FLOW_STEPS.len());
 
   let options = run_blocking(options::read_user_options).await?;
+  let run_log_guard =
+    logging::begin_run_log(&record.id, "patch-flow", options.max_run_log_count.unwrap_or(50));
+
+  log::info!("[patch-flow] Starting install workflow");
   let plugin_urls = options::resolve_plugin_repositories(&options);
   let themes = options::resolve_themes(&options);
+  flow_checkpoint.repo_dir = options.vencord_repo_dir.clone();
+  flow_checkpoint.plugin_urls = plugin_urls.clone();
+  flow_checkpoint.offline = offline;
+  flow_checkpoint.build_only = build_only;
+
+  // Conflicting plugins only fail much later in the build, so refuse up front.
+  if let Err(err) = options::check_plugin_conflicts(&options) {
+    log::error!("[patch-flow] {err}");
+    run_log::finalize(record, "failed");
+    run_log::write_run(record);
+    return Err(err);
+  }
+
+  let offline = offline.unwrap_or(options.offline_mode);
+  if offline {
+    log::info!("[patch-flow] Offline mode: using the existing clone at {}", options.vencord_repo_dir);
+
+    let has_clone = run_blocking({
+      let repo_dir = options.vencord_repo_dir.clone();
+      move || Ok(repo::is_git_repo(&repo_dir).unwrap_or(false))
+    })
+    .await?;
+
+    // Checked before closing Discord so nothing has changed yet.
+    if !has_clone {
+      let err = format!(
+        "Offline mode needs an existing Vencord clone, but {} is not one",
+        options.vencord_repo_dir
+      );
+      log::error!("[patch-flow] {err}");
+      run_log::finalize(record, "failed");
+      run_log::write_run(record);
+      return Err(err);
+    }
+  }
+
+  // Without a client to inject into the flow would close Discord and rebuild
+  // for nothing, so refuse before anything changes.
+  if !build_only {
+    let has_client = run_blocking({
+      let selected = options.selected_discord_clients.clone();
+      move || Ok(has_installed_selection(&selected))
+    })
+    .await?;
+
+    if !has_client {
+      let err = "Select at least one Discord client to patch, or run a build-only patch".to_string();
+      log::error!("[patch-flow] {err}");
+      run_log::finalize(record, "failed");
+      run_log::write_run(record);
+      return Err(err);
+    }
+  }
 
   log::info!("[patch-flow] Step: close-discord - starting");
+  checkpoint::mark_step(flow_checkpoint, "closeDiscord");
   emit_step_event(
     &app,
     PatchFlowStep::CloseDiscord,
     &StepResult::<()>::running("Closing Discord clients"),
   );
 
-  let discord_state = run_blocking({
-    let close_enabled = options.close_discord_on_backup;
-    move || Ok(discord_clients::close_discord_clients(close_enabled))
-  })
-  .await?;
+  // Nothing is injected in a build-only run, so Discord can keep running.
+  let mut discord_state = if build_only {
+    discord_clients::DiscordClientsState {
+      closed_clients: Vec::new(),
+      processes: Vec::new(),
+      closing_skipped: true,
+      failed_to_close: Vec::new(),
+    }
+  } else {
+    run_blocking({
+      let close_enabled = options.close_discord_on_backup;
+      let scope = discord_clients::close_scope(&options);
+      move || Ok(discord_clients::close_discord_clients(close_enabled, &scope))
+    })
+    .await?
+  };
+  let close_skip_reason = if build_only {
+    "Build-only run; Discord was left running"
+  } else {
+    "Closing Discord is disabled in settings"
+  };
+
+  // Clients the failed run closed and left closed still need reopening at
+  // the end of this one.
+  if let Some(resume) = resume {
+    for process in &resume.closed_processes {
+      if !discord_state.processes.iter().any(|proc| proc.exe == process.exe) {
+        discord_state.processes.push(process.clone());
+      }
+    }
+    for client in &resume.closed_clients {
+      if !discord_state.closed_clients.contains(client) {
+        discord_state.closed_clients.push(client.clone());
+      }
+    }
+  }
+  flow_checkpoint.closed_processes = discord_state.processes.clone();
+  flow_checkpoint.closed_clients = discord_state.closed_clients.clone();
+
+  let restart_on_failure = !discord_state.closing_skipped && options.reopen_discord_after_patch;
+
+  // Patching while a client still holds its files open would leave a
+  // half-written install, so stop here instead.
+  if !discord_state.failed_to_close.is_empty() {
+    let err = format!(
+      "Discord process(es) did not exit: {}",
+      discord_state
+        .failed_to_close
+        .iter()
+        .map(|proc| format!("{} (pid {})", proc.name, proc.pid))
+        .collect::<Vec<_>>()
+        .join(", ")
+    );
+    log::error!("[patch-flow] Step: close-discord - failed: {err}");
+    if restart_on_failure {
+      let _ = run_blocking({
+        let processes = discord_state.processes.clone();
+        move || Ok(discord_clients::restart_processes(&processes))
+      })
+      .await;
+    }
+    record.steps.push(RunStep {
+      id: "closeDiscord".to_string(),
+      title: "Close Discord".to_string(),
+      status: "failed".to_string(),
+      friendly_message: friendly_step_error("closeDiscord", &err),
+      verbose_detail: Some(err.clone()),
+    });
+    run_log::finalize(record, "failed");
+    run_log::write_run(&record);
+    return Err(friendly_step_error("closeDiscord", &err));
+  }
 
   let close_step = if discord_state.closing_skipped {
-    log::info!("[patch-flow] Step: close-discord - skipped (disabled in settings)");
-    StepResult::skipped("Closing Discord is disabled in settings")
+    log::info!("[patch-flow] Step: close-discord - skipped ({close_skip_reason})");
+    StepResult::skipped(close_skip_reason)
   } else {
     log::info!(
       "[patch-flow] Step: close-discord - completed ({} client(s) closed)",
@@ -391,6 +929,7 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
     StepResult::completed(discord_state.closed_clients.clone())
   };
   emit_step_event(&app, PatchFlowStep::CloseDiscord, &close_step);
+  progress.advance(&app);
   record.steps.push(RunStep {
     id: "closeDiscord".to_string(),
     title: "Close Discord".to_string(),
@@ -400,7 +939,7 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
       "completed".to_string()
     },
     friendly_message: if discord_state.closing_skipped {
-      "Discord was not closed (disabled in settings)".to_string()
+      close_skip_reason.to_string()
     } else {
       format!("{} Discord client(s) closed", discord_state.closed_clients.len())
     },
@@ -411,21 +950,29 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
   let theme_sources = options::resolve_themes(&options);
 
   log::info!("[patch-flow] Step: backup - starting");
+  checkpoint::mark_step(flow_checkpoint, "backup");
   emit_step_event(
     &app,
     PatchFlowStep::Backup,
     &StepResult::<()>::running("Backing up Vencord installation"),
   );
 
-  let backup_step = if vencord_install.exists() {
-    let backup_path = match run_blocking({
+  let backup_step = if resumed_past("backup") {
+    log::info!("[patch-flow] Step: backup - skipped (done by the resumed run)");
+    StepResult::skipped("Already backed up by the run being resumed")
+  } else if offline {
+    log::info!("[patch-flow] Step: backup - skipped (offline mode)");
+    StepResult::skipped("Skipped in offline mode; the existing clone is built in place")
+  } else {
+    let backup_outcome = match run_blocking({
       let vencord_install = vencord_install.clone();
       let theme_sources = theme_sources.clone();
-      move || backup::move_vencord_install(&vencord_install, &theme_sources)
+      let settings = backup::BackupSettings::from_options(&options);
+      move || backup::move_vencord_install(&vencord_install, &theme_sources, &settings)
     })
     .await
     {
-      Ok(p) => p,
+      Ok(outcome) => outcome,
       Err(err) => {
         record.steps.push(RunStep {
           id: "backup".to_string(),
@@ -434,49 +981,64 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
           friendly_message: friendly_step_error("backup", &err),
           verbose_detail: Some(err.clone()),
         });
-        run_log::finalize(&mut record, "failed");
+        run_log::finalize(record, "failed");
         run_log::write_run(&record);
         return Err(friendly_step_error("backup", &err));
       }
     };
 
-    if let Err(err) = run_blocking({
-      let max_count = options.max_backup_count;
-      let max_size = options.max_backup_size_mb;
-      move || backup::apply_backup_limits(max_count, max_size)
-    })
-    .await
-    {
-      record.steps.push(RunStep {
-        id: "backup".to_string(),
-        title: "Backup Vencord".to_string(),
-        status: "failed".to_string(),
-        friendly_message: friendly_step_error("backup", &err),
-        verbose_detail: Some(err.clone()),
-      });
-      run_log::finalize(&mut record, "failed");
-      run_log::write_run(&record);
-      return Err(friendly_step_error("backup", &err));
+    if let Some(backup_path) = backup_outcome {
+      let pruned_backups = match run_blocking({
+        let max_count = options.max_backup_count;
+        let max_size = options.max_backup_size_mb;
+        move || backup::apply_backup_limits(max_count, max_size)
+      })
+      .await
+      {
+        Ok(pruned) => pruned,
+        Err(err) => {
+          record.steps.push(RunStep {
+            id: "backup".to_string(),
+            title: "Backup Vencord".to_string(),
+            status: "failed".to_string(),
+            friendly_message: friendly_step_error("backup", &err),
+            verbose_detail: Some(err.clone()),
+          });
+          run_log::finalize(record, "failed");
+          run_log::write_run(&record);
+          return Err(friendly_step_error("backup", &err));
+        }
+      };
+
+      let backup_result = backup::BackupResult {
+        source_path: vencord_install.to_string_lossy().into_owned(),
+        backup_path: backup_path.to_string_lossy().into_owned(),
+        closed_clients: discord_state.closed_clients.clone(),
+        restarted_clients: Vec::new(),
+        closing_skipped: discord_state.closing_skipped,
+        pruned_backups,
+      };
+
+      log::info!("[patch-flow] Step: backup - completed");
+      StepResult {
+        message: (pruned_backups > 0).then(|| {
+          format!(
+            "Saved to {}; pruned {pruned_backups} old backup(s)",
+            backup_path.display()
+          )
+        }),
+        ..StepResult::completed(backup_result)
+      }
+    } else {
+      log::info!(
+        "[patch-flow] Step: backup - skipped (no existing install at {})",
+        vencord_install.display()
+      );
+      StepResult::skipped("No existing install to back up")
     }
-
-    let backup_result = backup::BackupResult {
-      source_path: vencord_install.to_string_lossy().into_owned(),
-      backup_path: backup_path.to_string_lossy().into_owned(),
-      closed_clients: discord_state.closed_clients.clone(),
-      restarted_clients: Vec::new(),
-      closing_skipped: discord_state.closing_skipped,
-    };
-
-    log::info!("[patch-flow] Step: backup - completed");
-    StepResult::completed(backup_result)
-  } else {
-    log::info!("[patch-flow] Step: backup - skipped (no existing install)");
-    StepResult::skipped(format!(
-      "No Vencord installation found at {}; skipping backup",
-      vencord_install.display()
-    ))
   };
   emit_step_event(&app, PatchFlowStep::Backup, &backup_step);
+  progress.advance(&app);
   record.steps.push(RunStep {
     id: "backup".to_string(),
     title: "Backup Vencord".to_string(),
@@ -498,170 +1060,235 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
   });
 
   log::info!("[patch-flow] Step: sync-repo - starting");
+  checkpoint::mark_step(flow_checkpoint, "syncRepo");
   emit_step_event(
     &app,
     PatchFlowStep::SyncRepo,
     &StepResult::<()>::running("Syncing Vencord repository"),
   );
 
-  let sync_path = match run_blocking({
-    let repo_url = options.vencord_repo_url.clone();
-    let repo_dir = options.vencord_repo_dir.clone();
-    let plugin_urls = plugin_urls.clone();
-    move || repo::sync_vencord_repo(&repo_url, &repo_dir, &plugin_urls)
-  })
-  .await
-  {
-    Ok(path) => path,
-    Err(err) => {
-      log::error!("[patch-flow] Step: sync-repo - failed: {err}");
-      if !discord_state.closing_skipped {
-        let _ = run_blocking({
-          let processes = discord_state.processes.clone();
-          move || Ok(discord_clients::restart_processes(&processes))
-        })
-        .await;
-      }
-      record.steps.push(RunStep {
-        id: "syncRepo".to_string(),
-        title: "Sync repository".to_string(),
-        status: "failed".to_string(),
-        friendly_message: friendly_step_error("syncRepo", &err),
-        verbose_detail: Some(err.clone()),
-      });
-      run_log::finalize(&mut record, "failed");
-      run_log::write_run(&record);
-      return Err(friendly_step_error("syncRepo", &err));
-    }
-  };
-
-  log::info!("[patch-flow] Step: sync-repo - completed at {sync_path}");
-  let sync_step = StepResult::completed(sync_path.clone());
-  emit_step_event(&app, PatchFlowStep::SyncRepo, &sync_step);
-  record.steps.push(RunStep {
-    id: "syncRepo".to_string(),
-    title: "Sync repository".to_string(),
-    status: "completed".to_string(),
-    friendly_message: "Repository synced successfully".to_string(),
-    verbose_detail: None,
-  });
+  let resumed_sync_path = resume
+    .filter(|checkpoint| checkpoint.completed("syncRepo"))
+    .and_then(|checkpoint| checkpoint.sync_path.clone());
+
+  let sync_path = if let Some(path) = resumed_sync_path.clone() {
+    path
+  } else if offline {
+    options.vencord_repo_dir.clone()
+  } else {
+    match run_blocking_with({
+      let repo_url = options.vencord_repo_url.clone();
+      let repo_dir = options.vencord_repo_dir.clone();
+      let plugin_urls = plugin_urls.clone();
+      let userplugins_dir = options.userplugins_dir.clone();
+      let reference_repo = options.git_reference_repo.clone();
+      let plugin_cache_mb = options.max_plugin_cache_mb;
+      move || {
+        repo::sync_vencord_repo(
+          &repo_url,
+          &repo_dir,
+          &plugin_urls,
+          userplugins_dir.as_deref(),
+          reference_repo.as_deref(),
+          plugin_cache_mb,
+        )
+      }
+    })
+    .await
+    {
+      Ok(path) => path,
+      Err(err) => {
+        record.command_error = err.command().cloned();
+        let err = err.to_string();
+        log::error!("[patch-flow] Step: sync-repo - failed: {err}");
+        if restart_on_failure {
+          let _ = run_blocking({
+            let processes = discord_state.processes.clone();
+            move || Ok(discord_clients::restart_processes(&processes))
+          })
+          .await;
+        }
+        record.steps.push(RunStep {
+          id: "syncRepo".to_string(),
+          title: "Sync repository".to_string(),
+          status: "failed".to_string(),
+          friendly_message: friendly_step_error("syncRepo", &err),
+          verbose_detail: Some(err.clone()),
+        });
+        run_log::finalize(record, "failed");
+        run_log::write_run(&record);
+        return Err(friendly_step_error("syncRepo", &err));
+      }
+    }
+  };
+  flow_checkpoint.sync_path = Some(sync_path.clone());
+
+  let sync_step = if resumed_sync_path.is_some() {
+    log::info!("[patch-flow] Step: sync-repo - skipped (reusing {sync_path} from the resumed run)");
+    record.steps.push(RunStep {
+      id: "syncRepo".to_string(),
+      title: "Sync repository".to_string(),
+      status: "skipped".to_string(),
+      friendly_message: "Reused the repository synced by the run being resumed".to_string(),
+      verbose_detail: None,
+    });
+    StepResult::skipped("Reused the repository synced by the run being resumed")
+  } else if offline {
+    log::info!("[patch-flow] Step: sync-repo - skipped (offline mode)");
+    record.steps.push(RunStep {
+      id: "syncRepo".to_string(),
+      title: "Sync repository".to_string(),
+      status: "skipped".to_string(),
+      friendly_message: "Skipped in offline mode; using the existing clone".to_string(),
+      verbose_detail: None,
+    });
+    StepResult::skipped("Skipped in offline mode; using the existing clone")
+  } else {
+    log::info!("[patch-flow] Step: sync-repo - completed at {sync_path}");
+    let cloud_warning = repo::cloud_sync_warning(&sync_path);
+    if let Some(warning) = &cloud_warning {
+      log::warn!("[patch-flow] {warning}");
+    }
+    record.steps.push(RunStep {
+      id: "syncRepo".to_string(),
+      title: "Sync repository".to_string(),
+      status: "completed".to_string(),
+      friendly_message: match &cloud_warning {
+        Some(warning) => format!("Repository synced successfully. {warning}"),
+        None => "Repository synced successfully".to_string(),
+      },
+      verbose_detail: None,
+    });
+    StepResult {
+      message: cloud_warning.clone(),
+      ..StepResult::completed(sync_path.clone())
+    }
+  };
+  emit_step_event(&app, PatchFlowStep::SyncRepo, &sync_step);
+  progress.advance(&app);
 
   log::info!("[patch-flow] Step: build - starting");
+  checkpoint::mark_step(flow_checkpoint, "build");
   emit_step_event(
     &app,
     PatchFlowStep::Build,
     &StepResult::<()>::running("Building Vencord artifacts"),
   );
 
-  let build_step = match run_blocking({
-    let sync_path = sync_path.clone();
-    move || repo::build_vencord_repo(&sync_path)
-  })
-  .await
-  {
-    Ok((message, verbose)) => {
-      log::info!("[patch-flow] Step: build - completed");
-      log::debug!("[patch-flow] Build output: {verbose}");
-      record.steps.push(RunStep {
-        id: "build".to_string(),
-        title: "Build files".to_string(),
-        status: "completed".to_string(),
-        friendly_message: "Vencord built successfully".to_string(),
-        verbose_detail: Some(verbose),
-      });
-      StepResult::completed(message)
-    }
-    Err(err) => {
-      log::error!("[patch-flow] Step: build - failed: {err}");
-      if !discord_state.closing_skipped {
-        let _ = run_blocking({
-          let processes = discord_state.processes.clone();
-          move || Ok(discord_clients::restart_processes(&processes))
+  let build_step = if resumed_past("build") {
+    log::info!("[patch-flow] Step: build - skipped (reusing the resumed run's build)");
+    record.steps.push(RunStep {
+      id: "build".to_string(),
+      title: "Build files".to_string(),
+      status: "skipped".to_string(),
+      friendly_message: "Reused the build from the run being resumed".to_string(),
+      verbose_detail: None,
+    });
+    StepResult::skipped("Reused the build from the run being resumed")
+  } else {
+    // The phase that was running when the build failed, if it got that far.
+    let build_phase = Arc::new(Mutex::new(None));
+
+    match run_blocking_with({
+      let app = app.clone();
+      let sync_path = sync_path.clone();
+      let tools = repo::BuildTools::from_options(&options);
+      let build_phase = Arc::clone(&build_phase);
+      move || {
+        dependencies::ensure_minimum_versions()?;
+        repo::build_vencord_repo(&sync_path, &tools, offline, |phase| {
+          *build_phase.lock().unwrap_or_else(|e| e.into_inner()) = Some(phase);
+          log::info!("[patch-flow] Step: build - {}", phase.running_message());
+          emit_step_event(
+            &app,
+            PatchFlowStep::Build,
+            &StepResult::<()>::running(phase.running_message()),
+          );
         })
-        .await;
       }
-      record.steps.push(RunStep {
-        id: "build".to_string(),
-        title: "Build files".to_string(),
-        status: "failed".to_string(),
-        friendly_message: friendly_step_error("build", &err),
-        verbose_detail: Some(err.clone()),
-      });
-      run_log::finalize(&mut record, "failed");
-      run_log::write_run(&record);
-      return Err(friendly_step_error("build", &err));
+    })
+    .await
+    {
+      Ok((outcome, verbose)) => {
+        log::info!("[patch-flow] Step: build - completed");
+        log::debug!("[patch-flow] Build output: {verbose}");
+        record.steps.push(RunStep {
+          id: "build".to_string(),
+          title: "Build files".to_string(),
+          status: "completed".to_string(),
+          friendly_message: "Vencord built successfully".to_string(),
+          verbose_detail: Some(verbose),
+        });
+        StepResult::completed(outcome)
+      }
+      Err(err) => {
+        record.command_error = err.command().cloned();
+        let err = err.to_string();
+        let phase = *build_phase.lock().unwrap_or_else(|e| e.into_inner());
+        log::error!("[patch-flow] Step: build - failed: {err}");
+        if restart_on_failure {
+          let _ = run_blocking({
+            let processes = discord_state.processes.clone();
+            move || Ok(discord_clients::restart_processes(&processes))
+          })
+          .await;
+        }
+        record.steps.push(RunStep {
+          id: "build".to_string(),
+          title: "Build files".to_string(),
+          status: "failed".to_string(),
+          friendly_message: friendly_build_error(phase, &err),
+          verbose_detail: Some(err.clone()),
+        });
+        run_log::finalize(record, "failed");
+        run_log::write_run(&record);
+        return Err(friendly_build_error(phase, &err));
+      }
     }
   };
   emit_step_event(&app, PatchFlowStep::Build, &build_step);
+  progress.advance(&app);
 
   log::info!("[patch-flow] Step: inject - starting");
+  checkpoint::mark_step(flow_checkpoint, "inject");
   emit_step_event(
     &app,
     PatchFlowStep::Inject,
     &StepResult::<()>::running("Injecting patched files"),
   );
 
-  let inject_locations = match run_blocking({
-    let selected = options.selected_discord_clients.clone();
-    let sync = sync_path.clone();
-    move || resolve_inject_locations(&selected, &sync)
-  })
-  .await
-  {
-    Ok(locations) => locations,
-    Err(err) => {
-      log::error!("[patch-flow] Step: inject - failed resolving locations: {err}");
-      if !discord_state.closing_skipped {
-        let _ = run_blocking({
-          let processes = discord_state.processes.clone();
-          move || Ok(discord_clients::restart_processes(&processes))
-        })
-        .await;
-      }
-      record.steps.push(RunStep {
-        id: "inject".to_string(),
-        title: "Inject Vencord".to_string(),
-        status: "failed".to_string(),
-        friendly_message: friendly_step_error("inject", &err),
-        verbose_detail: Some(err.clone()),
-      });
-      run_log::finalize(&mut record, "failed");
-      run_log::write_run(&record);
-      return Err(friendly_step_error("inject", &err));
-    }
-  };
-
-  let inject_step = if inject_locations.is_empty() {
-    log::info!("[patch-flow] Step: inject - skipped (no clients selected)");
+  let inject_step = if resumed_past("inject") {
+    log::info!("[patch-flow] Step: inject - skipped (done by the resumed run)");
     record.steps.push(RunStep {
       id: "inject".to_string(),
       title: "Inject Vencord".to_string(),
       status: "skipped".to_string(),
-      friendly_message: "No Discord clients selected for injection".to_string(),
+      friendly_message: "Already injected by the run being resumed".to_string(),
       verbose_detail: None,
     });
-    StepResult::skipped("No Discord clients selected for injection")
+    StepResult::skipped("Already injected by the run being resumed")
+  } else if build_only {
+    log::info!("[patch-flow] Step: inject - skipped (build-only run)");
+    record.steps.push(RunStep {
+      id: "inject".to_string(),
+      title: "Inject Vencord".to_string(),
+      status: "skipped".to_string(),
+      friendly_message: "Build-only run; nothing was injected".to_string(),
+      verbose_detail: None,
+    });
+    StepResult::skipped("Build-only run; nothing was injected")
   } else {
-    match run_blocking({
-      let sync_path = sync_path.clone();
-      move || repo::inject_vencord_repo(&sync_path, &inject_locations)
+    let inject_locations = match run_blocking({
+      let selected = options.selected_discord_clients.clone();
+      let sync = sync_path.clone();
+      move || resolve_inject_locations(&selected, &sync)
     })
     .await
     {
-      Ok((message, verbose)) => {
-        log::info!("[patch-flow] Step: inject - completed");
-        record.steps.push(RunStep {
-          id: "inject".to_string(),
-          title: "Inject Vencord".to_string(),
-          status: "completed".to_string(),
-          friendly_message: "Vencord injected into Discord successfully".to_string(),
-          verbose_detail: if verbose.is_empty() { None } else { Some(verbose) },
-        });
-        StepResult::completed(message)
-      }
+      Ok(locations) => locations,
       Err(err) => {
-        log::error!("[patch-flow] Step: inject - failed: {err}");
-        if !discord_state.closing_skipped {
+        log::error!("[patch-flow] Step: inject - failed resolving locations: {err}");
+        if restart_on_failure {
           let _ = run_blocking({
             let processes = discord_state.processes.clone();
             move || Ok(discord_clients::restart_processes(&processes))
@@ -675,22 +1302,92 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
           friendly_message: friendly_step_error("inject", &err),
           verbose_detail: Some(err.clone()),
         });
-        run_log::finalize(&mut record, "failed");
+        run_log::finalize(record, "failed");
         run_log::write_run(&record);
         return Err(friendly_step_error("inject", &err));
       }
+    };
+
+    if inject_locations.is_empty() {
+      log::info!("[patch-flow] Step: inject - skipped (no clients selected)");
+      record.steps.push(RunStep {
+        id: "inject".to_string(),
+        title: "Inject Vencord".to_string(),
+        status: "skipped".to_string(),
+        friendly_message: "No Discord clients selected for injection".to_string(),
+        verbose_detail: None,
+      });
+      StepResult::skipped("No Discord clients selected for injection")
+    } else {
+      match run_blocking({
+        let sync_path = sync_path.clone();
+        let tools = repo::BuildTools::from_options(&options);
+        move || repo::inject_vencord_repo(&sync_path, &inject_locations, &tools)
+      })
+      .await
+      {
+        Ok((message, verbose)) => {
+          log::info!("[patch-flow] Step: inject - completed");
+          record.build_fingerprint = run_blocking({
+            let sync_path = sync_path.clone();
+            move || Ok(repo::build_fingerprint(Path::new(&sync_path)))
+          })
+          .await
+          .unwrap_or(None);
+          record.steps.push(RunStep {
+            id: "inject".to_string(),
+            title: "Inject Vencord".to_string(),
+            status: "completed".to_string(),
+            friendly_message: "Vencord injected into Discord successfully".to_string(),
+            verbose_detail: if verbose.is_empty() { None } else { Some(verbose) },
+          });
+          StepResult::completed(message)
+        }
+        Err(err) => {
+          log::error!("[patch-flow] Step: inject - failed: {err}");
+          if restart_on_failure {
+            let _ = run_blocking({
+              let processes = discord_state.processes.clone();
+              move || Ok(discord_clients::restart_processes(&processes))
+            })
+            .await;
+          }
+          record.steps.push(RunStep {
+            id: "inject".to_string(),
+            title: "Inject Vencord".to_string(),
+            status: "failed".to_string(),
+            friendly_message: friendly_step_error("inject", &err),
+            verbose_detail: Some(err.clone()),
+          });
+          run_log::finalize(record, "failed");
+          run_log::write_run(&record);
+          return Err(friendly_step_error("inject", &err));
+        }
+      }
     }
   };
   emit_step_event(&app, PatchFlowStep::Inject, &inject_step);
+  progress.advance(&app);
 
   log::info!("[patch-flow] Step: download-themes - starting");
+  checkpoint::mark_step(flow_checkpoint, "downloadThemes");
   emit_step_event(
     &app,
     PatchFlowStep::DownloadThemes,
     &StepResult::<()>::running("Downloading themes"),
   );
 
-  let themes_step = if themes.is_empty() {
+  let themes_step = if offline {
+    log::info!("[patch-flow] Step: download-themes - skipped (offline mode)");
+    record.steps.push(RunStep {
+      id: "downloadThemes".to_string(),
+      title: "Download themes".to_string(),
+      status: "skipped".to_string(),
+      friendly_message: "Skipped in offline mode".to_string(),
+      verbose_detail: None,
+    });
+    StepResult::skipped("Skipped in offline mode")
+  } else if themes.is_empty() {
     log::info!("[patch-flow] Step: download-themes - skipped (none enabled)");
     record.steps.push(RunStep {
       id: "downloadThemes".to_string(),
@@ -703,24 +1400,38 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
   } else {
     match run_blocking({
       let themes = themes.clone();
-      move || themes::download_themes(&themes)
+      let app = app.clone();
+      move || themes::download_themes(&themes, &themes::theme_dirs()?, Some(&app))
     })
     .await
     {
-      Ok(message) => {
-        log::info!("[patch-flow] Step: download-themes - completed");
+      Ok(results) => {
+        let summary = themes::summarize_theme_downloads(&results);
+        let failed = results
+          .iter()
+          .filter(|result| result.status != themes::ThemeDownloadStatus::Downloaded)
+          .count();
+        log::info!("[patch-flow] Step: download-themes - completed ({summary})");
         record.steps.push(RunStep {
           id: "downloadThemes".to_string(),
           title: "Download themes".to_string(),
           status: "completed".to_string(),
-          friendly_message: "Themes downloaded successfully".to_string(),
-          verbose_detail: None,
+          friendly_message: if failed == 0 {
+            "Themes downloaded successfully".to_string()
+          } else {
+            format!("{failed} of {} theme(s) could not be downloaded", results.len())
+          },
+          verbose_detail: (failed > 0).then(|| summary.clone()),
         });
-        StepResult::completed(message)
+        StepResult {
+          status: StepStatus::Completed,
+          message: Some(summary),
+          detail: Some(results),
+        }
       }
       Err(err) => {
         log::error!("[patch-flow] Step: download-themes - failed: {err}");
-        if !discord_state.closing_skipped {
+        if restart_on_failure {
           let _ = run_blocking({
             let processes = discord_state.processes.clone();
             move || Ok(discord_clients::restart_processes(&processes))
@@ -734,22 +1445,83 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
           friendly_message: friendly_step_error("downloadThemes", &err),
           verbose_detail: Some(err.clone()),
         });
-        run_log::finalize(&mut record, "failed");
+        run_log::finalize(record, "failed");
         run_log::write_run(&record);
         return Err(friendly_step_error("downloadThemes", &err));
       }
     }
   };
   emit_step_event(&app, PatchFlowStep::DownloadThemes, &themes_step);
+  progress.advance(&app);
+
+  let will_reopen = !build_only
+    && options.reopen_discord_after_patch
+    && (!discord_state.closing_skipped || options.always_restart_discord);
+  let mut reopen_confirmation_timed_out = false;
+  if options.confirm_before_reopen && will_reopen {
+    log::info!("[patch-flow] Waiting for confirmation before reopening Discord");
+    app.state::<ReopenConfirmation>().reset();
+    let _ = app.emit(
+      "patch-flow-awaiting-confirmation",
+      AwaitingConfirmationPayload {
+        timeout_secs: REOPEN_CONFIRMATION_TIMEOUT.as_secs(),
+      },
+    );
+
+    let confirmed = run_blocking({
+      let app = app.clone();
+      move || Ok(app.state::<ReopenConfirmation>().wait(REOPEN_CONFIRMATION_TIMEOUT))
+    })
+    .await
+    .unwrap_or(false);
+
+    if !confirmed {
+      log::warn!("[patch-flow] No confirmation received in time; reopening Discord anyway");
+      reopen_confirmation_timed_out = true;
+    }
+  }
 
   log::info!("[patch-flow] Step: reopen-discord - starting");
+  checkpoint::mark_step(flow_checkpoint, "reopenDiscord");
   emit_step_event(
     &app,
     PatchFlowStep::ReopenDiscord,
     &StepResult::<()>::running("Restarting Discord clients"),
   );
 
-  let reopen_step = if discord_state.closing_skipped {
+  let reopen_step = if build_only {
+    log::info!("[patch-flow] Step: reopen-discord - skipped (build-only run)");
+    record.steps.push(RunStep {
+      id: "reopenDiscord".to_string(),
+      title: "Reopen Discord".to_string(),
+      status: "skipped".to_string(),
+      friendly_message: "Build-only run; Discord was left running".to_string(),
+      verbose_detail: None,
+    });
+    StepResult::skipped("Build-only run; Discord was left running")
+  } else if options.reopen_discord_after_patch
+    && options.always_restart_discord
+    && (discord_state.closing_skipped || discord_state.processes.is_empty())
+  {
+    // Nothing the flow closed needs restarting, so start the selected clients
+    // from their install paths instead.
+    let launched = run_blocking({
+      let selected = options.selected_discord_clients.clone();
+      move || Ok(discord_clients::launch_installed_clients(&selected))
+    })
+    .await;
+
+    let launched_names = launched
+      .as_ref()
+      .map(|outcomes| discord_clients::restarted_names(outcomes))
+      .unwrap_or_default();
+    let success_message = if launched_names.is_empty() {
+      "No selected Discord clients needed starting".to_string()
+    } else {
+      format!("Started {}", launched_names.join(", "))
+    };
+    reopen_step_result(record, launched, success_message)
+  } else if discord_state.closing_skipped {
     log::info!("[patch-flow] Step: reopen-discord - skipped (Discord was not closed)");
     record.steps.push(RunStep {
       id: "reopenDiscord".to_string(),
@@ -759,29 +1531,32 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
       verbose_detail: None,
     });
     StepResult::skipped("Discord was not closed; no restart needed")
+  } else if !options.reopen_discord_after_patch {
+    discord_clients::take_last_closed_state();
+    log::info!("[patch-flow] Step: reopen-discord - skipped (automatic restart disabled)");
+    record.steps.push(RunStep {
+      id: "reopenDiscord".to_string(),
+      title: "Reopen Discord".to_string(),
+      status: "skipped".to_string(),
+      friendly_message: "Automatic restart disabled; Discord was left closed".to_string(),
+      verbose_detail: None,
+    });
+    StepResult::skipped("Automatic restart disabled; Discord was left closed")
   } else {
     let restarted = run_blocking({
       let processes = discord_state.processes.clone();
       move || Ok(discord_clients::restart_processes(&processes))
     })
-    .await
-    .unwrap_or_default();
+    .await;
 
-    log::info!("[patch-flow] Step: reopen-discord - completed");
-    record.steps.push(RunStep {
-      id: "reopenDiscord".to_string(),
-      title: "Reopen Discord".to_string(),
-      status: "completed".to_string(),
-      friendly_message: "Discord restarted successfully".to_string(),
-      verbose_detail: None,
-    });
-    StepResult::completed(restarted)
+    reopen_step_result(record, restarted, "Discord restarted successfully".to_string())
   };
   emit_step_event(&app, PatchFlowStep::ReopenDiscord, &reopen_step);
+  progress.advance(&app);
 
   log::info!("[patch-flow] Install workflow completed successfully");
 
-  run_log::finalize(&mut record, "completed");
+  run_log::finalize(record, "completed");
   run_log::write_run(&record);
 
   Ok(PatchFlowResult {
@@ -792,18 +1567,609 @@ pub async fn run_patch_flow(app: tauri::AppHandle) -> Result<PatchFlowResult, St
     inject: inject_step,
     download_themes: themes_step,
     reopen_discord: reopen_step,
+    run_log_path: run_log_guard.map(|guard| guard.path().to_string_lossy().into_owned()),
+    reopen_confirmation_timed_out,
+  })
+}
+
+/// Lets a patch flow paused by `confirm_before_reopen` go on to reopen Discord.
+#[tauri::command]
+pub fn confirm_reopen(state: tauri::State<'_, ReopenConfirmation>) {
+  state.confirm();
+}
+
+#[tauri::command]
+pub async fn run_themes_flow(app: tauri::AppHandle) -> Result<ThemesFlowResult, InstallerError> {
+  log::info!("[themes-flow] Starting themes workflow");
+
+  let options = run_blocking(options::read_user_options)
+    .await
+    .map_err(InstallerError::filesystem)?;
+  let themes = options::resolve_themes(&options);
+
+  emit_step_event(
+    &app,
+    PatchFlowStep::DownloadThemes,
+    &StepResult::<()>::running("Downloading themes"),
+  );
+
+  let removed_themes = run_blocking({
+    let themes = themes.clone();
+    move || themes::prune_disabled_themes(&themes)
+  })
+  .await
+  .map_err(|err| {
+    log::error!("[themes-flow] Failed to prune disabled themes: {err}");
+    InstallerError::filesystem(friendly_step_error("downloadThemes", &err))
+  })?;
+
+  let download_themes = if themes.is_empty() {
+    log::info!("[themes-flow] No themes enabled; skipping download");
+    StepResult::skipped("No themes enabled; skipping download")
+  } else {
+    let results = run_blocking({
+      let app = app.clone();
+      move || themes::download_themes(&themes, &themes::theme_dirs()?, Some(&app))
+    })
+    .await
+    .map_err(|err| {
+      log::error!("[themes-flow] Failed to download themes: {err}");
+      InstallerError::network(friendly_step_error("downloadThemes", &err))
+    })?;
+
+    StepResult {
+      status: StepStatus::Completed,
+      message: Some(themes::summarize_theme_downloads(&results)),
+      detail: Some(results),
+    }
+  };
+  emit_step_event(&app, PatchFlowStep::DownloadThemes, &download_themes);
+
+  log::info!("[themes-flow] Themes workflow completed");
+
+  Ok(ThemesFlowResult {
+    download_themes,
+    removed_themes,
   })
 }
 
+/// Backs up the Vencord clone and managed themes without changing anything:
+/// the files are copied rather than moved, and nothing is synced, built or
+/// injected. Discord is still closed around the copy (per the options) so
+/// the files are not mid-write.
+#[tauri::command]
+pub async fn run_backup_only_flow(
+  app: tauri::AppHandle,
+) -> Result<BackupOnlyFlowResult, InstallerError> {
+  log::info!("[backup-flow] Starting backup-only workflow");
+
+  let options = run_blocking(options::read_user_options)
+    .await
+    .map_err(InstallerError::filesystem)?;
+  let mut progress = FlowProgress::new(3);
+
+  emit_step_event(
+    &app,
+    PatchFlowStep::CloseDiscord,
+    &StepResult::<()>::running("Closing Discord clients"),
+  );
+  let discord_state = run_blocking({
+    let close_enabled = options.close_discord_on_backup;
+    let scope = discord_clients::close_scope(&options);
+    move || Ok(discord_clients::close_discord_clients(close_enabled, &scope))
+  })
+  .await?;
+  let restart_enabled = !discord_state.closing_skipped && options.reopen_discord_after_patch;
+
+  if !discord_state.failed_to_close.is_empty() {
+    log::error!(
+      "[backup-flow] {} Discord process(es) did not exit; aborting",
+      discord_state.failed_to_close.len()
+    );
+    if restart_enabled {
+      let _ = run_blocking({
+        let processes = discord_state.processes.clone();
+        move || Ok(discord_clients::restart_processes(&processes))
+      })
+      .await;
+    }
+    return Err(friendly_step_error("closeDiscord", "").into());
+  }
+
+  let close_discord = if discord_state.closing_skipped {
+    StepResult::skipped("Closing Discord is disabled in settings")
+  } else {
+    StepResult::completed(discord_state.closed_clients.clone())
+  };
+  emit_step_event(&app, PatchFlowStep::CloseDiscord, &close_discord);
+  progress.advance(&app);
+
+  emit_step_event(
+    &app,
+    PatchFlowStep::Backup,
+    &StepResult::<()>::running("Backing up Vencord installation"),
+  );
+  let vencord_install = PathBuf::from(&options.vencord_repo_dir);
+  let outcome = run_blocking({
+    let vencord_install = vencord_install.clone();
+    let theme_sources = options::resolve_themes(&options);
+    let settings = backup::BackupSettings {
+      keep_source: true,
+      ..backup::BackupSettings::from_options(&options)
+    };
+    let max_count = options.max_backup_count;
+    let max_size = options.max_backup_size_mb;
+    move || {
+      let Some(path) = backup::move_vencord_install(&vencord_install, &theme_sources, &settings)?
+      else {
+        return Ok(None);
+      };
+      let pruned = backup::apply_backup_limits(max_count, max_size)?;
+      Ok(Some((path, pruned)))
+    }
+  })
+  .await;
+
+  let backup = match outcome {
+    Ok(Some((backup_path, pruned_backups))) => {
+      log::info!("[backup-flow] Backed up to {}", backup_path.display());
+      StepResult {
+        status: StepStatus::Completed,
+        message: Some(format!(
+          "Saved to {}; pruned {pruned_backups} old backup(s)",
+          backup_path.display()
+        )),
+        detail: Some(backup::BackupResult {
+          source_path: vencord_install.to_string_lossy().into_owned(),
+          backup_path: backup_path.to_string_lossy().into_owned(),
+          closed_clients: discord_state.closed_clients.clone(),
+          restarted_clients: Vec::new(),
+          closing_skipped: discord_state.closing_skipped,
+          pruned_backups,
+        }),
+      }
+    }
+    Ok(None) => {
+      log::info!("[backup-flow] No Vencord install at {}", vencord_install.display());
+      StepResult::skipped("No Vencord installation to back up")
+    }
+    Err(err) => {
+      log::error!("[backup-flow] Backup failed: {err}");
+      let message = friendly_step_error("backup", &err);
+      emit_step_event(&app, PatchFlowStep::Backup, &StepResult::<()>::failed(message.clone()));
+      if restart_enabled {
+        let _ = run_blocking({
+          let processes = discord_state.processes.clone();
+          move || Ok(discord_clients::restart_processes(&processes))
+        })
+        .await;
+      }
+      return Err(InstallerError::filesystem(message));
+    }
+  };
+  emit_step_event(&app, PatchFlowStep::Backup, &backup);
+  progress.advance(&app);
+
+  let reopen_discord = if restart_enabled {
+    emit_step_event(
+      &app,
+      PatchFlowStep::ReopenDiscord,
+      &StepResult::<()>::running("Restarting Discord clients"),
+    );
+    let restarted = run_blocking({
+      let processes = discord_state.processes.clone();
+      move || Ok(discord_clients::restart_processes(&processes))
+    })
+    .await?;
+    discord_clients::take_last_closed_state();
+    StepResult::completed(restarted)
+  } else if discord_state.closing_skipped {
+    StepResult::skipped("Discord was not closed; no restart needed")
+  } else {
+    discord_clients::take_last_closed_state();
+    StepResult::skipped("Automatic restart disabled; Discord was left closed")
+  };
+  emit_step_event(&app, PatchFlowStep::ReopenDiscord, &reopen_discord);
+  progress.advance(&app);
+
+  log::info!("[backup-flow] Backup-only workflow completed");
+
+  Ok(BackupOnlyFlowResult {
+    close_discord,
+    backup,
+    reopen_discord,
+  })
+}
+
+fn emit_reset_step(
+  app: &tauri::AppHandle,
+  step: FactoryResetStep,
+  status: StepStatus,
+  message: impl Into<String>,
+) {
+  let _ = app.emit(
+    "factory-reset-step",
+    FactoryResetStepPayload {
+      step,
+      status,
+      message: Some(message.into()),
+    },
+  );
+}
+
+/// What the factory reset changed between closing and reopening Discord.
+struct FactoryResetChanges {
+  unpatched: Vec<String>,
+  backup_path: Option<String>,
+  deleted_clone: Option<repo::RepoCloneDeletion>,
+  removed_themes: Vec<String>,
+}
+
+/// Removes Vencord entirely: restores the original `app.asar` of every
+/// detected Discord install, deletes the Vencord clone and the theme files
+/// the installer downloaded. With `keep_backup` the clone and themes are
+/// moved into a backup first instead of being deleted outright.
+#[tauri::command]
+pub async fn factory_reset_vencord(
+  app: tauri::AppHandle,
+  keep_backup: bool,
+) -> Result<FactoryResetResult, InstallerError> {
+  log::info!("[factory-reset] Starting factory reset (keep_backup={keep_backup})");
+
+  let options = run_blocking(options::read_user_options)
+    .await
+    .map_err(InstallerError::filesystem)?;
+
+  emit_reset_step(&app, FactoryResetStep::CloseDiscord, StepStatus::Running, "Closing Discord clients");
+  let discord_state = run_blocking(|| {
+    Ok(discord_clients::close_discord_clients(true, &discord_clients::CloseScope::All))
+  })
+  .await?;
+
+  let changes = if discord_state.failed_to_close.is_empty() {
+    emit_reset_step(
+      &app,
+      FactoryResetStep::CloseDiscord,
+      StepStatus::Completed,
+      format!("{} Discord client(s) closed", discord_state.closed_clients.len()),
+    );
+    reset_vencord_files(&app, &options, keep_backup).await
+  } else {
+    Err((
+      FactoryResetStep::CloseDiscord,
+      format!(
+        "{} Discord process(es) did not exit",
+        discord_state.failed_to_close.len()
+      ),
+    ))
+  };
+
+  let changes = match changes {
+    Ok(changes) => changes,
+    Err((step, err)) => {
+      log::error!("[factory-reset] Factory reset failed: {err}");
+      let message = match step {
+        FactoryResetStep::CloseDiscord => friendly_step_error("closeDiscord", &err),
+        _ => err,
+      };
+      emit_reset_step(&app, step, StepStatus::Failed, message.clone());
+
+      // Leave Discord as it was found rather than closed after a failure.
+      if options.reopen_discord_after_patch {
+        let _ = run_blocking({
+          let processes = discord_state.processes.clone();
+          move || Ok(discord_clients::restart_processes(&processes))
+        })
+        .await;
+      }
+      discord_clients::take_last_closed_state();
+
+      return Err(message.into());
+    }
+  };
+
+  let restarted = if options.reopen_discord_after_patch {
+    let restarted = run_blocking(|| {
+      let last_closed = discord_clients::take_last_closed_state();
+      Ok(discord_clients::restart_processes(&last_closed.processes))
+    })
+    .await?;
+    emit_reset_step(
+      &app,
+      FactoryResetStep::ReopenDiscord,
+      StepStatus::Completed,
+      format!("Restarted {} client(s)", discord_clients::restarted_names(&restarted).len()),
+    );
+    restarted
+  } else {
+    let _ = discord_clients::take_last_closed_state();
+    emit_reset_step(&app, FactoryResetStep::ReopenDiscord, StepStatus::Skipped, "Automatic restart disabled");
+    Vec::new()
+  };
+
+  log::info!("[factory-reset] Factory reset completed");
+
+  Ok(FactoryResetResult {
+    unpatched: changes.unpatched,
+    backup_path: changes.backup_path,
+    deleted_clone: changes.deleted_clone,
+    removed_themes: changes.removed_themes,
+    restarted,
+  })
+}
+
+/// The factory reset steps that run while Discord is closed. Errors carry
+/// the step that failed.
+async fn reset_vencord_files(
+  app: &tauri::AppHandle,
+  options: &options::UserOptions,
+  keep_backup: bool,
+) -> Result<FactoryResetChanges, (FactoryResetStep, String)> {
+  emit_reset_step(app, FactoryResetStep::Unpatch, StepStatus::Running, "Restoring Discord installs");
+  let unpatched = run_blocking(|| {
+    let mut restored = Vec::new();
+    let mut failures = Vec::new();
+
+    for install in discord::get_discord_installs() {
+      match repo::unpatch_location(&install.path) {
+        Ok(paths) if !paths.is_empty() => restored.push(install.name),
+        Ok(_) => {}
+        Err(err) => failures.push(format!("{}: {err}", install.name)),
+      }
+    }
+
+    if failures.is_empty() {
+      Ok(restored)
+    } else {
+      Err(format!("Failed to unpatch {}", failures.join("; ")))
+    }
+  })
+  .await
+  .map_err(|err| (FactoryResetStep::Unpatch, err))?;
+  emit_reset_step(
+    app,
+    FactoryResetStep::Unpatch,
+    StepStatus::Completed,
+    format!("Restored {} Discord install(s)", unpatched.len()),
+  );
+
+  let backup_path = if keep_backup {
+    emit_reset_step(app, FactoryResetStep::Backup, StepStatus::Running, "Backing up Vencord installation");
+    let path = run_blocking({
+      let source = PathBuf::from(&options.vencord_repo_dir);
+      let themes = options::resolve_themes(options);
+      let settings = backup::BackupSettings::from_options(options);
+      move || backup::move_vencord_install(&source, &themes, &settings)
+    })
+    .await
+    .map_err(|err| (FactoryResetStep::Backup, friendly_step_error("backup", &err)))?
+    .map(|path| path.to_string_lossy().into_owned());
+    emit_reset_step(
+      app,
+      FactoryResetStep::Backup,
+      StepStatus::Completed,
+      path.clone().unwrap_or_else(|| "Nothing to back up".to_string()),
+    );
+    path
+  } else {
+    emit_reset_step(app, FactoryResetStep::Backup, StepStatus::Skipped, "Backup not requested");
+    None
+  };
+
+  // The backup moves the clone away, so only delete what is still there.
+  let deleted_clone = if PathBuf::from(&options.vencord_repo_dir).exists() {
+    emit_reset_step(app, FactoryResetStep::DeleteClone, StepStatus::Running, "Deleting the Vencord clone");
+    let deletion = run_blocking(|| repo::delete_repo_clone().map_err(String::from))
+      .await
+      .map_err(|err| (FactoryResetStep::DeleteClone, err))?;
+    emit_reset_step(
+      app,
+      FactoryResetStep::DeleteClone,
+      StepStatus::Completed,
+      format!("Deleted {}", deletion.path),
+    );
+    Some(deletion)
+  } else {
+    emit_reset_step(app, FactoryResetStep::DeleteClone, StepStatus::Skipped, "No Vencord clone to delete");
+    None
+  };
+
+  emit_reset_step(app, FactoryResetStep::RemoveThemes, StepStatus::Running, "Removing downloaded themes");
+  // Pruning against an empty enabled list removes every manifest-tracked
+  // theme file and nothing else.
+  let removed_themes = run_blocking(|| themes::prune_disabled_themes(&[]))
+    .await
+    .map_err(|err| (FactoryResetStep::RemoveThemes, err))?;
+  emit_reset_step(
+    app,
+    FactoryResetStep::RemoveThemes,
+    StepStatus::Completed,
+    format!("Removed {} theme file(s)", removed_themes.len()),
+  );
+
+  Ok(FactoryResetChanges {
+    unpatched,
+    backup_path,
+    deleted_clone,
+    removed_themes,
+  })
+}
+
+/// Injects into every detected client regardless of `selected_discord_clients`,
+/// resolving locations and closing Discord around it like the patch flow does.
+#[tauri::command]
+pub async fn inject_all_clients() -> Result<Vec<repo::ClientInjectResult>, InstallerError> {
+  let options = run_blocking(options::read_user_options)
+    .await
+    .map_err(InstallerError::filesystem)?;
+  let installs = run_blocking(|| Ok(discord::get_discord_installs())).await?;
+
+  if installs.is_empty() {
+    return Err(InstallerError::not_found("No Discord installations found"));
+  }
+
+  run_blocking({
+    let repo_dir = options.vencord_repo_dir.clone();
+    move || repo::ensure_build_present(&repo_dir)
+  })
+  .await
+  .map_err(|message| InstallerError::Build { message, details: None })?;
+
+  let mut ids: Vec<String> = Vec::new();
+  for install in &installs {
+    if !ids.contains(&install.id) {
+      ids.push(install.id.clone());
+    }
+  }
+
+  let locations = run_blocking({
+    let repo_dir = options.vencord_repo_dir.clone();
+    move || resolve_inject_locations(&ids, &repo_dir)
+  })
+  .await
+  .map_err(InstallerError::not_found)?;
+
+  let discord_state = run_blocking({
+    let close_enabled = options.close_discord_on_backup;
+    move || Ok(discord_clients::close_discord_clients(close_enabled, &discord_clients::CloseScope::All))
+  })
+  .await?;
+  let reopen = !discord_state.closing_skipped && options.reopen_discord_after_patch;
+
+  if !discord_state.failed_to_close.is_empty() {
+    log::error!(
+      "[inject-all] {} Discord process(es) did not exit; aborting",
+      discord_state.failed_to_close.len()
+    );
+    if reopen {
+      let processes = discord_state.processes;
+      let _ = run_blocking(move || Ok(discord_clients::restart_processes(&processes))).await;
+    }
+    return Err(friendly_step_error("closeDiscord", "").into());
+  }
+
+  let results = run_blocking({
+    let repo_dir = options.vencord_repo_dir.clone();
+    let tools = repo::BuildTools::from_options(&options);
+    move || repo::inject_locations(&repo_dir, &locations, &tools)
+  })
+  .await;
+
+  let results = match results {
+    Ok(results) => results,
+    Err(err) => {
+      log::error!("[inject-all] Failed to inject: {err}");
+      if reopen {
+        let processes = discord_state.processes;
+        let _ = run_blocking(move || Ok(discord_clients::restart_processes(&processes))).await;
+      }
+      return Err(InstallerError::dependency(friendly_step_error("inject", &err)));
+    }
+  };
+
+  log::info!(
+    "[inject-all] Injected {} of {} client(s)",
+    results.iter().filter(|result| result.injected).count(),
+    results.len()
+  );
+
+  if reopen {
+    let processes = discord_state.processes;
+    let _ = run_blocking(move || Ok(discord_clients::restart_processes(&processes))).await;
+  }
+
+  Ok(results)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectReadiness {
+  pub id: String,
+  pub injectable: bool,
+  pub reason: Option<String>,
+}
+
+fn inject_blocker(
+  install: &discord::DiscordInstall,
+  running: &[discord_clients::DiscordProcess],
+) -> Option<String> {
+  let path = Path::new(&install.path);
+
+  if !path.is_dir() {
+    return Some(format!("Install folder {} no longer exists", install.path));
+  }
+
+  if let Err(err) = repo::check_resources_writable(path) {
+    return Some(err);
+  }
+
+  running
+    .iter()
+    .find(|proc| discord_clients::process_runs_from(proc, path))
+    .map(|proc| {
+      format!(
+        "{} (pid {}) is running from this install and closing Discord is disabled",
+        proc.name, proc.pid
+      )
+    })
+}
+
+/// Checks whether each selected client could be injected right now, so the
+/// UI can flag problems before the flow starts instead of at the Inject step.
+#[tauri::command]
+pub fn check_injectable() -> Result<Vec<InjectReadiness>, InstallerError> {
+  let options = options::read_user_options().map_err(InstallerError::filesystem)?;
+  let installs = discord::get_discord_installs();
+  // Clients the flow closes first cannot hold files open by the time it injects.
+  let running = if options.close_discord_on_backup {
+    Vec::new()
+  } else {
+    discord_clients::capture_discord_processes()
+  };
+
+  Ok(
+    options
+      .selected_discord_clients
+      .iter()
+      .map(|id| {
+        let reason = match installs.iter().find(|install| &install.id == id) {
+          Some(install) => inject_blocker(install, &running),
+          None => Some("No installation found for this client".to_string()),
+        };
+
+        InjectReadiness {
+          id: id.clone(),
+          injectable: reason.is_none(),
+          reason,
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Reports what injecting into one install would write, without running it.
+/// Uses static detection only, since the CLI detection runs `pnpm inject`.
+#[tauri::command]
+pub async fn preview_injection(id: String) -> Result<repo::InjectionPreview, InstallerError> {
+  let locations = run_blocking(move || discord_clients::resolve_selected_discord_locations(&[id]))
+    .await
+    .map_err(InstallerError::not_found)?;
+
+  Ok(repo::preview_injection(&locations))
+}
+
 #[tauri::command]
 pub fn run_dev_test(
   step: DevTestStep,
   source_path: Option<String>,
-) -> Result<DevTestResult, String> {
+) -> Result<DevTestResult, InstallerError> {
   match step {
     DevTestStep::CloseDiscord => {
-      let options = options::read_user_options()?;
-      let state = discord_clients::close_discord_clients(options.close_discord_on_backup);
+      let options = options::read_user_options().map_err(InstallerError::filesystem)?;
+      let state = discord_clients::close_discord_clients(
+        options.close_discord_on_backup,
+        &discord_clients::close_scope(&options),
+      );
 
       let mut closed_clients = state.closed_clients;
 
@@ -825,32 +2191,40 @@ pub fn run_dev_test(
         .filter(|value| !value.trim().is_empty())
         .ok_or_else(|| "Provide a source path before running the backup test".to_string())?;
 
-      let result = backup::backup_vencord_install(path)?;
+      let result = backup::backup_vencord_install(path, None)?;
 
       Ok(DevTestResult::Backup { result })
     }
     DevTestStep::SyncRepo => {
-      let options = options::read_user_options()?;
+      let options = options::read_user_options().map_err(InstallerError::filesystem)?;
       let plugins = options::resolve_plugin_repositories(&options);
       let path = repo::sync_vencord_repo(
         &options.vencord_repo_url,
         &options.vencord_repo_dir,
         &plugins,
+        options.userplugins_dir.as_deref(),
+        options.git_reference_repo.as_deref(),
+        options.max_plugin_cache_mb,
       )?;
 
       Ok(DevTestResult::SyncRepo { path })
     }
     DevTestStep::Build => {
-      let options = options::read_user_options()?;
-      let (message, _verbose) = repo::build_vencord_repo(&options.vencord_repo_dir)?;
+      let options = options::read_user_options().map_err(InstallerError::filesystem)?;
+      let (outcome, _verbose) = repo::build_vencord_repo(
+        &options.vencord_repo_dir,
+        &repo::BuildTools::from_options(&options),
+        options.offline_mode,
+        |_| {},
+      )?;
 
       Ok(DevTestResult::Build {
-        message,
+        message: outcome.build,
         path: Some(options.vencord_repo_dir),
       })
     }
     DevTestStep::Inject => {
-      let options = options::read_user_options()?;
+      let options = options::read_user_options().map_err(InstallerError::filesystem)?;
       let locations = resolve_inject_locations(&options.selected_discord_clients, &options.vencord_repo_dir)?;
 
       if locations.is_empty() {
@@ -859,24 +2233,31 @@ pub fn run_dev_test(
         });
       }
 
-      let message = repo::inject_vencord_repo(&options.vencord_repo_dir, &locations)
-        .map(|(msg, _)| msg)?;
+      repo::ensure_build_present(&options.vencord_repo_dir)
+        .map_err(|message| InstallerError::Build { message, details: None })?;
+
+      let message = repo::inject_vencord_repo(
+        &options.vencord_repo_dir,
+        &locations,
+        &repo::BuildTools::from_options(&options),
+      )
+      .map(|(msg, _)| msg)?;
 
       Ok(DevTestResult::Inject { message })
     }
     DevTestStep::DownloadThemes => {
-      let options = options::read_user_options()?;
+      let options = options::read_user_options().map_err(InstallerError::filesystem)?;
       let themes = options::resolve_themes(&options);
 
       if themes.is_empty() {
-        return Ok(DevTestResult::DownloadThemes {
-          message: "No themes enabled; skipping download".to_string(),
-        });
+        return Ok(DevTestResult::DownloadThemes { results: Vec::new() });
       }
 
-      let message = themes::download_themes(&themes)?;
+      let results = themes::theme_dirs()
+        .and_then(|dirs| themes::download_themes(&themes, &dirs, None))
+        .map_err(InstallerError::network)?;
 
-      Ok(DevTestResult::DownloadThemes { message })
+      Ok(DevTestResult::DownloadThemes { results })
     }
     DevTestStep::ReopenDiscord => {
       let last_closed = discord_clients::take_last_closed_state();
@@ -904,3 +2285,46 @@ pub fn run_dev_test(
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn install(id: &str, path: &str) -> discord::DiscordInstall {
+    discord::DiscordInstall {
+      id: id.to_string(),
+      name: id.to_string(),
+      path: path.to_string(),
+      last_updated: None,
+    }
+  }
+
+  #[test]
+  fn ids_the_cli_does_not_report_use_static_detection() {
+    let wine_path = "/home/user/.wine/drive_c/users/user/AppData/Local/Discord";
+    let cli_detected = vec![("stable".to_string(), "/opt/discord".to_string())];
+    let installs = vec![
+      install("stable", "/usr/share/discord"),
+      install("stable-wine-default", wine_path),
+    ];
+
+    let locations = merge_inject_locations(
+      &["stable".to_string(), "stable-wine-default".to_string()],
+      &cli_detected,
+      &[],
+      &installs,
+    )
+    .unwrap();
+
+    assert_eq!(locations, vec!["/opt/discord".to_string(), wine_path.to_string()]);
+  }
+
+  #[test]
+  fn ids_found_nowhere_are_reported_missing() {
+    let cli_detected = vec![("stable".to_string(), "/opt/discord".to_string())];
+
+    let err = merge_inject_locations(&["canary".to_string()], &cli_detected, &[], &[]).unwrap_err();
+
+    assert!(err.contains("canary"));
+  }
+}
@@ -0,0 +1,117 @@
+use sha2::{Digest, Sha256};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  time::SystemTime,
+};
+
+use crate::config::app_config_dir;
+
+use super::{
+  backup,
+  repo::{self, RepoError},
+};
+
+const PLUGIN_CACHE_DIR: &str = "plugin-cache";
+// Inside `.git` so it never shows up as a change in the plugin checkout.
+const LAST_USED_MARKER: &str = ".git/installer-last-used";
+
+fn cache_root() -> Result<PathBuf, String> {
+  let root = app_config_dir()
+    .map_err(|err| format!("Failed to get config directory: {err}"))?
+    .join(PLUGIN_CACHE_DIR);
+
+  fs::create_dir_all(&root)
+    .map_err(|err| format!("Failed to create plugin cache {}: {err}", root.display()))?;
+
+  Ok(root)
+}
+
+/// Folder name for a cached clone: readable, plus a hash of the full URL so
+/// forks with the same repository name do not collide.
+fn cache_key(url: &str) -> String {
+  let name: String = repo::repo_folder_name_from_url(url)
+    .chars()
+    .map(|ch| {
+      if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+        ch
+      } else {
+        '-'
+      }
+    })
+    .collect();
+  let hash: String = Sha256::digest(url.as_bytes())[..4]
+    .iter()
+    .map(|byte| format!("{byte:02x}"))
+    .collect();
+
+  format!("{}-{hash}", name.trim_matches('.'))
+}
+
+/// Brings the cached clone of `url` up to date, cloning it on first use, and
+/// copies it to `destination`. A cached clone that can no longer be
+/// fast-forwarded is dropped and cloned again.
+pub fn install_from_cache(url: &str, destination: &Path) -> Result<(), RepoError> {
+  let entry = cache_root()?.join(cache_key(url));
+
+  let source = match repo::sync_external_plugin_repo(url, &entry) {
+    Ok(source) => source,
+    Err(err) if entry.exists() => {
+      log::warn!("[plugin-cache] Failed to update cached {url} ({err}); cloning it again");
+      backup::remove_dir_tree(&entry)?;
+      repo::sync_external_plugin_repo(url, &entry)?
+    }
+    Err(err) => return Err(err),
+  };
+
+  if let Err(err) = fs::write(source.join(LAST_USED_MARKER), b"") {
+    log::warn!("[plugin-cache] Failed to mark {} as used: {err}", source.display());
+  }
+
+  backup::copy_dir_filtered(&source, destination, Path::new(""), &[])?;
+  Ok(())
+}
+
+/// Removes the least recently used clones until the cache fits in `max_mb`.
+/// Returns how many it removed.
+pub fn prune_plugin_cache(max_mb: u64) -> Result<usize, String> {
+  let root = cache_root()?;
+  let mut entries = Vec::new();
+
+  for entry in fs::read_dir(&root)
+    .map_err(|err| format!("Failed to read plugin cache {}: {err}", root.display()))?
+  {
+    let path = entry
+      .map_err(|err| format!("Failed to read plugin cache entry: {err}"))?
+      .path();
+
+    if !path.is_dir() {
+      continue;
+    }
+
+    let last_used = fs::metadata(path.join(LAST_USED_MARKER))
+      .and_then(|meta| meta.modified())
+      .unwrap_or(SystemTime::UNIX_EPOCH);
+    let size = backup::dir_size(&path)?;
+
+    entries.push((last_used, size, path));
+  }
+
+  entries.sort_by_key(|(last_used, _, _)| *last_used);
+
+  let max_bytes = max_mb.saturating_mul(1024 * 1024);
+  let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+  let mut pruned = 0;
+
+  for (_, size, path) in entries {
+    if total <= max_bytes {
+      break;
+    }
+
+    backup::remove_dir_tree(&path)?;
+    total = total.saturating_sub(size);
+    pruned += 1;
+  }
+
+  Ok(pruned)
+}
@@ -1,20 +1,156 @@
 use std::{
-  env, fs,
+  env,
+  ffi::OsString,
+  fmt, fs,
   path::{Path, PathBuf},
+  process::Command,
 };
 
-use crate::command_utils::{build_command, command_candidates};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+  command_utils::{build_command, command_candidates, CommandError},
+  config::is_writable_dir,
+  error::InstallerError,
+  network, options,
+};
+
+use super::{backup, plugin_cache};
+
+/// Failure while syncing or building the Vencord clone. Failed git/pnpm
+/// invocations keep their structured details; anything else is a message.
+///
+/// Kept apart from `InstallerError` because the patch flow needs the raw
+/// `CommandError` for its run record and rewrites the message per step
+/// before categorizing it; commands convert with `From<RepoError>`.
+#[derive(Debug)]
+pub enum RepoError {
+  Command(Box<CommandError>),
+  Other(String),
+}
+
+impl RepoError {
+  pub fn command(&self) -> Option<&CommandError> {
+    match self {
+      Self::Command(err) => Some(err),
+      Self::Other(_) => None,
+    }
+  }
+}
+
+impl fmt::Display for RepoError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Command(err) => err.fmt(f),
+      Self::Other(message) => f.write_str(message),
+    }
+  }
+}
+
+impl From<CommandError> for RepoError {
+  fn from(err: CommandError) -> Self {
+    Self::Command(Box::new(err))
+  }
+}
+
+impl From<String> for RepoError {
+  fn from(message: String) -> Self {
+    Self::Other(message)
+  }
+}
+
+impl From<RepoError> for String {
+  fn from(err: RepoError) -> Self {
+    err.to_string()
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoCloneDeletion {
+  pub path: String,
+  pub freed_bytes: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoStatus {
+  pub exists: bool,
+  pub is_git: bool,
+  pub has_build: bool,
+  /// Checked-out branch, or the short commit hash when HEAD is detached.
+  pub current_ref: Option<String>,
+  pub remote_url: Option<String>,
+}
+
+/// Absolute Node/pnpm binaries configured by the user, used instead of PATH
+/// lookup (e.g. for nvm/volta installs the GUI cannot see).
+#[derive(Clone, Debug, Default)]
+pub struct BuildTools {
+  pub node: Option<PathBuf>,
+  pub pnpm: Option<PathBuf>,
+}
+
+impl BuildTools {
+  pub fn from_options(options: &options::UserOptions) -> Self {
+    Self {
+      node: options.node_path.as_ref().map(PathBuf::from),
+      pnpm: options.pnpm_path.as_ref().map(PathBuf::from),
+    }
+  }
+
+  fn candidates(&self, command: &str) -> Vec<String> {
+    let configured = match command {
+      "node" => self.node.as_ref(),
+      "pnpm" => self.pnpm.as_ref(),
+      _ => None,
+    };
+
+    match configured {
+      Some(path) => vec![path.to_string_lossy().into_owned()],
+      None => command_candidates(command).to_vec(),
+    }
+  }
+
+  // pnpm and npm are node scripts, so the configured binaries' folders must
+  // also come first on PATH for their shebangs/shims to find node.
+  fn apply_path(&self, cmd: &mut Command) {
+    let tool_dirs: Vec<PathBuf> = [&self.node, &self.pnpm]
+      .into_iter()
+      .flatten()
+      .filter_map(|path| path.parent().map(Path::to_path_buf))
+      .collect();
+
+    if tool_dirs.is_empty() {
+      return;
+    }
+
+    let current = cmd
+      .get_envs()
+      .find(|(key, _)| *key == "PATH")
+      .and_then(|(_, value)| value.map(OsString::from))
+      .or_else(|| env::var_os("PATH"))
+      .unwrap_or_default();
+
+    if let Ok(joined) = env::join_paths(tool_dirs.into_iter().chain(env::split_paths(&current))) {
+      cmd.env("PATH", joined);
+    }
+  }
+}
 
 fn run_command(
+  tools: &BuildTools,
   command: &str,
   args: &[&str],
   working_dir: Option<&str>,
   error_prefix: &str,
-) -> Result<(String, String), String> {
+) -> Result<(String, String), CommandError> {
   let mut last_error: Option<String> = None;
 
-  for candidate in command_candidates(command) {
+  for candidate in tools.candidates(command) {
     let mut cmd = build_command(&candidate);
+    tools.apply_path(&mut cmd);
 
     if let Some(dir) = working_dir {
       cmd.current_dir(dir);
@@ -29,13 +165,15 @@ fn run_command(
           ));
         }
 
-        return Err(format!(
+        let message = format!(
           "{error_prefix}: exit status {} when running {}. Stdout: {}\nStderr: {}",
           output.status,
           candidate,
           String::from_utf8_lossy(&output.stdout),
           String::from_utf8_lossy(&output.stderr)
-        ));
+        );
+
+        return Err(CommandError::from_output(&candidate, args, &output, message));
       }
       Err(err) => last_error = Some(format!("{candidate}: {err}")),
     }
@@ -44,8 +182,12 @@ fn run_command(
   let path = env::var("PATH").unwrap_or_else(|_| "<not set>".to_string());
   let errors = last_error.unwrap_or_else(|| "unknown error".to_string());
 
-  Err(format!(
-    "{error_prefix}: failed to run {command}. Tried: {errors}. Ensure it is installed and available in PATH (current PATH: {path})."
+  Err(CommandError::not_started(
+    command,
+    args,
+    format!(
+      "{error_prefix}: failed to run {command}. Tried: {errors}. Ensure it is installed and available in PATH (current PATH: {path})."
+    ),
   ))
 }
 
@@ -69,44 +211,28 @@ fn output_indicates_inject_failure(stdout: &str, stderr: &str) -> bool {
   .any(|needle| haystack.contains(needle))
 }
 
-fn ensure_inject_location_writable(location: &str) -> Result<(), String> {
-  #[cfg(target_os = "linux")]
-  {
-    let resources_dir = Path::new(location).join("resources");
+/// Checks the folders injection writes to exist and are writable, on every
+/// platform rather than only where a failure is likely. Used both before
+/// injecting and by the readiness check.
+pub fn check_resources_writable(location: &Path) -> Result<(), String> {
+  let dirs = resources_dirs(location);
 
-    if !resources_dir.exists() {
-      return Ok(());
-    }
-
-    let probe = resources_dir.join(".vencord_installer_write_test");
-
-    match fs::OpenOptions::new()
-      .create(true)
-      .truncate(true)
-      .write(true)
-      .open(&probe)
-    {
-      Ok(_) => {
-        let _ = fs::remove_file(&probe);
-        Ok(())
-      }
-      Err(err) => Err(format!(
-        "Cannot write to {} ({}). Vencord injection needs write access to Discord's resources directory. Try running Discord from a user-writable install location or adjust ownership/permissions (for example with chown/chmod) before retrying",
-        resources_dir.display(),
-        err
-      )),
-    }
+  if dirs.is_empty() {
+    return Err(format!("No resources folder found in {}", location.display()));
   }
 
-  #[cfg(not(target_os = "linux"))]
-  {
-    let _ = location;
-    Ok(())
+  match dirs.iter().find(|dir| !is_writable_dir(dir)) {
+    Some(dir) => Err(format!(
+      "Cannot write to {}. Vencord injection needs write access to Discord's resources directory. Try running Discord from a user-writable install location or adjust the folder's ownership/permissions before retrying",
+      dir.display()
+    )),
+    None => Ok(()),
   }
 }
 
-fn check_tool(command: &str, args: &[&str], name: &str) -> Result<(), String> {
+fn check_tool(tools: &BuildTools, command: &str, args: &[&str], name: &str) -> Result<(), CommandError> {
   run_command(
+    tools,
     command,
     args,
     None,
@@ -115,6 +241,32 @@ fn check_tool(command: &str, args: &[&str], name: &str) -> Result<(), String> {
   .map(|_| ())
 }
 
+/// `<command> --version` output, resolved the same way the build resolves it.
+pub fn tool_version(tools: &BuildTools, command: &str, name: &str) -> Result<String, String> {
+  run_command(
+    tools,
+    command,
+    &["--version"],
+    None,
+    &format!("{name} is not installed or not in PATH"),
+  )
+  .map(|(stdout, _)| stdout)
+  .map_err(String::from)
+}
+
+pub fn ensure_git_available() -> Result<String, String> {
+  let output = build_command("git")
+    .arg("--version")
+    .output()
+    .map_err(|err| format!("Git is not installed or not in PATH: {err}"))?;
+
+  if !output.status.success() {
+    return Err(format!("git --version failed with status {}", output.status));
+  }
+
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 fn vencord_repo_path(dir: &str) -> PathBuf {
   PathBuf::from(dir)
 }
@@ -123,7 +275,66 @@ fn vencord_user_plugins_path(repo_dir: &Path) -> PathBuf {
   repo_dir.join("src").join("userplugins")
 }
 
-fn repo_folder_name_from_url(url: &str) -> String {
+const BUILD_ARTIFACTS: &[&str] = &["patcher.js", "preload.js", "renderer.js"];
+
+pub fn build_artifact_paths(repo_dir: &Path) -> Vec<PathBuf> {
+  let dist = repo_dir.join("dist");
+
+  BUILD_ARTIFACTS
+    .iter()
+    .map(|artifact| dist.join(artifact))
+    .collect()
+}
+
+/// Short SHA-256 digest over the build artifacts, sorted by name, so a user
+/// can check which build was injected. `None` when any artifact is missing.
+pub fn build_fingerprint(repo_dir: &Path) -> Option<String> {
+  let mut paths = build_artifact_paths(repo_dir);
+  paths.sort();
+
+  let mut hasher = Sha256::new();
+
+  for path in &paths {
+    let content = fs::read(path).ok()?;
+    // Length-prefixed so moving bytes between files changes the digest.
+    hasher.update(path.file_name()?.as_encoded_bytes());
+    hasher.update((content.len() as u64).to_le_bytes());
+    hasher.update(&content);
+  }
+
+  Some(
+    hasher.finalize()[..8]
+      .iter()
+      .map(|byte| format!("{byte:02x}"))
+      .collect(),
+  )
+}
+
+pub fn missing_build_artifacts(repo_dir: &Path) -> Vec<PathBuf> {
+  build_artifact_paths(repo_dir)
+    .into_iter()
+    .filter(|path| !path.is_file())
+    .collect()
+}
+
+pub fn ensure_build_present(repo_dir: &str) -> Result<(), String> {
+  let missing = missing_build_artifacts(Path::new(repo_dir));
+
+  if missing.is_empty() {
+    return Ok(());
+  }
+
+  Err(format!(
+    "No Vencord build found in {repo_dir} (missing {}). Run the Build step first",
+    missing
+      .iter()
+      .map(|path| path.display().to_string())
+      .collect::<Vec<_>>()
+      .join(", ")
+  ))
+}
+
+pub fn repo_folder_name_from_url(url: &str) -> String {
   let last = url
     .trim_end_matches('/')
     .rsplit('/')
@@ -148,7 +359,28 @@ fn clean_node_modules(repo_dir: &Path) -> Result<(), String> {
   Ok(())
 }
 
-fn sync_user_plugin_repos(plugin_urls: &[String], repo_dir: &Path) -> Result<(), String> {
+fn clone_plugin_repo(url: &str, destination: &Path) -> Result<(), RepoError> {
+  let destination_str = destination
+    .to_str()
+    .ok_or_else(|| "Invalid user plugin destination path".to_string())?;
+
+  let _permit = network::acquire();
+  run_git(&["clone", url, destination_str]).map_err(|err| {
+    err
+      .with_context(format!(
+        "Failed to clone user plugin {url} into {}",
+        destination.display()
+      ))
+      .into()
+  })
+}
+
+fn sync_user_plugin_repos(
+  plugin_urls: &[String],
+  repo_dir: &Path,
+  userplugins_dir: Option<&str>,
+  plugin_cache_mb: Option<u64>,
+) -> Result<(), RepoError> {
   if plugin_urls.is_empty() {
     return Ok(());
   }
@@ -156,53 +388,201 @@ fn sync_user_plugin_repos(plugin_urls: &[String], repo_dir: &Path) -> Result<(),
   let plugins_dir = vencord_user_plugins_path(repo_dir);
 
   if plugins_dir.exists() {
-    fs::remove_dir_all(&plugins_dir)
+    backup::remove_dir_tree(&plugins_dir)
       .map_err(|err| format!("Failed to reset userplugins directory: {err}"))?;
   }
 
   fs::create_dir_all(&plugins_dir)
     .map_err(|err| format!("Failed to create userplugins directory: {err}"))?;
 
-  for url in plugin_urls {
-    let folder_name = repo_folder_name_from_url(url);
-    let destination = plugins_dir.join(folder_name);
-    let destination_str = destination
-      .to_str()
-      .ok_or_else(|| "Invalid user plugin destination path".to_string())?;
+  let external_dir = userplugins_dir
+    .map(str::trim)
+    .filter(|dir| !dir.is_empty())
+    .map(PathBuf::from);
+
+  // Each plugin goes to its own folder, so they are synced in parallel and
+  // `max_concurrent_network` limits how many transfer at once.
+  std::thread::scope(|scope| {
+    let handles: Vec<_> = plugin_urls
+      .iter()
+      .map(|url| {
+        let plugins_dir = &plugins_dir;
+        let external_dir = external_dir.as_deref();
+        scope.spawn(move || sync_user_plugin(url, plugins_dir, external_dir, plugin_cache_mb))
+      })
+      .collect();
+
+    handles
+      .into_iter()
+      .map(|handle| {
+        handle
+          .join()
+          .unwrap_or_else(|_| Err("User plugin sync panicked".to_string().into()))
+      })
+      .collect::<Result<Vec<()>, RepoError>>()
+  })?;
+
+  if let Some(max_mb) = plugin_cache_mb.filter(|_| external_dir.is_none()) {
+    match plugin_cache::prune_plugin_cache(max_mb) {
+      Ok(0) => {}
+      Ok(pruned) => log::info!("[repo] Pruned {pruned} cached plugin clone(s)"),
+      Err(err) => log::warn!("[repo] Failed to prune the plugin cache: {err}"),
+    }
+  }
 
-    run_git(&["clone", url, destination_str]).map_err(|err| {
-      format!(
-        "Failed to clone user plugin {url} into {}: {err}",
+  Ok(())
+}
+
+fn sync_user_plugin(
+  url: &str,
+  plugins_dir: &Path,
+  external_dir: Option<&Path>,
+  plugin_cache_mb: Option<u64>,
+) -> Result<(), RepoError> {
+  let folder_name = repo_folder_name_from_url(url);
+  let destination = plugins_dir.join(&folder_name);
+
+  match (external_dir, plugin_cache_mb) {
+    (Some(external_dir), _) => {
+      let source = sync_external_plugin_repo(url, &external_dir.join(&folder_name))?;
+      link_user_plugin(&source, &destination)?;
+    }
+    (None, Some(_)) => plugin_cache::install_from_cache(url, &destination)?,
+    (None, None) => clone_plugin_repo(url, &destination)?,
+  }
+
+  Ok(())
+}
+
+/// Clones or fast-forwards a plugin repo kept outside the Vencord clone, so it
+/// persists (including local edits) when the clone is deleted or re-cloned.
+pub fn sync_external_plugin_repo(url: &str, destination: &Path) -> Result<PathBuf, RepoError> {
+  let destination_str = destination
+    .to_str()
+    .ok_or_else(|| "Invalid user plugin destination path".to_string())?;
+
+  if destination.join(".git").exists() {
+    let _permit = network::acquire();
+    run_git(&["-C", destination_str, "pull", "--ff-only"]).map_err(|err| {
+      err.with_context(format!(
+        "Failed to update user plugin {url} in {}",
         destination.display()
-      )
+      ))
     })?;
+  } else {
+    if let Some(parent) = destination.parent() {
+      fs::create_dir_all(parent).map_err(|err| {
+        format!(
+          "Failed to create userplugins directory {}: {err}",
+          parent.display()
+        )
+      })?;
+    }
+
+    clone_plugin_repo(url, destination)?;
   }
 
-  Ok(())
+  // Symlink targets must be absolute to resolve from inside src/userplugins.
+  Ok(dunce::canonicalize(destination).unwrap_or_else(|_| destination.to_path_buf()))
+}
+
+/// Exposes an external plugin checkout inside `src/userplugins`. Symlinks keep
+/// edits in sync; when they are unavailable (e.g. Windows without developer
+/// mode) the plugin is copied instead, so changes need another sync to apply.
+fn link_user_plugin(source: &Path, destination: &Path) -> Result<(), String> {
+  #[cfg(unix)]
+  let linked = std::os::unix::fs::symlink(source, destination);
+  #[cfg(windows)]
+  let linked = std::os::windows::fs::symlink_dir(source, destination);
+
+  match linked {
+    Ok(()) => Ok(()),
+    Err(err) => {
+      log::info!(
+        "[repo] Could not symlink {} ({err}); copying it instead",
+        source.display()
+      );
+      backup::copy_dir_filtered(source, destination, Path::new(""), &[".git".to_string()])
+    }
+  }
+}
+
+/// Common git failures, matched case-insensitively against stderr, paired with
+/// guidance the user can act on.
+const GIT_FAILURE_GUIDANCE: &[(&[&str], &str)] = &[
+  // Ahead of the connection patterns, since git reports these as "unable to access".
+  (
+    &["rate limit", "returned error: 429"],
+    "The repository host is rate limiting requests. Wait a while before patching again rather than retrying right away, or configure git credentials so clones are authenticated.",
+  ),
+  (
+    &["not possible to fast-forward", "have diverged", "divergent branches"],
+    "Your Vencord clone has diverged from the upstream repository, so it cannot be updated in place. Delete the Vencord clone folder so it is re-cloned on the next patch, or reset your local changes manually.",
+  ),
+  (
+    &["would be overwritten by merge", "commit your changes or stash them"],
+    "Your Vencord clone has local changes that block the update. Discard or stash them, or delete the Vencord clone folder so it is re-cloned on the next patch.",
+  ),
+  (
+    &["could not resolve host", "unable to access", "connection timed out", "connection refused"],
+    "Could not reach the repository. Check your internet connection and try again.",
+  ),
+  (
+    &["repository not found", "does not appear to be a git repository"],
+    "The repository could not be found. Check the repository URL in Settings.",
+  ),
+  (
+    &["authentication failed", "terminal prompts disabled", "permission denied (publickey)"],
+    "The repository requires authentication. Use a public HTTPS URL or configure git credentials.",
+  ),
+  (
+    &["detected dubious ownership"],
+    "Git refused to use the clone because it is owned by another user. Delete the Vencord clone folder or mark it as a safe.directory in git.",
+  ),
+  (
+    &["index.lock"],
+    "Another git process is using the Vencord clone. Close it, or delete the stale .git/index.lock file, and try again.",
+  ),
+];
+
+/// Maps raw git output to user-facing guidance for known failure patterns.
+pub fn git_failure_guidance(output: &str) -> Option<&'static str> {
+  let lower = output.to_lowercase();
+
+  GIT_FAILURE_GUIDANCE
+    .iter()
+    .find(|(patterns, _)| patterns.iter().any(|pattern| lower.contains(pattern)))
+    .map(|(_, guidance)| *guidance)
 }
 
-fn run_git(args: &[&str]) -> Result<(), String> {
+fn run_git(args: &[&str]) -> Result<(), CommandError> {
   let output = build_command("git")
     .args(args)
     .output()
-    .map_err(|err| format!("Failed to run git: {err}"))?;
+    .map_err(|err| CommandError::not_started("git", args, format!("Failed to run git: {err}")))?;
 
   if !output.status.success() {
-    return Err(format!(
-      "Git command failed with status {}: {}",
-      output.status,
-      String::from_utf8_lossy(&output.stderr)
-    ));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if let Some(guidance) = git_failure_guidance(&stderr) {
+      log::warn!("[repo] git {} failed with status {}: {}", args.join(" "), output.status, stderr.trim());
+      let message = format!("{guidance}\n\nGit output: {}", stderr.trim());
+      return Err(CommandError::from_output("git", args, &output, message));
+    }
+
+    let message = format!("Git command failed with status {}: {}", output.status, stderr);
+    return Err(CommandError::from_output("git", args, &output, message));
   }
 
   Ok(())
 }
 
-fn is_git_repo(repo_path_str: &str) -> Result<bool, String> {
+pub fn is_git_repo(repo_path_str: &str) -> Result<bool, CommandError> {
+  let args = ["-C", repo_path_str, "rev-parse", "--is-inside-work-tree"];
   let output = build_command("git")
-    .args(["-C", repo_path_str, "rev-parse", "--is-inside-work-tree"])
+    .args(args)
     .output()
-    .map_err(|err| format!("Failed to run git: {err}"))?;
+    .map_err(|err| CommandError::not_started("git", &args, format!("Failed to run git: {err}")))?;
 
   if output.status.success() {
     return Ok(true);
@@ -214,17 +594,79 @@ fn is_git_repo(repo_path_str: &str) -> Result<bool, String> {
     return Ok(false);
   }
 
-  Err(format!(
-    "Git command failed with status {}: {}",
-    output.status, stderr
-  ))
+  let message = format!("Git command failed with status {}: {}", output.status, stderr);
+  Err(CommandError::from_output("git", &args, &output, message))
+}
+
+// Path components used by common sync clients, including macOS's
+// `~/Library/CloudStorage` and iCloud's `Mobile Documents` folders.
+const CLOUD_SYNC_MARKERS: &[&str] = &[
+  "onedrive",
+  "dropbox",
+  "icloud drive",
+  "iclouddrive",
+  "mobile documents",
+  "cloudstorage",
+  "google drive",
+  "googledrive",
+  "box sync",
+  "pcloud",
+];
+
+/// Warns when the Vencord clone lives in a cloud-synced folder, where the
+/// sync client locking files mid-build tends to corrupt node_modules.
+pub fn cloud_sync_warning(repo_dir: &str) -> Option<String> {
+  let path = Path::new(repo_dir);
+
+  let env_match = ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"]
+    .iter()
+    .filter_map(|key| env::var_os(key))
+    .any(|root| path.starts_with(root));
+
+  let marker_match = path.components().any(|component| {
+    let name = component.as_os_str().to_string_lossy().to_lowercase();
+    CLOUD_SYNC_MARKERS.iter().any(|marker| name.contains(marker))
+  });
+
+  (env_match || marker_match).then(|| {
+    format!(
+      "{repo_dir} appears to be inside a cloud-synced folder. Syncing can lock files during builds and corrupt node_modules; consider moving the Vencord folder to a location that is not synced."
+    )
+  })
+}
+
+// The reference is re-checked here since it may have moved since it was
+// saved; an unusable one just means a normal clone.
+fn clone_vencord_repo(
+  repo_url: &str,
+  repo_path_str: &str,
+  reference: Option<&str>,
+) -> Result<(), RepoError> {
+  let _permit = network::acquire();
+
+  match reference {
+    Some(reference) if options::is_valid_reference_repo(reference) => {
+      log::info!("[repo] Cloning with reference repository {reference}");
+      run_git(&["clone", "--reference", reference, "--dissociate", repo_url, repo_path_str])?;
+    }
+    Some(reference) => {
+      log::warn!("[repo] Reference repository {reference} is not a git repository; cloning normally");
+      run_git(&["clone", repo_url, repo_path_str])?;
+    }
+    None => run_git(&["clone", repo_url, repo_path_str])?,
+  }
+
+  Ok(())
 }
 
 pub fn sync_vencord_repo(
   repo_url: &str,
   repo_dir: &str,
   plugin_urls: &[String],
-) -> Result<String, String> {
+  userplugins_dir: Option<&str>,
+  reference_repo: Option<&str>,
+  plugin_cache_mb: Option<u64>,
+) -> Result<String, RepoError> {
   let repo_path = vencord_repo_path(repo_dir);
   let repo_path_str = repo_path
     .to_str()
@@ -232,24 +674,38 @@ pub fn sync_vencord_repo(
 
   if repo_path.exists() {
     if is_git_repo(repo_path_str)? {
-      run_git(&["-C", repo_path_str, "pull", "--ff-only"])?;
+      let _permit = network::acquire();
+      // Resolved once per sync; `None` leaves the choice to git's upstream config.
+      match default_branch(repo_path_str) {
+        Some(branch) => {
+          log::info!("[repo] Pulling default branch {branch} from origin");
+          run_git(&["-C", repo_path_str, "pull", "--ff-only", "origin", &branch])?;
+        }
+        None => run_git(&["-C", repo_path_str, "pull", "--ff-only"])?,
+      }
     } else if repo_path.is_dir() {
       let mut entries = fs::read_dir(&repo_path)
         .map_err(|err| format!("Failed to read directory {}: {err}", repo_path.display()))?;
 
       if entries.next().is_some() {
-        return Err(format!(
-          "Existing path {} is not a git repository. Remove it or choose an empty directory before syncing",
-          repo_path.display()
-        ));
+        return Err(
+          format!(
+            "Existing path {} is not a git repository. Remove it or choose an empty directory before syncing",
+            repo_path.display()
+          )
+          .into(),
+        );
       }
 
-      run_git(&["clone", repo_url, repo_path_str])?;
+      clone_vencord_repo(repo_url, repo_path_str, reference_repo)?;
     } else {
-      return Err(format!(
-        "Existing path {} is not a directory. Choose a directory for the Vencord clone",
-        repo_path.display()
-      ));
+      return Err(
+        format!(
+          "Existing path {} is not a directory. Choose a directory for the Vencord clone",
+          repo_path.display()
+        )
+        .into(),
+      );
     }
   } else {
     if let Some(parent) = repo_path.parent() {
@@ -261,90 +717,152 @@ pub fn sync_vencord_repo(
       })?;
     }
 
-    run_git(&["clone", repo_url, repo_path_str])?;
+    clone_vencord_repo(repo_url, repo_path_str, reference_repo)?;
   }
 
-  sync_user_plugin_repos(plugin_urls, &repo_path)?;
+  sync_user_plugin_repos(plugin_urls, &repo_path, userplugins_dir, plugin_cache_mb)?;
 
   Ok(repo_path_str.to_string())
 }
 
-pub fn build_vencord_repo(repo_dir: &str) -> Result<(String, String), String> {
-  check_tool("node", &["--version"], "Node.js")?;
-  check_tool("npm", &["--version"], "npm")?;
+/// The two halves of the build step, reported separately since a failed
+/// dependency install and a failed build need very different fixes.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildPhase {
+  Install,
+  Build,
+}
+
+impl BuildPhase {
+  pub fn running_message(self) -> &'static str {
+    match self {
+      Self::Install => "Installing dependencies (pnpm install)",
+      Self::Build => "Building Vencord (pnpm build)",
+    }
+  }
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildOutcome {
+  pub install: String,
+  pub build: String,
+}
+
+/// `offline` keeps the existing `node_modules` and has pnpm install only from
+/// its local store, so the build makes no network requests.
+fn install_vencord_dependencies(
+  repo_dir: &str,
+  tools: &BuildTools,
+  offline: bool,
+) -> Result<(String, String), RepoError> {
+  check_tool(tools, "node", &["--version"], "Node.js")?;
+  check_tool(tools, "npm", &["--version"], "npm")?;
 
   let repo_path = Path::new(repo_dir);
 
-  clean_node_modules(repo_path)?;
+  if !offline {
+    clean_node_modules(repo_path)?;
+  }
 
-  check_tool("pnpm", &["--version"], "pnpm")
+  check_tool(tools, "pnpm", &["--version"], "pnpm")
     .map_err(|_| "pnpm is not installed. Please install it via the Dependencies panel before building.".to_string())?;
 
-  let (install_stdout, install_stderr) = run_command(
+  let (stdout, stderr) = run_command(
+    tools,
     "pnpm",
-    &["install"],
+    if offline { &["install", "--offline"] } else { &["install"] },
     Some(repo_dir),
-    "Failed to install project dependencies with pnpm",
+    "Dependency install failed (pnpm install)",
   )?;
 
-  let (build_stdout, build_stderr) = run_command(
+  Ok((
+    "Dependencies installed".to_string(),
+    format!("pnpm install stdout:\n{stdout}\npnpm install stderr:\n{stderr}"),
+  ))
+}
+
+fn run_vencord_build(repo_dir: &str, tools: &BuildTools) -> Result<(String, String), RepoError> {
+  let (stdout, stderr) = run_command(
+    tools,
     "pnpm",
     &["build"],
     Some(repo_dir),
-    "Failed to build Vencord with pnpm",
+    "Build failed (pnpm build)",
   )?;
 
-  let verbose = format!(
-    "pnpm install stdout:\n{install_stdout}\npnpm install stderr:\n{install_stderr}\n\npnpm build stdout:\n{build_stdout}\npnpm build stderr:\n{build_stderr}"
-  );
+  Ok((
+    format!("Vencord built successfully in {repo_dir}"),
+    format!("pnpm build stdout:\n{stdout}\npnpm build stderr:\n{stderr}"),
+  ))
+}
 
-  Ok((format!("Vencord built successfully in {repo_dir}"), verbose))
+/// Installs dependencies, then builds. `on_phase` is called as each phase
+/// starts so callers can report progress.
+pub fn build_vencord_repo(
+  repo_dir: &str,
+  tools: &BuildTools,
+  offline: bool,
+  on_phase: impl Fn(BuildPhase),
+) -> Result<(BuildOutcome, String), RepoError> {
+  on_phase(BuildPhase::Install);
+  let (install, install_verbose) = install_vencord_dependencies(repo_dir, tools, offline)?;
+
+  on_phase(BuildPhase::Build);
+  let (build, build_verbose) = run_vencord_build(repo_dir, tools)?;
+
+  Ok((BuildOutcome { install, build }, format!("{install_verbose}\n\n{build_verbose}")))
 }
 
-pub fn inject_vencord_repo(repo_dir: &str, locations: &[String]) -> Result<(String, String), String> {
-  if locations.is_empty() {
-    return Ok(("No Discord clients selected for injection; skipping".to_string(), String::new()));
+/// Runs `pnpm inject` for one location and returns its stdout and stderr.
+fn inject_location(
+  repo_dir: &str,
+  location: &str,
+  tools: &BuildTools,
+) -> Result<(String, String), String> {
+  check_resources_writable(Path::new(location))?;
+
+  let (stdout, stderr) = run_command(
+    tools,
+    "pnpm",
+    &["inject", "-location", location],
+    Some(repo_dir),
+    &format!("Failed to inject Vencord into {location} with pnpm"),
+  )?;
+
+  if output_indicates_inject_failure(&stdout, &stderr) {
+    return Err(format!(
+      "injection command reported failure. stdout: {} | stderr: {}",
+      if stdout.is_empty() { "<empty>" } else { &stdout },
+      if stderr.is_empty() { "<empty>" } else { &stderr },
+    ));
   }
 
-  check_tool("pnpm", &["--version"], "pnpm")?;
+  Ok((stdout, stderr))
+}
 
-  let mut unique_locations: Vec<String> = Vec::new();
-  for location in locations {
-    if !unique_locations.contains(location) {
-      unique_locations.push(location.clone());
-    }
+pub fn inject_vencord_repo(
+  repo_dir: &str,
+  locations: &[String],
+  tools: &BuildTools,
+) -> Result<(String, String), String> {
+  if locations.is_empty() {
+    return Ok(("No Discord clients selected for injection; skipping".to_string(), String::new()));
   }
 
-  let total = unique_locations.len();
+  let results = inject_locations(repo_dir, locations, tools)?;
+
+  let total = results.len();
   let mut succeeded = Vec::new();
   let mut succeeded_details = Vec::new();
   let mut failed = Vec::new();
 
-  for location in &unique_locations {
-    if let Err(err) = ensure_inject_location_writable(location) {
-      failed.push(format!("- {location}: {err}"));
-      continue;
-    }
+  for result in &results {
+    let location = &result.location;
 
-    let (stdout, stderr) = match run_command(
-      "pnpm",
-      &["inject", "-location", location],
-      Some(repo_dir),
-      &format!("Failed to inject Vencord into {location} with pnpm"),
-    ) {
-      Ok(output) => output,
-      Err(err) => {
-        failed.push(format!("- {location}: {err}"));
-        continue;
-      }
-    };
-
-    if output_indicates_inject_failure(&stdout, &stderr) {
-      failed.push(format!(
-        "- {location}: injection command reported failure. stdout: {} | stderr: {}",
-        if stdout.is_empty() { "<empty>" } else { &stdout },
-        if stderr.is_empty() { "<empty>" } else { &stderr },
-      ));
+    if let Some(err) = &result.error {
+      failed.push(format!("- {location}: {err}"));
       continue;
     }
 
@@ -352,12 +870,12 @@ pub fn inject_vencord_repo(repo_dir: &str, locations: &[String]) -> Result<(Stri
 
     let mut detail_lines = vec![format!("- location: {location}")];
 
-    if !stdout.is_empty() {
-      detail_lines.push(format!("  stdout: {stdout}"))
+    if !result.stdout.is_empty() {
+      detail_lines.push(format!("  stdout: {}", result.stdout))
     }
 
-    if !stderr.is_empty() {
-      detail_lines.push(format!("  stderr: {stderr}"))
+    if !result.stderr.is_empty() {
+      detail_lines.push(format!("  stderr: {}", result.stderr))
     }
 
     succeeded_details.push(detail_lines.join("\n"))
@@ -398,3 +916,359 @@ pub fn inject_vencord_repo(repo_dir: &str, locations: &[String]) -> Result<(Stri
 
   Ok((message, verbose))
 }
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientInjectResult {
+  pub location: String,
+  pub injected: bool,
+  pub error: Option<String>,
+  #[serde(skip)]
+  stdout: String,
+  #[serde(skip)]
+  stderr: String,
+}
+
+/// Injects into each location once. Failures are reported per location so one
+/// broken client does not stop the others.
+pub fn inject_locations(
+  repo_dir: &str,
+  locations: &[String],
+  tools: &BuildTools,
+) -> Result<Vec<ClientInjectResult>, String> {
+  ensure_build_present(repo_dir)?;
+  check_tool(tools, "pnpm", &["--version"], "pnpm")?;
+
+  let mut unique_locations: Vec<&String> = Vec::new();
+  for location in locations {
+    if !unique_locations.contains(&location) {
+      unique_locations.push(location);
+    }
+  }
+
+  Ok(
+    unique_locations
+      .into_iter()
+      .map(|location| match inject_location(repo_dir, location, tools) {
+        Ok((stdout, stderr)) => {
+          log::info!("[inject] Injected Vencord into {location}");
+          ClientInjectResult {
+            location: location.clone(),
+            injected: true,
+            error: None,
+            stdout,
+            stderr,
+          }
+        }
+        Err(err) => {
+          log::error!("[inject] Failed to inject into {location}: {err}");
+          ClientInjectResult {
+            location: location.clone(),
+            injected: false,
+            error: Some(err),
+            stdout: String::new(),
+            stderr: String::new(),
+          }
+        }
+      })
+      .collect(),
+  )
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectionPreview {
+  pub target_files: Vec<String>,
+  pub will_overwrite: Vec<String>,
+}
+
+// Squirrel installs (Windows) keep one `app-<version>` folder per update,
+// Linux installs have `resources` at the root and macOS bundles use
+// `Contents/Resources`.
+fn resources_dirs(location: &Path) -> Vec<PathBuf> {
+  let mut dirs: Vec<PathBuf> = [location.join("resources"), location.join("Contents").join("Resources")]
+    .into_iter()
+    .filter(|dir| dir.is_dir())
+    .collect();
+
+  if let Ok(entries) = fs::read_dir(location) {
+    let mut app_dirs: Vec<PathBuf> = entries
+      .filter_map(Result::ok)
+      .map(|entry| entry.path())
+      .filter(|path| {
+        path
+          .file_name()
+          .and_then(|name| name.to_str())
+          .map_or(false, |name| name.starts_with("app-"))
+      })
+      .map(|path| path.join("resources"))
+      .filter(|dir| dir.is_dir())
+      .collect();
+
+    app_dirs.sort();
+    dirs.extend(app_dirs);
+  }
+
+  dirs
+}
+
+/// Lists the files `pnpm inject` would write for the given install locations.
+/// Vencord's injector moves the original `app.asar` to `_app.asar` and writes
+/// a shim `app.asar` in its place; an existing `_app.asar` means the install
+/// is already patched, so only the shim is replaced.
+pub fn preview_injection(locations: &[String]) -> InjectionPreview {
+  let mut preview = InjectionPreview::default();
+
+  for location in locations {
+    for resources in resources_dirs(Path::new(location)) {
+      let shim = resources.join("app.asar");
+      let original = resources.join("_app.asar");
+      let legacy_app_dir = resources.join("app");
+
+      preview.target_files.push(shim.display().to_string());
+
+      if shim.exists() {
+        preview.will_overwrite.push(shim.display().to_string());
+      }
+
+      if !original.exists() {
+        preview.target_files.push(original.display().to_string());
+      }
+
+      // Older injector versions used an unpacked `resources/app` folder,
+      // which is replaced as well.
+      if legacy_app_dir.is_dir() {
+        preview.will_overwrite.push(legacy_app_dir.display().to_string());
+      }
+    }
+  }
+
+  preview
+}
+
+/// Undoes an injection by putting the original `_app.asar` back in place of
+/// the Vencord shim. Only resources folders inside the canonical install
+/// location are touched; unpatched folders are left alone.
+pub fn unpatch_location(location: &str) -> Result<Vec<String>, String> {
+  let root = dunce::canonicalize(location)
+    .map_err(|err| format!("Failed to resolve {location}: {err}"))?;
+  let mut restored = Vec::new();
+
+  for resources in resources_dirs(&root) {
+    let resources = dunce::canonicalize(&resources)
+      .map_err(|err| format!("Failed to resolve {}: {err}", resources.display()))?;
+
+    if !resources.starts_with(&root) {
+      log::warn!("[repo] Skipping {} because it is outside {}", resources.display(), root.display());
+      continue;
+    }
+
+    let original = resources.join("_app.asar");
+
+    if !original.is_file() {
+      continue;
+    }
+
+    let shim = resources.join("app.asar");
+
+    if shim.is_dir() {
+      fs::remove_dir_all(&shim)
+    } else if shim.exists() {
+      fs::remove_file(&shim)
+    } else {
+      Ok(())
+    }
+    .map_err(|err| format!("Failed to remove Vencord shim {}: {err}", shim.display()))?;
+
+    fs::rename(&original, &shim).map_err(|err| {
+      format!(
+        "Failed to restore {} to {}: {err}",
+        original.display(),
+        shim.display()
+      )
+    })?;
+
+    log::info!("[repo] Restored original app.asar in {}", resources.display());
+    restored.push(shim.display().to_string());
+  }
+
+  Ok(restored)
+}
+
+fn git_stdout(args: &[&str]) -> Option<String> {
+  let output = build_command("git").args(args).output().ok()?;
+
+  if !output.status.success() {
+    return None;
+  }
+
+  Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Upstream default branch of `origin`, read from the local `origin/HEAD`
+/// ref and falling back to asking the remote.
+fn default_branch(repo_path_str: &str) -> Option<String> {
+  let from_ref = git_stdout(&[
+    "-C",
+    repo_path_str,
+    "symbolic-ref",
+    "--short",
+    "refs/remotes/origin/HEAD",
+  ])
+  .and_then(|head| head.strip_prefix("origin/").map(str::to_string));
+
+  if let Some(branch) = from_ref.filter(|branch| !branch.is_empty()) {
+    return Some(branch);
+  }
+
+  git_stdout(&["-C", repo_path_str, "remote", "show", "origin"])?
+    .lines()
+    .find_map(|line| line.trim().strip_prefix("HEAD branch:"))
+    .map(|branch| branch.trim().to_string())
+    .filter(|branch| !branch.is_empty() && branch != "(unknown)")
+}
+
+fn origin_remote_url(repo_path_str: &str) -> Option<String> {
+  let output = build_command("git")
+    .args(["-C", repo_path_str, "remote", "get-url", "origin"])
+    .output()
+    .ok()?;
+
+  if !output.status.success() {
+    return None;
+  }
+
+  Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn is_suspiciously_broad_path(path: &Path) -> bool {
+  if path.parent().is_none() || path.components().count() <= 2 {
+    return true;
+  }
+
+  [
+    dirs::home_dir(),
+    dirs::desktop_dir(),
+    dirs::document_dir(),
+    dirs::download_dir(),
+    dirs::config_dir(),
+    dirs::data_local_dir(),
+  ]
+  .into_iter()
+  .flatten()
+  .filter_map(|dir| dunce::canonicalize(&dir).ok())
+  .any(|dir| path == dir || dir.starts_with(path))
+}
+
+/// State of the configured clone, so the UI can offer the right next step
+/// (clone, build or patch).
+#[tauri::command]
+pub fn repo_status() -> Result<RepoStatus, InstallerError> {
+  let options = options::read_user_options().map_err(InstallerError::filesystem)?;
+  let repo_path = vencord_repo_path(&options.vencord_repo_dir);
+
+  if !repo_path.is_dir() {
+    return Ok(RepoStatus {
+      exists: repo_path.exists(),
+      is_git: false,
+      has_build: false,
+      current_ref: None,
+      remote_url: None,
+    });
+  }
+
+  let repo_path_str = repo_path.to_string_lossy();
+  let is_git = is_git_repo(&repo_path_str)?;
+
+  let (current_ref, remote_url) = if is_git {
+    let current_ref = git_stdout(&["-C", &repo_path_str, "rev-parse", "--abbrev-ref", "HEAD"])
+      .filter(|name| !name.is_empty() && name != "HEAD")
+      .or_else(|| git_stdout(&["-C", &repo_path_str, "rev-parse", "--short", "HEAD"]));
+
+    (current_ref, origin_remote_url(&repo_path_str))
+  } else {
+    (None, None)
+  };
+
+  Ok(RepoStatus {
+    exists: true,
+    is_git,
+    has_build: missing_build_artifacts(&repo_path).is_empty(),
+    current_ref,
+    remote_url,
+  })
+}
+
+/// Fingerprint of the build currently in the configured clone.
+#[tauri::command]
+pub fn current_build_fingerprint() -> Option<String> {
+  let options = options::read_user_options().ok()?;
+
+  build_fingerprint(&vencord_repo_path(&options.vencord_repo_dir))
+}
+
+#[tauri::command]
+pub fn delete_repo_clone() -> Result<RepoCloneDeletion, InstallerError> {
+  let options = options::read_user_options().map_err(InstallerError::filesystem)?;
+  let configured = vencord_repo_path(&options.vencord_repo_dir);
+
+  if !configured.exists() {
+    return Err(InstallerError::not_found(format!(
+      "No Vencord clone found at {}",
+      configured.display()
+    )));
+  }
+
+  let repo_path = dunce::canonicalize(&configured).map_err(|err| {
+    InstallerError::filesystem(format!("Failed to resolve {}: {err}", configured.display()))
+  })?;
+
+  if is_suspiciously_broad_path(&repo_path) {
+    return Err(InstallerError::invalid_input(format!(
+      "Refusing to delete {} because it is not a dedicated Vencord directory",
+      repo_path.display()
+    )));
+  }
+
+  if !repo_path.join(".git").exists() {
+    return Err(InstallerError::invalid_input(format!(
+      "Refusing to delete {} because it is not a git clone",
+      repo_path.display()
+    )));
+  }
+
+  let repo_path_str = repo_path
+    .to_str()
+    .ok_or_else(|| InstallerError::invalid_input("Invalid repository path"))?;
+
+  let origin = origin_remote_url(repo_path_str).ok_or_else(|| {
+    InstallerError::Git {
+      message: format!(
+        "Refusing to delete {} because its origin remote could not be read",
+        repo_path.display()
+      ),
+      details: None,
+    }
+  })?;
+
+  if options::normalize_remote_url(&origin) != options::normalize_remote_url(&options.vencord_repo_url) {
+    return Err(InstallerError::invalid_input(format!(
+      "Refusing to delete {} because its origin ({origin}) does not match the configured repository {}",
+      repo_path.display(),
+      options.vencord_repo_url
+    )));
+  }
+
+  let freed_bytes = backup::dir_size(&repo_path).map_err(InstallerError::filesystem)?;
+  backup::remove_dir_tree(&repo_path).map_err(InstallerError::filesystem)?;
+
+  log::info!(
+    "[repo] Deleted Vencord clone at {} ({freed_bytes} bytes)",
+    repo_path.display()
+  );
+
+  Ok(RepoCloneDeletion {
+    path: repo_path.to_string_lossy().into_owned(),
+    freed_bytes,
+  })
+}
@@ -0,0 +1,230 @@
+use reqwest::{blocking::Client, StatusCode};
+use serde::Serialize;
+use std::{
+  process::Stdio,
+  thread,
+  time::{Duration, Instant},
+};
+
+use crate::{command_utils::build_command, error::InstallerError, options};
+
+use super::themes;
+
+const SOURCE_CHECK_TIMEOUT: Duration = Duration::from_secs(8);
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum SourceKind {
+  Repository,
+  Theme,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceCheck {
+  pub url: String,
+  pub kind: SourceKind,
+  pub reachable: bool,
+  pub status: Option<u16>,
+  pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlValidation {
+  pub valid: bool,
+  pub normalized: String,
+  pub reason: Option<String>,
+}
+
+impl UrlValidation {
+  fn invalid(url: &str, reason: String) -> Self {
+    Self {
+      valid: false,
+      normalized: url.trim().to_string(),
+      reason: Some(reason),
+    }
+  }
+}
+
+fn check_theme_url(client: &Client, theme: &options::ProvidedThemeInfo) -> SourceCheck {
+  let url = theme.url.as_str();
+  let mut response = themes::with_theme_auth(client.head(url), theme).send();
+
+  // Some hosts reject HEAD outright; retry with a plain GET before giving up.
+  if let Ok(res) = &response {
+    if res.status() == StatusCode::METHOD_NOT_ALLOWED {
+      response = themes::with_theme_auth(client.get(url), theme).send();
+    }
+  }
+
+  match response {
+    Ok(res) => SourceCheck {
+      url: url.to_string(),
+      kind: SourceKind::Theme,
+      reachable: res.status().is_success(),
+      status: Some(res.status().as_u16()),
+      error: if res.status().is_success() {
+        None
+      } else {
+        Some(format!("Request failed with status {}", res.status()))
+      },
+    },
+    Err(err) => SourceCheck {
+      url: url.to_string(),
+      kind: SourceKind::Theme,
+      reachable: false,
+      status: err.status().map(|status| status.as_u16()),
+      error: Some(err.to_string()),
+    },
+  }
+}
+
+fn git_ls_remote(url: &str) -> Result<(), String> {
+  let mut child = build_command("git")
+    .args(["ls-remote", "--heads", url])
+    .env("GIT_TERMINAL_PROMPT", "0")
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| format!("Failed to run git: {err}"))?;
+
+  let started = Instant::now();
+
+  loop {
+    match child.try_wait() {
+      Ok(Some(status)) => {
+        if status.success() {
+          return Ok(());
+        }
+
+        let output = child
+          .wait_with_output()
+          .map_err(|err| format!("Failed to read git output: {err}"))?;
+
+        return Err(format!(
+          "git ls-remote failed with status {status}: {}",
+          String::from_utf8_lossy(&output.stderr).trim()
+        ));
+      }
+      Ok(None) => {
+        if started.elapsed() >= SOURCE_CHECK_TIMEOUT {
+          let _ = child.kill();
+          let _ = child.wait();
+          return Err(format!(
+            "git ls-remote timed out after {} seconds",
+            SOURCE_CHECK_TIMEOUT.as_secs()
+          ));
+        }
+
+        thread::sleep(Duration::from_millis(100));
+      }
+      Err(err) => return Err(format!("Failed to wait for git: {err}")),
+    }
+  }
+}
+
+fn check_repository_url(url: &str) -> SourceCheck {
+  let result = git_ls_remote(url);
+
+  SourceCheck {
+    url: url.to_string(),
+    kind: SourceKind::Repository,
+    reachable: result.is_ok(),
+    status: None,
+    error: result.err(),
+  }
+}
+
+#[tauri::command]
+pub fn test_sources() -> Result<Vec<SourceCheck>, InstallerError> {
+  let options = options::read_user_options().map_err(InstallerError::filesystem)?;
+
+  let mut repositories = vec![options.vencord_repo_url.clone()];
+  for url in options::resolve_plugin_repositories(&options) {
+    if !repositories.contains(&url) {
+      repositories.push(url);
+    }
+  }
+
+  let themes = options::resolve_themes(&options);
+
+  let client = Client::builder()
+    .timeout(SOURCE_CHECK_TIMEOUT)
+    .build()
+    .map_err(|err| InstallerError::network(format!("Failed to create HTTP client: {err}")))?;
+
+  let mut results: Vec<SourceCheck> = repositories
+    .iter()
+    .map(|url| check_repository_url(url))
+    .collect();
+
+  results.extend(themes.iter().map(|theme| check_theme_url(&client, theme)));
+
+  Ok(results)
+}
+
+#[tauri::command]
+pub fn validate_repo_url(url: String, check_remote: Option<bool>) -> UrlValidation {
+  let normalized = match options::normalize_repo_url(&url) {
+    Ok(normalized) => normalized,
+    Err(reason) => return UrlValidation::invalid(&url, reason),
+  };
+
+  let reason = if check_remote.unwrap_or(false) {
+    git_ls_remote(&normalized).err()
+  } else {
+    None
+  };
+
+  UrlValidation {
+    valid: reason.is_none(),
+    normalized,
+    reason,
+  }
+}
+
+#[tauri::command]
+pub fn validate_theme_url(url: String) -> UrlValidation {
+  let normalized = match options::normalize_theme_url(&url) {
+    Ok(normalized) => normalized,
+    Err(reason) => return UrlValidation::invalid(&url, reason),
+  };
+
+  let theme = options::ProvidedThemeInfo {
+    id: "validate".to_string(),
+    name: normalized.clone(),
+    url: normalized.clone(),
+    auth_header: None,
+  };
+
+  let reason = themes::theme_http_client()
+    .and_then(|client| {
+      client
+        .head(&normalized)
+        .timeout(SOURCE_CHECK_TIMEOUT)
+        .send()
+        .map_err(|err| format!("Failed to reach {normalized}: {err}"))
+    })
+    .and_then(|response| {
+      // Hosts that refuse HEAD are still treated as valid; the download
+      // itself re-checks the content type.
+      if response.status() == StatusCode::METHOD_NOT_ALLOWED {
+        return Ok(());
+      }
+
+      if !response.status().is_success() {
+        return Err(format!("Request failed with status {}", response.status()));
+      }
+
+      themes::validate_theme_response(&theme, &response)
+    })
+    .err();
+
+  UrlValidation {
+    valid: reason.is_none(),
+    normalized,
+    reason,
+  }
+}
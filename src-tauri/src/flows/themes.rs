@@ -1,60 +1,334 @@
-use reqwest::blocking::get;
+use reqwest::{
+  blocking::{Client, RequestBuilder, Response},
+  header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE, RANGE, RETRY_AFTER},
+  StatusCode, Url,
+  redirect::Policy,
+};
+use chrono::{DateTime, Local};
+use serde::Serialize;
 use std::{
-  fs, io,
+  collections::HashMap,
+  fs,
+  io::{self, Cursor, Read, Write},
   path::{Path, PathBuf},
+  sync::Mutex,
+  time::Duration,
 };
+use tauri::Emitter;
+use tauri_plugin_opener::OpenerExt;
 
-use crate::options::ProvidedThemeInfo;
+use crate::{
+  config::{config_base_dir, is_writable_dir},
+  error::InstallerError,
+  network,
+  options::{self, ProvidedThemeInfo},
+};
 
 pub fn theme_dir() -> Result<PathBuf, String> {
-  #[cfg(target_os = "windows")]
-  {
-    use std::env;
+  let override_dir = options::read_user_options()
+    .ok()
+    .and_then(|options| options.theme_dir_override)
+    .map(PathBuf::from);
 
-    if let Ok(appdata) = env::var("APPDATA") {
-      return Ok(Path::new(&appdata).join("Vencord").join("themes"));
+  if let Some(dir) = override_dir {
+    if is_writable_dir(&dir) {
+      return Ok(dir);
     }
 
-    if let Some(config) = dirs::config_dir() {
-      return Ok(config.join("Vencord").join("themes"));
+    log::warn!(
+      "[themes] Theme directory override {} is not a writable directory; using the default",
+      dir.display()
+    );
+  }
+
+  default_theme_dir()
+}
+
+/// Every directory themes are written to: the Vencord theme directory, plus
+/// the theme folders of other detected clients (currently Vesktop) when
+/// `write_themes_to_all_clients` is enabled.
+pub fn theme_dirs() -> Result<Vec<PathBuf>, String> {
+  let mut dirs = vec![theme_dir()?];
+
+  let all_clients = options::read_user_options()
+    .map(|options| options.write_themes_to_all_clients)
+    .unwrap_or(false);
+
+  if all_clients {
+    for dir in client_theme_dirs() {
+      if !dirs.contains(&dir) {
+        dirs.push(dir);
+      }
     }
+  }
 
-    return Err("Unable to determine theme directory: APPDATA is not set".to_string());
+  Ok(dirs)
+}
+
+fn client_theme_dirs() -> Vec<PathBuf> {
+  let mut roots = Vec::new();
+
+  if let Some(config) = config_base_dir() {
+    roots.push(config.join("vesktop"));
   }
 
   #[cfg(target_os = "linux")]
-  {
-    let config =
-      dirs::config_dir().ok_or_else(|| "Unable to determine config directory".to_string())?;
-    return Ok(config.join("Vencord").join("themes"));
+  if let Some(home) = dirs::home_dir() {
+    roots.push(home.join(".var/app/dev.vencord.Vesktop/config/vesktop"));
   }
 
-  #[cfg(target_os = "macos")]
-  {
-    if let Some(home) = dirs::home_dir() {
-      return Ok(
-        home
-          .join("Library")
-          .join("Application Support")
-          .join("Vencord")
-          .join("themes"),
-      );
-    }
+  roots
+    .into_iter()
+    .filter(|root| root.is_dir())
+    .map(|root| root.join("themes"))
+    .collect()
+}
+
+fn default_theme_dir() -> Result<PathBuf, String> {
+  config_base_dir()
+    .map(|config| config.join("Vencord").join("themes"))
+    .ok_or_else(|| "Unable to determine config directory for theme download".to_string())
+}
+
+/// Opens the Vencord settings folder (where settings.json and quickCss live).
+/// Derived from the default theme folder rather than `theme_dir()`, since a
+/// theme directory override can point anywhere.
+#[tauri::command]
+pub fn open_vencord_settings_dir(app: tauri::AppHandle) -> Result<(), InstallerError> {
+  let dir = default_theme_dir()
+    .ok()
+    .and_then(|dir| dir.parent().map(Path::to_path_buf))
+    .ok_or_else(|| InstallerError::not_found("Unable to determine the Vencord settings directory"))?;
 
-    return Err("Unable to determine home directory for theme download".to_string());
+  fs::create_dir_all(&dir).map_err(|err| {
+    InstallerError::filesystem(format!(
+      "Failed to create Vencord settings directory {}: {err}",
+      dir.display()
+    ))
+  })?;
+
+  app
+    .opener()
+    .open_path(dir.to_string_lossy().into_owned(), None::<&str>)
+    .map_err(|err| InstallerError::filesystem(format!("Failed to open Vencord settings directory: {err}")))
+}
+
+const THEME_MANIFEST_FILE: &str = ".vencord-installer-themes.json";
+const MAX_ARCHIVE_ENTRY_BYTES: u64 = 16 * 1024 * 1024;
+const MAX_THEME_REDIRECTS: usize = 5;
+const THEME_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const THEME_HEADER_BYTES: u64 = 16 * 1024;
+const THEME_DOWNLOAD_CHUNK_BYTES: usize = 64 * 1024;
+// Largest theme body (CSS or archive) accepted from a host.
+const MAX_THEME_BYTES: u64 = 32 * 1024 * 1024;
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeMetadata {
+  pub name: Option<String>,
+  pub author: Option<String>,
+  pub description: Option<String>,
+  pub preview_url: Option<String>,
+  pub version: Option<String>,
+  pub variables: Vec<ThemeVariable>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeVariable {
+  pub kind: String,
+  pub name: String,
+  pub label: String,
+  pub default: Option<String>,
+  pub options: Vec<ThemeVariableOption>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeVariableOption {
+  pub value: String,
+  pub label: String,
+}
+
+fn url_path(url: &str) -> &str {
+  url.split(['?', '#']).next().unwrap_or(url)
+}
+
+fn is_archive_url(url: &str) -> bool {
+  url_path(url).to_lowercase().ends_with(".zip")
+}
+
+fn sanitize_file_name(name: &str) -> Option<String> {
+  let base = name.rsplit(['/', '\\']).next().unwrap_or(name).trim();
+
+  if base.is_empty() || base == "." || base == ".." {
+    return None;
+  }
+
+  let cleaned: String = base
+    .chars()
+    .map(|ch| match ch {
+      '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+      ch if ch.is_control() => '_',
+      ch => ch,
+    })
+    .collect();
+
+  let cleaned = cleaned.trim_start_matches('.').to_string();
+
+  if cleaned.is_empty() {
+    None
+  } else {
+    Some(cleaned)
   }
 }
 
 fn theme_file_name(theme: &ProvidedThemeInfo) -> Result<String, String> {
-  theme
-    .url
+  url_path(&theme.url)
     .rsplit('/')
     .next()
-    .map(|name| name.to_string())
-    .or_else(|| Some(format!("{}.theme.css", theme.id)))
+    .and_then(sanitize_file_name)
+    .or_else(|| sanitize_file_name(&format!("{}.theme.css", theme.id)))
     .ok_or_else(|| format!("could not determine file name from url: {}", theme.url))
 }
 
+fn is_css_file_name(name: &str) -> bool {
+  name.to_lowercase().ends_with(".css")
+}
+
+type ThemeManifest = HashMap<String, Vec<String>>;
+
+fn read_theme_manifest(dir: &Path) -> ThemeManifest {
+  fs::read_to_string(dir.join(THEME_MANIFEST_FILE))
+    .ok()
+    .and_then(|content| serde_json::from_str(&content).ok())
+    .unwrap_or_default()
+}
+
+fn write_theme_manifest(dir: &Path, manifest: &ThemeManifest) -> Result<(), String> {
+  let path = dir.join(THEME_MANIFEST_FILE);
+  let json = serde_json::to_string_pretty(manifest)
+    .map_err(|err| format!("Failed to serialize theme manifest: {err}"))?;
+
+  fs::write(&path, json)
+    .map_err(|err| format!("Failed to write theme manifest {}: {err}", path.display()))
+}
+
+fn managed_theme_files(
+  theme: &ProvidedThemeInfo,
+  manifest: &ThemeManifest,
+) -> Result<Vec<String>, String> {
+  if let Some(files) = manifest.get(&theme.url) {
+    return Ok(files.clone());
+  }
+
+  if is_archive_url(&theme.url) {
+    return Ok(Vec::new());
+  }
+
+  Ok(vec![theme_file_name(theme)?])
+}
+
+fn extract_theme_archive(
+  bytes: &[u8],
+  dir: &Path,
+  url: &str,
+) -> Result<Vec<String>, String> {
+  let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+    .map_err(|err| format!("Theme archive {url} is not a valid zip file: {err}"))?;
+
+  let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+  for index in 0..archive.len() {
+    let entry = archive
+      .by_index(index)
+      .map_err(|err| format!("Failed to read entry {index} of theme archive {url}: {err}"))?;
+
+    if entry.is_dir() || !is_css_file_name(entry.name()) {
+      continue;
+    }
+
+    let Some(file_name) = sanitize_file_name(entry.name()) else {
+      continue;
+    };
+
+    if entry.size() > MAX_ARCHIVE_ENTRY_BYTES {
+      return Err(format!(
+        "Theme archive {url} entry {} is larger than {} bytes",
+        entry.name(),
+        MAX_ARCHIVE_ENTRY_BYTES
+      ));
+    }
+
+    let mut content = Vec::new();
+    entry
+      .take(MAX_ARCHIVE_ENTRY_BYTES)
+      .read_to_end(&mut content)
+      .map_err(|err| format!("Failed to extract {file_name} from theme archive {url}: {err}"))?;
+
+    match files.iter_mut().find(|(name, _)| *name == file_name) {
+      Some((_, existing)) => *existing = content,
+      None => files.push((file_name, content)),
+    }
+  }
+
+  if files.is_empty() {
+    return Err(format!("Theme archive {url} does not contain any .css files"));
+  }
+
+  // Only write once every entry extracted cleanly, so a bad archive never
+  // replaces part of a previously good theme.
+  for (file_name, content) in &files {
+    write_file_atomically(&dir.join(file_name), content)?;
+  }
+
+  Ok(files.into_iter().map(|(file_name, _)| file_name).collect())
+}
+
+/// Writes to a hidden `.partial` file next to `destination` and renames it
+/// into place, so an interrupted write never leaves a truncated theme behind
+/// and the previous version survives any failure.
+fn write_file_atomically(destination: &Path, content: &[u8]) -> Result<(), String> {
+  let file_name = destination
+    .file_name()
+    .and_then(|name| name.to_str())
+    .ok_or_else(|| format!("Invalid theme path {}", destination.display()))?;
+  let temp = destination.with_file_name(format!(".{file_name}.partial"));
+
+  let result = fs::File::create(&temp)
+    .and_then(|mut file| {
+      file.write_all(content)?;
+      file.sync_all()
+    })
+    .and_then(|_| fs::rename(&temp, destination));
+
+  if let Err(err) = result {
+    let _ = fs::remove_file(&temp);
+    return Err(format!("Failed to write theme {}: {err}", destination.display()));
+  }
+
+  Ok(())
+}
+
+/// Temporary files `write_file_atomically` left in the theme directories
+/// when the app exited mid-write.
+pub fn partial_theme_files() -> Vec<PathBuf> {
+  theme_dirs()
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|dir| fs::read_dir(dir).ok())
+    .flat_map(|entries| entries.filter_map(Result::ok))
+    .map(|entry| entry.path())
+    .filter(|path| {
+      path.is_file()
+        && path
+          .file_name()
+          .and_then(|name| name.to_str())
+          .is_some_and(|name| name.starts_with('.') && name.ends_with(".partial"))
+    })
+    .collect()
+}
+
 fn is_cross_device_link(err: &io::Error) -> bool {
   #[cfg(not(target_os = "windows"))]
   { err.kind() == io::ErrorKind::CrossesDevices }
@@ -62,22 +336,50 @@ fn is_cross_device_link(err: &io::Error) -> bool {
   { err.raw_os_error() == Some(0x11) }
 }
 
+/// Moves managed theme files out of every directory in `theme_dirs()`, or
+/// copies them when `keep_source` is set. The Vencord theme directory goes to
+/// `destination/themes` and each further one to `destination/themes-<n>`.
+/// Returns the backup folders that received files.
 pub fn move_themes_to_backup(
   destination: &Path,
   themes: &[ProvidedThemeInfo],
-) -> Result<Option<PathBuf>, String> {
-  let source = theme_dir()?;
+  keep_source: bool,
+) -> Result<Vec<PathBuf>, String> {
+  let mut written = Vec::new();
+
+  for (index, source) in theme_dirs()?.iter().enumerate() {
+    let dest_path = if index == 0 {
+      destination.join("themes")
+    } else {
+      destination.join(format!("themes-{index}"))
+    };
+
+    if let Some(path) = move_dir_themes_to_backup(source, &dest_path, themes, keep_source)? {
+      written.push(path);
+    }
+  }
 
+  Ok(written)
+}
+
+fn move_dir_themes_to_backup(
+  source: &Path,
+  dest_path: &Path,
+  themes: &[ProvidedThemeInfo],
+  keep_source: bool,
+) -> Result<Option<PathBuf>, String> {
   if themes.is_empty() || !source.exists() {
     return Ok(None);
   }
 
+  let manifest = read_theme_manifest(source);
   let mut allowed_files = Vec::new();
 
   for theme in themes {
-    let file_name = theme_file_name(theme)?;
-    if !file_name.is_empty() {
-      allowed_files.push(file_name);
+    for file_name in managed_theme_files(theme, &manifest)? {
+      if !file_name.is_empty() && !allowed_files.contains(&file_name) {
+        allowed_files.push(file_name);
+      }
     }
   }
 
@@ -85,7 +387,6 @@ pub fn move_themes_to_backup(
     return Ok(None);
   }
 
-  let dest_path = destination.join("themes");
   let mut moved_any = false;
 
   for file_name in allowed_files {
@@ -96,7 +397,7 @@ pub fn move_themes_to_backup(
     }
 
     if !moved_any {
-      fs::create_dir_all(&dest_path).map_err(|err| {
+      fs::create_dir_all(dest_path).map_err(|err| {
         format!(
           "Failed to create backup theme directory {}: {err}",
           dest_path.display(),
@@ -106,6 +407,18 @@ pub fn move_themes_to_backup(
 
     let dest_file = dest_path.join(&file_name);
 
+    if keep_source {
+      fs::copy(&source_file, &dest_file).map_err(|err| {
+        format!(
+          "Failed to copy {} to {}: {err}",
+          source_file.display(),
+          dest_file.display()
+        )
+      })?;
+      moved_any = true;
+      continue;
+    }
+
     match fs::rename(&source_file, &dest_file) {
       Ok(_) => moved_any = true,
       Err(err) => {
@@ -136,55 +449,927 @@ pub fn move_themes_to_backup(
   }
 
   if moved_any {
-    Ok(Some(dest_path))
+    Ok(Some(dest_path.to_path_buf()))
   } else {
     Ok(None)
   }
 }
 
-// NOTE: Uses `reqwest::blocking::get` internally. Must always be called from a
+pub fn theme_http_client() -> Result<Client, String> {
+  Client::builder()
+    .redirect(Policy::limited(MAX_THEME_REDIRECTS))
+    .timeout(THEME_REQUEST_TIMEOUT)
+    .build()
+    .map_err(|err| format!("Failed to create HTTP client: {err}"))
+}
+
+/// Attaches the theme's `Authorization` header, marked sensitive so reqwest
+/// keeps it out of its own debug output.
+pub fn with_theme_auth(request: RequestBuilder, theme: &ProvidedThemeInfo) -> RequestBuilder {
+  let Some(header) = theme.auth_header.as_deref() else {
+    return request;
+  };
+
+  match HeaderValue::from_str(header) {
+    Ok(mut value) => {
+      value.set_sensitive(true);
+      request.header(AUTHORIZATION, value)
+    }
+    Err(_) => {
+      log::warn!("[themes] Ignoring invalid authorization header for {}", theme.url);
+      request
+    }
+  }
+}
+
+pub fn validate_theme_response(theme: &ProvidedThemeInfo, response: &Response) -> Result<(), String> {
+  let final_url = response.url();
+
+  match final_url.scheme() {
+    "https" => {}
+    "http" if !theme.url.starts_with("https://") => {}
+    scheme => {
+      return Err(format!(
+        "Theme {} redirected to an unsupported {scheme} URL: {final_url}",
+        theme.url
+      ));
+    }
+  }
+
+  let content_type = response
+    .headers()
+    .get(CONTENT_TYPE)
+    .and_then(|value| value.to_str().ok())
+    .unwrap_or("")
+    .to_lowercase();
+
+  if content_type.contains("text/html") {
+    return Err(format!(
+      "Theme {} resolved to an HTML page at {final_url} instead of a theme file",
+      theme.url
+    ));
+  }
+
+  if is_archive_url(&theme.url) && content_type.starts_with("text/") {
+    return Err(format!(
+      "Theme archive {} resolved to {content_type} content at {final_url}",
+      theme.url
+    ));
+  }
+
+  Ok(())
+}
+
+/// Recognises a host's rate-limit response (GitHub answers 403 with
+/// `X-RateLimit-Remaining: 0`, others 429) and says when to try again.
+/// Retrying straight away would only extend the limit, so callers give up.
+fn rate_limit_error(url: &str, response: &Response) -> Option<String> {
+  let header = |name: &str| {
+    response
+      .headers()
+      .get(name)
+      .and_then(|value| value.to_str().ok())
+      .map(str::trim)
+  };
+
+  let exhausted = header("x-ratelimit-remaining") == Some("0");
+  let limited = match response.status() {
+    StatusCode::TOO_MANY_REQUESTS => true,
+    StatusCode::FORBIDDEN => exhausted,
+    _ => false,
+  };
+
+  if !limited {
+    return None;
+  }
+
+  let now = Local::now();
+  let retry_at = header("x-ratelimit-reset")
+    .and_then(|reset| reset.parse::<i64>().ok())
+    .and_then(|reset| DateTime::from_timestamp(reset, 0))
+    .map(|reset| reset.with_timezone(&Local))
+    .or_else(|| {
+      header(RETRY_AFTER.as_str())
+        .and_then(|seconds| seconds.parse::<i64>().ok())
+        .map(|seconds| now + chrono::Duration::seconds(seconds))
+    });
+
+  let host = response.url().host_str().unwrap_or("The theme host").to_string();
+  let when = match retry_at {
+    Some(at) => {
+      let minutes = (at - now).num_minutes().max(1);
+      format!("try again after {} (about {minutes} minute(s))", at.format("%H:%M"))
+    }
+    None => "wait a while before trying again".to_string(),
+  };
+
+  Some(format!(
+    "{host} rate limit reached while downloading {url}; {when}, or add an authorization header for the theme so requests are authenticated"
+  ))
+}
+
+/// Deletes files the installer downloaded for themes that are no longer
+/// enabled. Only files recorded in the theme manifest are touched.
+pub fn prune_disabled_themes(enabled: &[ProvidedThemeInfo]) -> Result<Vec<String>, String> {
+  let mut removed = Vec::new();
+
+  for dir in theme_dirs()? {
+    removed.extend(prune_disabled_themes_in(&dir, enabled)?);
+  }
+
+  Ok(removed)
+}
+
+fn prune_disabled_themes_in(dir: &Path, enabled: &[ProvidedThemeInfo]) -> Result<Vec<String>, String> {
+  if !dir.exists() {
+    return Ok(Vec::new());
+  }
+
+  let mut manifest = read_theme_manifest(&dir);
+  let still_needed: Vec<String> = enabled
+    .iter()
+    .filter_map(|theme| manifest.get(&theme.url))
+    .flatten()
+    .cloned()
+    .collect();
+
+  let stale_urls: Vec<String> = manifest
+    .keys()
+    .filter(|url| !enabled.iter().any(|theme| &theme.url == *url))
+    .cloned()
+    .collect();
+
+  if stale_urls.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let mut removed = Vec::new();
+
+  for url in stale_urls {
+    for file_name in manifest.remove(&url).unwrap_or_default() {
+      if still_needed.contains(&file_name) {
+        continue;
+      }
+
+      let path = dir.join(&file_name);
+
+      match fs::remove_file(&path) {
+        Ok(()) => removed.push(file_name),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => {
+          return Err(format!("Failed to remove theme {}: {err}", path.display()));
+        }
+      }
+    }
+  }
+
+  write_theme_manifest(&dir, &manifest)?;
+
+  if !removed.is_empty() {
+    log::info!("[themes] Removed {} disabled theme file(s): {}", removed.len(), removed.join(", "));
+  }
+
+  Ok(removed)
+}
+
+fn is_valid_theme_name(name: &str) -> bool {
+  !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..") && name != THEME_MANIFEST_FILE
+}
+
+/// Deletes one file from the theme directory. Files the installer did not
+/// download are refused unless `force` is set, so hand-written themes kept in
+/// the same folder are safe by default.
+#[tauri::command]
+pub fn delete_installed_theme(name: String, force: bool) -> Result<(), InstallerError> {
+  if !is_valid_theme_name(&name) {
+    return Err(InstallerError::invalid_input(format!("Invalid theme name: {name}")));
+  }
+
+  let dir = theme_dir()
+    .and_then(|dir| {
+      dunce::canonicalize(&dir)
+        .map_err(|err| format!("Failed to resolve theme directory {}: {err}", dir.display()))
+    })
+    .map_err(InstallerError::filesystem)?;
+  let path = dunce::canonicalize(dir.join(&name))
+    .map_err(|_| InstallerError::not_found(format!("Theme {name} not found in {}", dir.display())))?;
+
+  if path.parent() != Some(dir.as_path()) || !path.is_file() {
+    return Err(InstallerError::invalid_input(format!(
+      "Refusing to delete {}: not a theme file in {}",
+      path.display(),
+      dir.display()
+    )));
+  }
+
+  let mut manifest = read_theme_manifest(&dir);
+  let managed = manifest.values().any(|files| files.contains(&name));
+
+  if !managed && !force {
+    return Err(InstallerError::invalid_input(format!(
+      "{name} was not installed by the installer; pass force to delete it anyway"
+    )));
+  }
+
+  fs::remove_file(&path).map_err(|err| {
+    InstallerError::filesystem(format!("Failed to remove theme {}: {err}", path.display()))
+  })?;
+
+  if managed {
+    for files in manifest.values_mut() {
+      files.retain(|file| *file != name);
+    }
+    manifest.retain(|_, files| !files.is_empty());
+    write_theme_manifest(&dir, &manifest).map_err(InstallerError::filesystem)?;
+  }
+
+  log::info!("[themes] Deleted theme file {}", path.display());
+
+  Ok(())
+}
+
+/// Payload of the `theme-download-progress` event. `total_bytes` is `None`
+/// when the server sent no Content-Length, so progress is indeterminate.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThemeDownloadProgress<'a> {
+  theme_id: &'a str,
+  name: &'a str,
+  received_bytes: u64,
+  total_bytes: Option<u64>,
+  done: bool,
+}
+
+fn read_theme_body(
+  theme: &ProvidedThemeInfo,
+  mut response: Response,
+  app: Option<&tauri::AppHandle>,
+) -> Result<Vec<u8>, String> {
+  let total_bytes = response.content_length();
+  let emit = |received_bytes: u64, done: bool| {
+    if let Some(app) = app {
+      let _ = app.emit(
+        "theme-download-progress",
+        ThemeDownloadProgress {
+          theme_id: &theme.id,
+          name: &theme.name,
+          received_bytes,
+          total_bytes,
+          done,
+        },
+      );
+    }
+  };
+
+  let too_large = || {
+    format!(
+      "Theme {} is larger than the {} MB limit",
+      theme.url,
+      MAX_THEME_BYTES / (1024 * 1024)
+    )
+  };
+
+  if total_bytes.is_some_and(|total| total > MAX_THEME_BYTES) {
+    return Err(too_large());
+  }
+
+  // Content-Length is only a hint from the host, so it is not used to size
+  // the buffer.
+  let mut body = Vec::new();
+  let mut chunk = vec![0u8; THEME_DOWNLOAD_CHUNK_BYTES];
+
+  emit(0, false);
+
+  loop {
+    let read = response
+      .read(&mut chunk)
+      .map_err(|err| format!("Failed to read response body for {}: {err}", theme.url))?;
+
+    if read == 0 {
+      break;
+    }
+
+    if (body.len() + read) as u64 > MAX_THEME_BYTES {
+      return Err(too_large());
+    }
+
+    body.extend_from_slice(&chunk[..read]);
+    emit(body.len() as u64, false);
+  }
+
+  emit(body.len() as u64, true);
+
+  Ok(body)
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ThemeDownloadStatus {
+  Downloaded,
+  Failed,
+  /// Not attempted because an earlier request hit the host's rate limit.
+  Skipped,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeDownloadResult {
+  pub name: String,
+  pub url: String,
+  pub status: ThemeDownloadStatus,
+  pub bytes: Option<u64>,
+  pub error: Option<String>,
+  /// Extra client theme folders this theme could not be written to. The
+  /// download still counts since the primary folder has it.
+  pub warnings: Vec<String>,
+}
+
+/// One-line summary of a download run, for logs and run records.
+pub fn summarize_theme_downloads(results: &[ThemeDownloadResult]) -> String {
+  let downloaded: Vec<&str> = results
+    .iter()
+    .filter(|result| result.status == ThemeDownloadStatus::Downloaded)
+    .map(|result| result.name.as_str())
+    .collect();
+  let failed: Vec<&str> = results
+    .iter()
+    .filter(|result| result.status != ThemeDownloadStatus::Downloaded)
+    .map(|result| result.name.as_str())
+    .collect();
+
+  let mut summary = format!(
+    "Downloaded {} of {} theme(s): {}",
+    downloaded.len(),
+    results.len(),
+    downloaded.join(", ")
+  );
+
+  if !failed.is_empty() {
+    summary.push_str(&format!(" (failed: {})", failed.join(", ")));
+  }
+
+  let mut warnings: Vec<&str> = results
+    .iter()
+    .flat_map(|result| result.warnings.iter().map(String::as_str))
+    .collect();
+  warnings.sort_unstable();
+  warnings.dedup();
+
+  if !warnings.is_empty() {
+    summary.push_str(&format!("; {}", warnings.join("; ")));
+  }
+
+  summary
+}
+
+enum ThemeFetchError {
+  RateLimited(String),
+  Failed(String),
+  /// Not requested because another request already hit the rate limit.
+  Skipped(String),
+}
+
+/// Downloads one theme, returning its body and the URL it resolved to.
+fn fetch_theme(
+  client: &Client,
+  theme: &ProvidedThemeInfo,
+  app: Option<&tauri::AppHandle>,
+) -> Result<(Vec<u8>, String), ThemeFetchError> {
+  let response = with_theme_auth(client.get(&theme.url), theme)
+    .send()
+    .map_err(|err| ThemeFetchError::Failed(format!("Failed to download {}: {err}", theme.url)))?;
+
+  if let Some(err) = rate_limit_error(&theme.url, &response) {
+    log::warn!("[themes] {err}");
+    return Err(ThemeFetchError::RateLimited(err));
+  }
+
+  if !response.status().is_success() {
+    return Err(ThemeFetchError::Failed(format!(
+      "Theme request failed for {} with status {}",
+      theme.url,
+      response.status()
+    )));
+  }
+
+  validate_theme_response(theme, &response).map_err(ThemeFetchError::Failed)?;
+
+  let resolved_url = response.url().to_string();
+  let body = read_theme_body(theme, response, app).map_err(ThemeFetchError::Failed)?;
+
+  Ok((body, resolved_url))
+}
+
+// NOTE: Uses `reqwest::blocking::Client` internally. Must always be called from a
 // blocking context - either a synchronous `#[tauri::command]` or inside
 // `tokio::task::spawn_blocking`. Do not call from an async context directly,
 // as it will block the async executor.
-pub fn download_themes(themes: &[ProvidedThemeInfo]) -> Result<String, String> {
+//
+// A theme that fails is recorded and the rest are still downloaded; only when
+// every theme fails (or the primary directory is unusable) is this an error.
+pub fn download_themes(
+  themes: &[ProvidedThemeInfo],
+  dirs: &[PathBuf],
+  app: Option<&tauri::AppHandle>,
+) -> Result<Vec<ThemeDownloadResult>, String> {
   if themes.is_empty() {
-    return Ok("No themes enabled; skipping download".to_string());
+    return Ok(Vec::new());
   }
 
-  let dir = theme_dir()?;
+  let primary = dirs
+    .first()
+    .ok_or_else(|| "No theme directory to download into".to_string())?;
 
-  fs::create_dir_all(&dir)
-    .map_err(|err| format!("Failed to create theme directory {}: {err}", dir.display()))?;
+  // Only the primary directory is required; extra client directories report
+  // their failures as warnings on the results instead of aborting the download.
+  let mut targets: Vec<ThemeTarget> = Vec::new();
 
-  let mut downloaded = Vec::new();
+  for dir in dirs {
+    let created = fs::create_dir_all(dir)
+      .map_err(|err| format!("Failed to create theme directory {}: {err}", dir.display()));
 
-  for theme in themes {
-    let file_name = theme_file_name(theme)?;
-    let destination = dir.join(&file_name);
+    if dir == primary {
+      created.clone()?;
+    }
 
-    let response =
-      get(&theme.url).map_err(|err| format!("Failed to download {}: {err}", theme.url))?;
+    targets.push(ThemeTarget {
+      dir: dir.clone(),
+      manifest: read_theme_manifest(dir),
+      error: created.err(),
+    });
+  }
 
-    if !response.status().is_success() {
-      return Err(format!(
-        "Theme request failed for {} with status {}",
-        theme.url,
-        response.status()
-      ));
+  let client = theme_http_client()?;
+  let fetched = fetch_themes(&client, themes, app);
+  let mut results = Vec::new();
+
+  for (theme, fetched) in themes.iter().zip(fetched) {
+    let mut result = ThemeDownloadResult {
+      name: theme.name.clone(),
+      url: theme.url.clone(),
+      status: ThemeDownloadStatus::Failed,
+      bytes: None,
+      error: None,
+      warnings: Vec::new(),
+    };
+
+    let (body, resolved_url) = match fetched {
+      Ok(fetched) => fetched,
+      Err(ThemeFetchError::Skipped(err)) => {
+        result.status = ThemeDownloadStatus::Skipped;
+        result.error = Some(err);
+        results.push(result);
+        continue;
+      }
+      Err(ThemeFetchError::RateLimited(err)) => {
+        result.error = Some(err);
+        results.push(result);
+        continue;
+      }
+      Err(ThemeFetchError::Failed(err)) => {
+        log::warn!("[themes] {err}");
+        result.error = Some(err);
+        results.push(result);
+        continue;
+      }
+    };
+
+    if resolved_url != theme.url {
+      log::info!("[themes] {} resolved to {resolved_url}", theme.url);
     }
 
-    let content = response
-      .text()
-      .map_err(|err| format!("Failed to read response body for {}: {err}", theme.url))?;
+    let mut primary_error = None;
+
+    for target in targets.iter_mut().filter(|target| target.error.is_none()) {
+      match write_theme(theme, &body, &target.dir) {
+        Ok(files) => {
+          target.manifest.insert(theme.url.clone(), files);
+        }
+        Err(err) if &target.dir == primary => primary_error = Some(err),
+        Err(err) => target.error = Some(err),
+      }
+    }
 
-    fs::write(&destination, content)
-      .map_err(|err| format!("Failed to write theme {}: {}", destination.display(), err))?;
-    downloaded.push(theme.name.clone());
+    match primary_error {
+      Some(err) => {
+        log::warn!("[themes] {err}");
+        result.error = Some(err);
+      }
+      None => {
+        result.status = ThemeDownloadStatus::Downloaded;
+        result.bytes = Some(body.len() as u64);
+        result.warnings = targets
+          .iter()
+          .filter_map(|target| target.error.as_ref().map(|err| extra_dir_warning(&target.dir, err)))
+          .collect();
+      }
+    }
+
+    results.push(result);
   }
 
-  Ok(format!(
-    "Downloaded {} theme(s): {}",
-    downloaded.len(),
-    downloaded.join(", ")
-  ))
+  for target in &mut targets {
+    if target.error.is_none() {
+      if let Err(err) = write_theme_manifest(&target.dir, &target.manifest) {
+        if &target.dir == primary {
+          return Err(err);
+        }
+
+        let warning = extra_dir_warning(&target.dir, &err);
+        for result in results
+          .iter_mut()
+          .filter(|result| result.status == ThemeDownloadStatus::Downloaded)
+        {
+          result.warnings.push(warning.clone());
+        }
+        target.error = Some(err);
+      }
+    }
+
+    if let Some(err) = &target.error {
+      log::warn!("[themes] Failed to write themes to {}: {err}", target.dir.display());
+    }
+  }
+
+  if results
+    .iter()
+    .all(|result| result.status != ThemeDownloadStatus::Downloaded)
+  {
+    let err = results
+      .iter()
+      .find_map(|result| result.error.clone())
+      .unwrap_or_else(|| "No themes were downloaded".to_string());
+    return Err(err);
+  }
+
+  log::info!("[themes] {}", summarize_theme_downloads(&results));
+
+  Ok(results)
+}
+
+fn extra_dir_warning(dir: &Path, err: &str) -> String {
+  format!("Could not update themes in {}: {err}", dir.display())
+}
+
+/// Fetches every theme in parallel, as many at a time as
+/// `max_concurrent_network` allows, returning the outcomes in `themes` order.
+fn fetch_themes(
+  client: &Client,
+  themes: &[ProvidedThemeInfo],
+  app: Option<&tauri::AppHandle>,
+) -> Vec<Result<(Vec<u8>, String), ThemeFetchError>> {
+  // Further requests would only extend the limit, so stop once it is hit.
+  let rate_limited: Mutex<Option<String>> = Mutex::new(None);
+
+  std::thread::scope(|scope| {
+    let handles: Vec<_> = themes
+      .iter()
+      .map(|theme| {
+        let rate_limited = &rate_limited;
+        scope.spawn(move || {
+          let _permit = network::acquire();
+
+          if let Some(err) = rate_limited.lock().unwrap_or_else(|err| err.into_inner()).clone() {
+            return Err(ThemeFetchError::Skipped(err));
+          }
+
+          let fetched = fetch_theme(client, theme, app);
+          if let Err(ThemeFetchError::RateLimited(err)) = &fetched {
+            *rate_limited.lock().unwrap_or_else(|err| err.into_inner()) = Some(err.clone());
+          }
+
+          fetched
+        })
+      })
+      .collect();
+
+    handles
+      .into_iter()
+      .map(|handle| {
+        handle
+          .join()
+          .unwrap_or_else(|_| Err(ThemeFetchError::Failed("Theme download panicked".to_string())))
+      })
+      .collect()
+  })
+}
+
+struct ThemeTarget {
+  dir: PathBuf,
+  manifest: ThemeManifest,
+  error: Option<String>,
+}
+
+fn write_theme(theme: &ProvidedThemeInfo, body: &[u8], dir: &Path) -> Result<Vec<String>, String> {
+  if is_archive_url(&theme.url) {
+    return extract_theme_archive(body, dir, &theme.url);
+  }
+
+  let file_name = theme_file_name(theme)?;
+  let destination = dir.join(&file_name);
+
+  write_file_atomically(&destination, body)?;
+
+  Ok(vec![file_name])
+}
+
+// Reads `@key value` lines from the first header comment. Covers both the
+// `/* ==UserStyle== ... ==/UserStyle== */` block and BetterDiscord-style
+// `/** @name ... */` headers.
+fn parse_theme_metadata(css: &str) -> ThemeMetadata {
+  let mut metadata = ThemeMetadata::default();
+
+  let Some(start) = css.find("/*") else {
+    return metadata;
+  };
+  let header = &css[start + 2..];
+  let header = header.find("*/").map_or(header, |end| &header[..end]);
+
+  let mut lines = header.lines();
+
+  while let Some(line) = lines.next() {
+    let line = line.trim().trim_start_matches('*').trim();
+
+    let Some(rest) = line.strip_prefix('@') else {
+      continue;
+    };
+
+    let (key, value) = match rest.split_once(char::is_whitespace) {
+      Some((key, value)) => (key, value.trim()),
+      None => continue,
+    };
+
+    if value.is_empty() {
+      continue;
+    }
+
+    if key == "var" {
+      let value = join_var_continuation(value, &mut lines);
+
+      if let Some(variable) = parse_theme_variable(&value) {
+        metadata.variables.push(variable);
+      }
+
+      continue;
+    }
+
+    let slot = match key {
+      "name" => &mut metadata.name,
+      "author" => &mut metadata.author,
+      "description" => &mut metadata.description,
+      "preview" => &mut metadata.preview_url,
+      "version" => &mut metadata.version,
+      _ => continue,
+    };
+
+    if slot.is_none() {
+      *slot = Some(value.to_string());
+    }
+  }
+
+  metadata
+}
+
+// Select options may span several lines (`["a", \n "b"]` or `{ ... }`), so
+// keep consuming lines until the opening bracket is closed.
+fn join_var_continuation<'a>(value: &str, lines: &mut impl Iterator<Item = &'a str>) -> String {
+  let mut joined = value.to_string();
+
+  let closer = match joined.find(['[', '{']) {
+    Some(index) if joined[index..].starts_with('[') => ']',
+    Some(_) => '}',
+    None => return joined,
+  };
+
+  while !joined.contains(closer) {
+    let Some(next) = lines.next() else {
+      break;
+    };
+    joined.push(' ');
+    joined.push_str(next.trim());
+  }
+
+  joined
+}
+
+// Splits off one bare word or a single/double quoted string.
+fn take_token(input: &str) -> Option<(String, &str)> {
+  let input = input.trim_start();
+  let quote = input.chars().next()?;
+
+  if quote == '"' || quote == '\'' {
+    let body = &input[1..];
+    let end = body.find(quote)?;
+    return Some((body[..end].to_string(), &body[end + 1..]));
+  }
+
+  let end = input.find(char::is_whitespace).unwrap_or(input.len());
+  Some((input[..end].to_string(), &input[end..]))
+}
+
+fn quoted_strings(input: &str) -> Vec<String> {
+  let mut values = Vec::new();
+  let mut rest = input;
+
+  while let Some(start) = rest.find(['"', '\'']) {
+    match take_token(&rest[start..]) {
+      Some((value, remaining)) => {
+        values.push(value);
+        rest = remaining;
+      }
+      None => break,
+    }
+  }
+
+  values
+}
+
+// Array entries are `"value:Label"` with a trailing `*` marking the default;
+// object entries are `"Label": "value"` pairs.
+fn parse_select_options(input: &str) -> (Vec<ThemeVariableOption>, Option<String>) {
+  let trimmed = input.trim();
+  let mut options = Vec::new();
+  let mut default = None;
+
+  if trimmed.starts_with('{') {
+    for pair in quoted_strings(trimmed).chunks(2) {
+      let [label, value] = pair else {
+        continue;
+      };
+      let (label, is_default) = match label.strip_suffix('*') {
+        Some(label) => (label.to_string(), true),
+        None => (label.clone(), false),
+      };
+
+      if is_default {
+        default = Some(value.clone());
+      }
+      options.push(ThemeVariableOption {
+        value: value.clone(),
+        label,
+      });
+    }
+  } else {
+    for entry in quoted_strings(trimmed) {
+      let (entry, is_default) = match entry.strip_suffix('*') {
+        Some(entry) => (entry.to_string(), true),
+        None => (entry, false),
+      };
+      let (value, label) = match entry.split_once(':') {
+        Some((value, label)) => (value.trim().to_string(), label.trim().to_string()),
+        None => (entry.clone(), entry),
+      };
+
+      if is_default {
+        default = Some(value.clone());
+      }
+      options.push(ThemeVariableOption { value, label });
+    }
+  }
+
+  if default.is_none() {
+    default = options.first().map(|option| option.value.clone());
+  }
+
+  (options, default)
+}
+
+// `@var <type> <name> <label> <default>`; only the types the UI can render
+// are kept, anything else (range, number, image, ...) is skipped.
+fn parse_theme_variable(value: &str) -> Option<ThemeVariable> {
+  let (kind, rest) = take_token(value)?;
+  let kind = match kind.as_str() {
+    "color" | "checkbox" | "text" => kind,
+    "select" | "dropdown" => "select".to_string(),
+    _ => return None,
+  };
+
+  let (name, rest) = take_token(rest)?;
+  let (label, rest) = take_token(rest).unwrap_or_else(|| (name.clone(), ""));
+  let rest = rest.trim();
+
+  let (options, default) = if kind == "select" {
+    parse_select_options(rest)
+  } else {
+    // Colors such as `rgba(0, 0, 0, 0.5)` contain spaces, so an unquoted
+    // default is the whole remainder of the line.
+    let default = if rest.starts_with(['"', '\'']) {
+      take_token(rest).map(|(token, _)| token)
+    } else {
+      Some(rest.to_string())
+    }
+    .filter(|value| !value.is_empty());
+    (Vec::new(), default)
+  };
+
+  Some(ThemeVariable {
+    kind,
+    name,
+    label,
+    default,
+    options,
+  })
+}
+
+/// Maps raw file URLs to the page that shows the file on its host, e.g.
+/// `raw.githubusercontent.com/o/r/main/x.css` to `github.com/o/r/blob/main/x.css`.
+/// Other URLs are returned unchanged.
+fn browsable_theme_url(url: &Url) -> Url {
+  let segments: Vec<&str> = url
+    .path_segments()
+    .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+    .unwrap_or_default();
+
+  let browsable = match (url.host_str(), segments.as_slice()) {
+    (Some("raw.githubusercontent.com"), [owner, repo, rest @ ..]) if !rest.is_empty() => {
+      // Newer raw links spell the branch as `refs/heads/<branch>`.
+      let rest = match rest {
+        ["refs", "heads" | "tags", rest @ ..] => rest,
+        rest => rest,
+      };
+      format!("https://github.com/{owner}/{repo}/blob/{}", rest.join("/"))
+    }
+    (Some("github.com"), [owner, repo, "raw", rest @ ..]) if !rest.is_empty() => {
+      format!("https://github.com/{owner}/{repo}/blob/{}", rest.join("/"))
+    }
+    (Some("gist.githubusercontent.com"), [user, gist, ..]) => {
+      format!("https://gist.github.com/{user}/{gist}")
+    }
+    (Some("cdn.jsdelivr.net"), ["gh", owner, repo_ref, rest @ ..]) if !rest.is_empty() => {
+      let (repo, reference) = repo_ref.split_once('@').unwrap_or((repo_ref, "HEAD"));
+      format!("https://github.com/{owner}/{repo}/blob/{reference}/{}", rest.join("/"))
+    }
+    _ => return url.clone(),
+  };
+
+  Url::parse(&browsable).unwrap_or_else(|_| url.clone())
+}
+
+/// Opens a theme's source in the browser so it can be reviewed before it is
+/// enabled.
+#[tauri::command]
+pub fn open_theme_source(app: tauri::AppHandle, id: String) -> Result<(), InstallerError> {
+  let options = options::read_user_options().map_err(InstallerError::filesystem)?;
+  let theme = options::find_theme(&options, &id)
+    .ok_or_else(|| InstallerError::not_found(format!("No theme with id {id}")))?;
+
+  let url = Url::parse(&theme.url)
+    .ok()
+    .filter(|url| matches!(url.scheme(), "https" | "http"))
+    .ok_or_else(|| InstallerError::invalid_input(format!("{} is not a web URL", theme.url)))?;
+  let url = browsable_theme_url(&url);
+
+  log::info!("[themes] Opening source of {} at {url}", theme.name);
+
+  app
+    .opener()
+    .open_url(url.as_str(), None::<&str>)
+    .map_err(|err| InstallerError::Other {
+      message: format!("Failed to open {url}: {err}"),
+    })
+}
+
+#[tauri::command]
+pub fn fetch_theme_metadata(url: String) -> Result<ThemeMetadata, InstallerError> {
+  let url = url.trim().to_string();
+
+  if url.is_empty() {
+    return Err(InstallerError::invalid_input("Theme URL is empty"));
+  }
+
+  let theme = ProvidedThemeInfo {
+    id: "metadata".to_string(),
+    name: url.clone(),
+    url: url.clone(),
+    auth_header: None,
+  };
+
+  let response = theme_http_client()
+    .map_err(InstallerError::network)?
+    .get(&url)
+    .header(RANGE, format!("bytes=0-{}", THEME_HEADER_BYTES - 1))
+    .send()
+    .map_err(|err| InstallerError::network(format!("Failed to fetch {url}: {err}")))?;
+
+  if let Some(err) = rate_limit_error(&url, &response) {
+    return Err(InstallerError::network(err));
+  }
+
+  if !response.status().is_success() {
+    return Err(InstallerError::network(format!(
+      "Theme request failed for {url} with status {}",
+      response.status()
+    )));
+  }
+
+  validate_theme_response(&theme, &response).map_err(InstallerError::invalid_input)?;
+
+  // Servers that ignore the Range header send the full file, so cap the read.
+  let mut header = Vec::new();
+  response
+    .take(THEME_HEADER_BYTES)
+    .read_to_end(&mut header)
+    .map_err(|err| InstallerError::network(format!("Failed to read response body for {url}: {err}")))?;
+
+  Ok(parse_theme_metadata(&String::from_utf8_lossy(&header)))
 }
@@ -1,11 +1,16 @@
-use std::{fs, io::{self, Write}, path::Path, path::PathBuf};
+use std::{fs, io::{self, Write}, path::Path, path::PathBuf, sync::Mutex};
 
-use chrono::Local;
+use chrono::{DateTime, Local};
 use log::LevelFilter;
+use serde::Serialize;
 use tauri::{Builder, Runtime};
 use tauri_plugin_log::{Builder as LogBuilder, Target, TargetKind};
 
-use crate::config::app_config_dir;
+use crate::{
+  config::app_config_dir,
+  error::InstallerError,
+  options,
+};
 
 pub fn installer_logs_dir() -> io::Result<PathBuf> {
   let log_dir = app_config_dir()?.join("logs");
@@ -14,6 +19,232 @@ pub fn installer_logs_dir() -> io::Result<PathBuf> {
   Ok(log_dir)
 }
 
+/// The run log output is currently mirrored into.
+struct ActiveRunLog {
+  path: PathBuf,
+  file: fs::File,
+  /// Prefix of the lines logged by the flow that owns this run, e.g.
+  /// `[patch-flow]`.
+  tag: String,
+}
+
+static ACTIVE_RUN_LOG: Mutex<Option<ActiveRunLog>> = Mutex::new(None);
+
+/// Keeps the per-run log file attached to the logger until dropped.
+pub struct RunLogGuard {
+  path: PathBuf,
+}
+
+impl RunLogGuard {
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+}
+
+impl Drop for RunLogGuard {
+  fn drop(&mut self) {
+    if let Ok(mut active) = ACTIVE_RUN_LOG.lock() {
+      if let Some(mut active) = active.take() {
+        let _ = active.file.flush();
+      }
+    }
+  }
+}
+
+/// Starts mirroring the lines tagged `[<flow>]` into `runs/<id>.log` and
+/// prunes older run logs down to `max_count`. Other flows may log at the same
+/// time, so only the owning flow's lines are copied.
+pub fn begin_run_log(id: &str, flow: &str, max_count: u32) -> Option<RunLogGuard> {
+  let runs_dir = match run_logs_dir() {
+    Ok(dir) => dir,
+    Err(err) => {
+      log::warn!("[logging] {err}");
+      return None;
+    }
+  };
+
+  let path = runs_dir.join(format!("{id}.log"));
+  let file = fs::create_dir_all(&runs_dir).and_then(|_| {
+    fs::OpenOptions::new().create(true).append(true).open(&path)
+  });
+
+  let file = match file {
+    Ok(file) => file,
+    Err(err) => {
+      log::warn!("[logging] Failed to create run log {}: {err}", path.display());
+      return None;
+    }
+  };
+
+  prune_run_logs(&runs_dir, max_count.max(1));
+
+  if let Ok(mut active) = ACTIVE_RUN_LOG.lock() {
+    *active = Some(ActiveRunLog {
+      path: path.clone(),
+      file,
+      tag: format!("[{flow}]"),
+    });
+  }
+
+  Some(RunLogGuard { path })
+}
+
+fn prune_run_logs(dir: &Path, max_count: u32) {
+  let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+    Ok(rd) => rd
+      .filter_map(|e| e.ok())
+      .map(|e| e.path())
+      .filter(|p| p.extension().map_or(false, |ext| ext == "log"))
+      .collect(),
+    Err(_) => return,
+  };
+
+  entries.sort();
+
+  if entries.len() > max_count as usize {
+    for old in &entries[..entries.len() - max_count as usize] {
+      let _ = fs::remove_file(old);
+    }
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLogInfo {
+  pub name: String,
+  pub path: String,
+  pub created_at: Option<String>,
+  pub size_bytes: u64,
+}
+
+fn run_logs_dir() -> Result<PathBuf, String> {
+  installer_logs_dir()
+    .map(|dir| dir.join("runs"))
+    .map_err(|err| format!("Failed to resolve run log directory: {err}"))
+}
+
+/// Run log files directly inside `root`, resolved through symlinks and kept
+/// only when they still point inside it.
+fn confined_run_logs(root: &Path) -> Result<Vec<PathBuf>, String> {
+  if !root.exists() {
+    return Ok(Vec::new());
+  }
+
+  let canonical_root = dunce::canonicalize(root)
+    .map_err(|err| format!("Failed to resolve run log directory: {err}"))?;
+
+  let entries = fs::read_dir(root)
+    .map_err(|err| format!("Failed to read run log directory: {err}"))?
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.path())
+    .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+    .filter_map(|path| {
+      let canonical = dunce::canonicalize(&path).ok()?;
+
+      if canonical.starts_with(&canonical_root) && canonical.is_file() {
+        Some(canonical)
+      } else {
+        log::warn!("[logging] Ignoring run log outside {}: {}", root.display(), path.display());
+        None
+      }
+    })
+    .collect();
+
+  Ok(entries)
+}
+
+#[tauri::command]
+pub fn list_run_logs() -> Result<Vec<RunLogInfo>, InstallerError> {
+  let mut logs: Vec<RunLogInfo> = run_logs_dir()
+    .and_then(|dir| confined_run_logs(&dir))
+    .map_err(InstallerError::filesystem)?
+    .into_iter()
+    .map(|path| {
+      let metadata = fs::metadata(&path).ok();
+
+      RunLogInfo {
+        name: path
+          .file_name()
+          .map(|name| name.to_string_lossy().into_owned())
+          .unwrap_or_default(),
+        path: path.to_string_lossy().into_owned(),
+        created_at: metadata
+          .as_ref()
+          .and_then(|meta| meta.created().or_else(|_| meta.modified()).ok())
+          .map(|time| DateTime::<Local>::from(time).to_rfc3339()),
+        size_bytes: metadata.map_or(0, |meta| meta.len()),
+      }
+    })
+    .collect();
+
+  // Names are run ids, which are timestamps, so this is newest first.
+  logs.sort_by(|a, b| b.name.cmp(&a.name));
+
+  Ok(logs)
+}
+
+/// Deletes every per-run log and returns how many were removed.
+#[tauri::command]
+pub fn clear_run_logs() -> Result<usize, InstallerError> {
+  let mut removed = 0;
+  let logs = run_logs_dir()
+    .and_then(|dir| confined_run_logs(&dir))
+    .map_err(InstallerError::filesystem)?;
+
+  // The log of a flow that is still running keeps being written to.
+  let active = ACTIVE_RUN_LOG
+    .lock()
+    .ok()
+    .and_then(|active| active.as_ref().map(|active| active.path.clone()))
+    .and_then(|path| dunce::canonicalize(path).ok());
+
+  for path in logs {
+    if active.as_ref() == Some(&path) {
+      continue;
+    }
+
+    match fs::remove_file(&path) {
+      Ok(()) => removed += 1,
+      Err(err) => log::warn!("[logging] Failed to remove run log {}: {err}", path.display()),
+    }
+  }
+
+  Ok(removed)
+}
+
+fn mirror_to_run_log(record: &log::Record) {
+  let Ok(mut active) = ACTIVE_RUN_LOG.lock() else {
+    return;
+  };
+  let Some(active) = active.as_mut() else {
+    return;
+  };
+
+  let message = record.args().to_string();
+  if !message.starts_with(&active.tag) {
+    return;
+  }
+
+  let _ = writeln!(
+    active.file,
+    "[{} {:<5} {}] {}",
+    Local::now().format("%Y-%m-%dT%H:%M:%S"),
+    record.level(),
+    record.target(),
+    message,
+  );
+}
+
+fn format_record(out: fern::FormatCallback, message: &std::fmt::Arguments, record: &log::Record) {
+  out.finish(format_args!(
+    "[{} {:<5} {}] {}",
+    Local::now().format("%Y-%m-%dT%H:%M:%S"),
+    record.level(),
+    record.target(),
+    message,
+  ))
+}
+
 struct LazyFileWriter {
   log_dir: PathBuf,
   file: Option<fs::File>,
@@ -82,30 +313,26 @@ fn rotate_latest_log(log_dir: &Path) {
 }
 
 pub fn with_tauri_logger<R: Runtime>(builder: Builder<R>) -> Builder<R> {
-  let log_dir: Option<PathBuf> = dirs::config_dir()
-    .map(|base| base.join("vencord-installer-gui").join("logs"))
-    .and_then(|dir| fs::create_dir_all(&dir).ok().map(|_| dir));
+  let log_dir: Option<PathBuf> = installer_logs_dir().ok();
 
-  let mut targets = vec![Target::new(TargetKind::Stdout)];
+  let mut targets = Vec::new();
+
+  if options::log_to_stdout_enabled() {
+    targets.push(Target::new(TargetKind::Stdout));
+  }
 
   if let Some(ref path) = log_dir {
     let writer: Box<dyn Write + Send> = Box::new(LazyFileWriter::new(path.clone()));
 
-    let dispatch = fern::Dispatch::new()
-      .format(|out, message, record| {
-        out.finish(format_args!(
-          "[{} {:<5} {}] {}",
-          Local::now().format("%Y-%m-%dT%H:%M:%S"),
-          record.level(),
-          record.target(),
-          message,
-        ))
-      })
-      .chain(writer);
+    let dispatch = fern::Dispatch::new().format(format_record).chain(writer);
 
     targets.push(Target::new(TargetKind::Dispatch(dispatch)));
   }
 
+  let run_dispatch = fern::Dispatch::new().chain(fern::Output::call(mirror_to_run_log));
+
+  targets.push(Target::new(TargetKind::Dispatch(run_dispatch)));
+
   builder.plugin(
     LogBuilder::default()
       .level(LevelFilter::Info)
@@ -3,14 +3,21 @@
 
 mod command_utils;
 mod config;
+mod console;
 mod dependencies;
 mod discord;
+mod environment;
+mod error;
 mod flows;
 mod logging;
+mod maintenance;
+mod network;
 mod options;
 mod run_log;
 
 fn main() {
+  console::attach_if_requested();
+
   #[cfg(target_os = "linux")]
   std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
 
@@ -19,23 +26,81 @@ fn main() {
       .plugin(tauri_plugin_dialog::init())
       .plugin(tauri_plugin_opener::init())
       .plugin(tauri_plugin_updater::Builder::new().build())
-      .setup(|_app| {
+      .manage(flows::pipeline::ReopenConfirmation::default())
+      .manage(flows::interrupted::InterruptedFlowState::default())
+      .setup(|app| {
+        match config::config_dir_state() {
+          Ok(state) => {
+            if let Some(unwritable) = &state.unwritable {
+              log::warn!(
+                "[config] {} is not writable; using {} instead. If that is a cache or temporary folder, settings, backups and logs there may be removed by the system",
+                unwritable.display(),
+                state.path.display()
+              );
+            }
+          }
+          Err(err) => log::error!("[config] {err}"),
+        }
+
         logging::installer_logs_dir()?;
+        flows::interrupted::report_interrupted_flow(app.handle());
         Ok(())
       })
       .invoke_handler(tauri::generate_handler![
         flows::backup::backup_vencord_install,
         flows::backup::delete_backups,
+        flows::backup::backups_disk_usage,
+        flows::backup::restore_discord_settings,
         flows::backup::list_backups,
+        flows::backup::next_backup_name,
+        flows::backup::inspect_backup,
+        flows::backup::cancel_backup,
         dependencies::install_dependency,
         dependencies::list_dependencies,
         flows::discord_clients::list_discord_processes,
+        flows::discord_clients::is_discord_running,
+        flows::discord_clients::get_discord_status,
+        flows::discord_clients::were_clients_closed_by_us,
+        flows::discord_clients::restart_client,
         flows::pipeline::run_patch_flow,
+        flows::pipeline::resume_patch_flow,
+        flows::pipeline::confirm_reopen,
+        flows::pipeline::run_themes_flow,
+        flows::pipeline::run_backup_only_flow,
+        flows::pipeline::factory_reset_vencord,
         flows::pipeline::run_dev_test,
+        flows::pipeline::preview_injection,
+        flows::pipeline::check_injectable,
+        flows::interrupted::get_interrupted_flow,
+        flows::interrupted::clear_interrupted_flow,
+        flows::interrupted::rollback_interrupted_flow,
+        flows::pipeline::inject_all_clients,
+        flows::repo::delete_repo_clone,
+        flows::repo::current_build_fingerprint,
+        flows::repo::repo_status,
+        flows::sources::test_sources,
+        flows::sources::validate_repo_url,
+        flows::sources::validate_theme_url,
+        flows::themes::fetch_theme_metadata,
+        flows::themes::open_theme_source,
+        flows::themes::open_vencord_settings_dir,
+        flows::themes::delete_installed_theme,
+        maintenance::run_maintenance,
+        logging::list_run_logs,
+        logging::clear_run_logs,
         run_log::list_runs,
         run_log::open_runs_dir,
         discord::get_discord_installs,
+        environment::run_environment_check,
+        environment::get_paths,
         options::get_user_options,
+        options::list_provided_repositories,
+        options::list_provided_themes,
+        options::set_provided_repository_enabled,
+        options::set_provided_theme_enabled,
+        options::set_close_discord_on_backup,
+        options::move_user_repository,
+        options::remove_user_repository,
         options::update_user_options,
         options::update_selected_discord_clients,
       ]),
@@ -0,0 +1,130 @@
+use serde::Serialize;
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  time::{Duration, SystemTime},
+};
+
+use crate::{
+  config::app_config_dir,
+  error::InstallerError,
+  flows::{backup, themes},
+  logging,
+};
+
+// Per-run logs younger than this may belong to a flow that is still running
+// and has not written its run record yet.
+const ORPHANED_RUN_LOG_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+// A younger `.partial` theme file may still be being written by a download.
+const PARTIAL_THEME_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceResult {
+  pub removed_paths: Vec<String>,
+  pub freed_bytes: u64,
+}
+
+fn entries(dir: &Path) -> Vec<PathBuf> {
+  fs::read_dir(dir)
+    .map(|rd| rd.filter_map(Result::ok).map(|entry| entry.path()).collect())
+    .unwrap_or_default()
+}
+
+fn is_older_than(path: &Path, age: Duration) -> bool {
+  fs::metadata(path)
+    .and_then(|meta| meta.modified())
+    .ok()
+    .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+    .map_or(false, |elapsed| elapsed >= age)
+}
+
+/// Half-written theme files from downloads the app did not finish.
+fn stale_partial_themes() -> Vec<PathBuf> {
+  themes::partial_theme_files()
+    .into_iter()
+    .filter(|path| is_older_than(path, PARTIAL_THEME_MIN_AGE))
+    .collect()
+}
+
+/// Per-run logs whose run never produced a run record (e.g. the app crashed).
+fn orphaned_run_logs(app_dir: &Path) -> Vec<PathBuf> {
+  let Ok(logs_dir) = logging::installer_logs_dir() else {
+    return Vec::new();
+  };
+  let records_dir = app_dir.join("runs");
+
+  entries(&logs_dir.join("runs"))
+    .into_iter()
+    .filter(|path| path.extension().map_or(false, |ext| ext == "log"))
+    .filter(|path| {
+      path
+        .file_stem()
+        .map_or(false, |id| !records_dir.join(id).with_extension("json").exists())
+    })
+    .filter(|path| is_older_than(path, ORPHANED_RUN_LOG_MIN_AGE))
+    .collect()
+}
+
+/// Removes `path` if it is inside `root`, the directory it was found in.
+fn remove_artifact(root: &Path, path: &Path, result: &mut MaintenanceResult) {
+  let inside_root = dunce::canonicalize(root)
+    .and_then(|root| dunce::canonicalize(path).map(|canonical| canonical.starts_with(root)))
+    .unwrap_or(false);
+
+  if !inside_root {
+    log::warn!("[maintenance] Skipping {} outside {}", path.display(), root.display());
+    return;
+  }
+
+  let (size, removed) = if path.is_dir() {
+    (
+      backup::dir_size(path).unwrap_or(0),
+      backup::remove_dir_tree(path),
+    )
+  } else {
+    (
+      fs::metadata(path).map(|meta| meta.len()).unwrap_or(0),
+      fs::remove_file(path).map_err(|err| format!("Failed to delete {}: {err}", path.display())),
+    )
+  };
+
+  match removed {
+    Ok(()) => {
+      result.freed_bytes += size;
+      result.removed_paths.push(path.to_string_lossy().into_owned());
+    }
+    Err(err) => log::warn!("[maintenance] {err}"),
+  }
+}
+
+#[tauri::command]
+pub fn run_maintenance() -> Result<MaintenanceResult, InstallerError> {
+  let app_dir = app_config_dir()
+    .and_then(dunce::canonicalize)
+    .map_err(|err| InstallerError::filesystem(format!("Failed to get config directory: {err}")))?;
+
+  let mut result = MaintenanceResult::default();
+
+  for path in backup::incomplete_backups()
+    .into_iter()
+    .chain(orphaned_run_logs(&app_dir))
+  {
+    // Backups and run logs live inside the installer's own directory.
+    remove_artifact(&app_dir, &path, &mut result);
+  }
+
+  for path in stale_partial_themes() {
+    if let Some(dir) = path.parent() {
+      remove_artifact(dir, &path, &mut result);
+    }
+  }
+
+  log::info!(
+    "[maintenance] Removed {} stale artifact(s), freed {} bytes",
+    result.removed_paths.len(),
+    result.freed_bytes
+  );
+
+  Ok(result)
+}
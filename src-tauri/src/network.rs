@@ -0,0 +1,45 @@
+use std::sync::{Condvar, Mutex};
+
+pub const DEFAULT_MAX_CONCURRENT_NETWORK: usize = 4;
+
+struct LimiterState {
+  limit: usize,
+  in_use: usize,
+}
+
+// Shared by plugin clones and theme downloads so their combined number of
+// open connections stays under `max_concurrent_network`.
+static LIMITER: Mutex<LimiterState> = Mutex::new(LimiterState {
+  limit: DEFAULT_MAX_CONCURRENT_NETWORK,
+  in_use: 0,
+});
+static RELEASED: Condvar = Condvar::new();
+
+/// Applies the `max_concurrent_network` option; 0 means one at a time.
+pub fn set_limit(max_concurrent: usize) {
+  let mut state = LIMITER.lock().unwrap_or_else(|err| err.into_inner());
+  state.limit = max_concurrent.max(1);
+  RELEASED.notify_all();
+}
+
+/// Held for the duration of one network transfer.
+pub struct NetworkPermit(());
+
+impl Drop for NetworkPermit {
+  fn drop(&mut self) {
+    let mut state = LIMITER.lock().unwrap_or_else(|err| err.into_inner());
+    state.in_use = state.in_use.saturating_sub(1);
+    RELEASED.notify_one();
+  }
+}
+
+/// Blocks until a transfer slot is free.
+pub fn acquire() -> NetworkPermit {
+  let state = LIMITER.lock().unwrap_or_else(|err| err.into_inner());
+  let mut state = RELEASED
+    .wait_while(state, |state| state.in_use >= state.limit)
+    .unwrap_or_else(|err| err.into_inner());
+  state.in_use += 1;
+
+  NetworkPermit(())
+}
@@ -1,9 +1,23 @@
+use chrono::{
+  format::{Item, StrftimeItems},
+  Local,
+};
 use log::warn;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::PathBuf};
-
-use crate::config::app_config_dir;
+use std::{
+  collections::HashMap,
+  fmt, fs,
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+use crate::{
+  config::{app_config_dir, is_writable_dir},
+  discord,
+  error::InstallerError,
+  network,
+};
 
 fn default_true() -> bool {
   true
@@ -29,6 +43,14 @@ fn default_max_run_log_count() -> Option<u32> {
   Some(50)
 }
 
+fn default_max_concurrent_network() -> usize {
+  network::DEFAULT_MAX_CONCURRENT_NETWORK
+}
+
+fn default_backup_exclude() -> Vec<String> {
+  vec!["node_modules".to_string()]
+}
+
 fn default_selected_discord_clients() -> Vec<String> {
   vec!["stable".to_string()]
 }
@@ -44,19 +66,56 @@ fn legacy_repo_base_dir() -> String {
 
 const DEFAULT_VENCORD_REPO_URL: &str = "https://github.com/Vendicated/Vencord.git";
 
+pub const CURRENT_OPTIONS_SCHEMA_VERSION: u32 = 2;
+
+type OptionsMigration = fn(&mut serde_json::Map<String, serde_json::Value>);
+
+// Index `n` upgrades a schema `n` file to schema `n + 1`. Files written before
+// versioning existed have no `schemaVersion` and are treated as schema 0.
+const OPTIONS_MIGRATIONS: &[OptionsMigration] = &[migrate_v0_to_v1, migrate_v1_to_v2];
+
+fn migrate_v0_to_v1(options: &mut serde_json::Map<String, serde_json::Value>) {
+  let legacy_default_dir = legacy_repo_base_dir();
+
+  if options.get("vencordRepoDir").and_then(|value| value.as_str())
+    == Some(legacy_default_dir.as_str())
+  {
+    options.insert(
+      "vencordRepoDir".to_string(),
+      serde_json::Value::String(default_repo_base_dir()),
+    );
+  }
+}
+
+// User themes used to be plain URL strings; they are now objects so an entry
+// can carry an `authHeader`.
+fn migrate_v1_to_v2(options: &mut serde_json::Map<String, serde_json::Value>) {
+  if let Some(serde_json::Value::Array(themes)) = options.get_mut("userThemes") {
+    for theme in themes.iter_mut() {
+      if let serde_json::Value::String(url) = theme {
+        *theme = serde_json::json!({ "url": url });
+      }
+    }
+  }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct ProvidedRepository {
+pub struct ProvidedRepository {
   id: String,
   name: String,
   url: String,
   description: String,
   default_enabled: bool,
+  /// Ids of other provided repositories, or URLs of user repositories, that
+  /// break the build when enabled together with this one.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  conflicts_with: Vec<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct ProvidedTheme {
+pub struct ProvidedTheme {
   id: String,
   name: String,
   url: String,
@@ -110,11 +169,101 @@ pub struct ProvidedThemeView {
   pub enabled: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomDiscordInstall {
+  pub id: String,
+  pub name: String,
+  pub path: String,
+}
+
+/// A theme URL the user added by hand. `auth_header` is sent as the
+/// `Authorization` header, for themes hosted somewhere private.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTheme {
+  pub url: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub auth_header: Option<String>,
+}
+
+// Hand-written so the header value never ends up in logs.
+impl fmt::Debug for UserTheme {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("UserTheme")
+      .field("url", &self.url)
+      .field("auth_header", &self.auth_header.as_ref().map(|_| "<redacted>"))
+      .finish()
+  }
+}
+
+#[derive(Clone)]
 pub struct ProvidedThemeInfo {
   pub id: String,
   pub name: String,
   pub url: String,
+  pub auth_header: Option<String>,
+}
+
+impl fmt::Debug for ProvidedThemeInfo {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ProvidedThemeInfo")
+      .field("id", &self.id)
+      .field("name", &self.name)
+      .field("url", &self.url)
+      .field("auth_header", &self.auth_header.as_ref().map(|_| "<redacted>"))
+      .finish()
+  }
+}
+
+/// A saved Vencord repository folder that `reconcile_options` replaced with
+/// the default because it is no longer usable.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoDirReset {
+  pub previous: String,
+  pub reason: String,
+}
+
+/// Provided entries that `reconcile_options` added or dropped because the
+/// bundled lists changed since the options were last saved, and settings it
+/// had to reset.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionsReconciliation {
+  pub added_repositories: Vec<String>,
+  pub removed_repositories: Vec<String>,
+  pub added_themes: Vec<String>,
+  pub removed_themes: Vec<String>,
+  pub repo_dir_reset: Option<RepoDirReset>,
+}
+
+impl OptionsReconciliation {
+  fn is_empty(&self) -> bool {
+    self.added_repositories.is_empty()
+      && self.removed_repositories.is_empty()
+      && self.added_themes.is_empty()
+      && self.removed_themes.is_empty()
+      && self.repo_dir_reset.is_none()
+  }
+}
+
+// Held until the UI fetches options so the change is reported exactly once.
+static PENDING_RECONCILIATION: Mutex<Option<OptionsReconciliation>> = Mutex::new(None);
+
+fn diff_ids(saved: &[String], current: &[String]) -> (Vec<String>, Vec<String>) {
+  let added = current
+    .iter()
+    .filter(|id| !saved.contains(id))
+    .cloned()
+    .collect();
+  let removed = saved
+    .iter()
+    .filter(|id| !current.contains(id))
+    .cloned()
+    .collect();
+
+  (added, removed)
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -125,13 +274,23 @@ pub struct OptionsResponse {
   pub vencord_repo_dir: String,
   pub user_repositories: Vec<String>,
   #[serde(default)]
-  pub user_themes: Vec<String>,
+  pub user_themes: Vec<UserTheme>,
   #[serde(default)]
   pub provided_repositories: Vec<ProvidedRepositoryView>,
   #[serde(default)]
   pub provided_themes: Vec<ProvidedThemeView>,
   #[serde(default = "default_true")]
   pub close_discord_on_backup: bool,
+  #[serde(default)]
+  pub aggressive_close: bool,
+  #[serde(default)]
+  pub always_restart_discord: bool,
+  #[serde(default = "default_true")]
+  pub reopen_discord_after_patch: bool,
+  #[serde(default)]
+  pub confirm_before_reopen: bool,
+  #[serde(default)]
+  pub offline_mode: bool,
   #[serde(default = "default_selected_discord_clients")]
   pub selected_discord_clients: Vec<String>,
   #[serde(default = "default_max_backup_count")]
@@ -140,24 +299,82 @@ pub struct OptionsResponse {
   pub max_backup_size_mb: Option<u64>,
   #[serde(default = "default_max_run_log_count")]
   pub max_run_log_count: Option<u32>,
+  #[serde(default = "default_max_concurrent_network")]
+  pub max_concurrent_network: usize,
+  #[serde(default = "default_true")]
+  pub log_to_stdout: bool,
+  #[serde(default)]
+  pub backup_include_node_modules: bool,
+  #[serde(default = "default_backup_exclude")]
+  pub backup_exclude: Vec<String>,
+  #[serde(default)]
+  pub backup_discord_settings: bool,
+  #[serde(default = "default_true")]
+  pub backup_user_themes: bool,
+  #[serde(default)]
+  pub backup_name_format: Option<String>,
+  #[serde(default)]
+  pub backup_use_utc: bool,
+  #[serde(default)]
+  pub theme_dir_override: Option<String>,
+  #[serde(default)]
+  pub write_themes_to_all_clients: bool,
+  #[serde(default)]
+  pub userplugins_dir: Option<String>,
+  #[serde(default)]
+  pub git_reference_repo: Option<String>,
+  #[serde(default)]
+  pub max_plugin_cache_mb: Option<u64>,
+  #[serde(default)]
+  pub node_path: Option<String>,
+  #[serde(default)]
+  pub pnpm_path: Option<String>,
+  #[serde(default)]
+  pub custom_discord_installs: Vec<CustomDiscordInstall>,
+  #[serde(default)]
+  pub detect_wine_installs: bool,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub reconciliation: Option<OptionsReconciliation>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserOptions {
+  #[serde(default)]
+  pub schema_version: u32,
   pub vencord_repo_url: String,
   #[serde(default = "default_repo_base_dir")]
   pub vencord_repo_dir: String,
   pub vencord_repo_url_default: Option<String>,
   pub user_repositories: Vec<String>,
   #[serde(default)]
-  pub user_themes: Vec<String>,
+  pub user_themes: Vec<UserTheme>,
   #[serde(default)]
   pub provided_repositories: Vec<ProvidedRepositoryState>,
   #[serde(default)]
   pub provided_themes: Vec<ProvidedThemeState>,
   #[serde(default = "default_true")]
   pub close_discord_on_backup: bool,
+  /// Closes every running Discord client instead of only the selected ones.
+  #[serde(default)]
+  pub aggressive_close: bool,
+  /// Starts the selected installed clients after patching even when closing
+  /// Discord was skipped, instead of leaving them closed.
+  #[serde(default)]
+  pub always_restart_discord: bool,
+  /// Restarts the Discord clients that were closed for patching. Disable when
+  /// something else (e.g. a process supervisor) is responsible for launching it.
+  #[serde(default = "default_true")]
+  pub reopen_discord_after_patch: bool,
+  /// Pauses before reopening Discord until `confirm_reopen` is called, so the
+  /// patched install can be inspected before the client can update over it.
+  #[serde(default)]
+  pub confirm_before_reopen: bool,
+  /// Builds and injects from the existing clone without syncing it or
+  /// downloading themes. The clone is also left in place instead of being
+  /// moved into a backup, since it cannot be re-cloned.
+  #[serde(default)]
+  pub offline_mode: bool,
   #[serde(default = "default_selected_discord_clients")]
   pub selected_discord_clients: Vec<String>,
   #[serde(default = "default_max_backup_count")]
@@ -166,11 +383,78 @@ pub struct UserOptions {
   pub max_backup_size_mb: Option<u64>,
   #[serde(default = "default_max_run_log_count")]
   pub max_run_log_count: Option<u32>,
+  /// How many plugin clones and theme downloads may run at once, 4 by
+  /// default. 0 runs them one at a time.
+  #[serde(default = "default_max_concurrent_network")]
+  pub max_concurrent_network: usize,
+  /// Mirrors log output to stdout. Read once at startup, so changes apply on
+  /// the next launch.
+  #[serde(default = "default_true")]
+  pub log_to_stdout: bool,
+  /// Keeps `node_modules` in the moved install instead of deleting it first.
+  /// Speeds up the next build at the cost of a much larger backup.
+  #[serde(default)]
+  pub backup_include_node_modules: bool,
+  /// Glob patterns left out of backups. A `node_modules` entry (there by
+  /// default) still applies when `backup_include_node_modules` is set, so it
+  /// has to be removed for node_modules to be kept.
+  #[serde(default = "default_backup_exclude")]
+  pub backup_exclude: Vec<String>,
+  /// Copies each selected client's `settings.json` and the Vencord settings
+  /// into `discord-settings/` inside every backup.
+  #[serde(default)]
+  pub backup_discord_settings: bool,
+  /// Moves user-added themes into backups along with the provided ones. When
+  /// off they are left in the theme folder.
+  #[serde(default = "default_true")]
+  pub backup_user_themes: bool,
+  /// strftime format for backup folder names, `%Y-%m-%d_%H-%M-%S` when unset.
+  /// Must render without path separators.
+  #[serde(default)]
+  pub backup_name_format: Option<String>,
+  /// Timestamps backup folder names in UTC instead of local time.
+  #[serde(default)]
+  pub backup_use_utc: bool,
+  #[serde(default)]
+  pub theme_dir_override: Option<String>,
+  /// Also writes themes into other detected clients' theme folders (e.g.
+  /// Vesktop), not just the Vencord one.
+  #[serde(default)]
+  pub write_themes_to_all_clients: bool,
+  /// Keeps plugin repositories here instead of inside the Vencord clone and
+  /// links them into `src/userplugins`. They survive re-clones of Vencord, but
+  /// are left behind when a plugin is removed from the list.
+  #[serde(default)]
+  pub userplugins_dir: Option<String>,
+  /// Local clone passed to `git clone --reference … --dissociate` so fresh
+  /// clones copy objects from disk instead of downloading them.
+  #[serde(default)]
+  pub git_reference_repo: Option<String>,
+  /// Keeps plugin clones under the config directory, up to this many MB, so
+  /// syncing pulls them instead of cloning again after the Vencord clone is
+  /// reset. Unset disables the cache. Ignored when `userplugins_dir` is set.
+  #[serde(default)]
+  pub max_plugin_cache_mb: Option<u64>,
+  /// Absolute Node.js / pnpm binaries to build with instead of looking them
+  /// up on PATH, for nvm/volta setups the GUI does not see.
+  #[serde(default)]
+  pub node_path: Option<String>,
+  #[serde(default)]
+  pub pnpm_path: Option<String>,
+  /// Portable or non-standard installs the user added by hand. Merged into
+  /// the detected installs when the path still looks like Discord.
+  #[serde(default)]
+  pub custom_discord_installs: Vec<CustomDiscordInstall>,
+  /// Linux only: also looks for the Windows client in Wine, Bottles and
+  /// Lutris prefixes. Off by default because scanning the prefixes is slow.
+  #[serde(default)]
+  pub detect_wine_installs: bool,
 }
 
 impl Default for UserOptions {
   fn default() -> Self {
     Self {
+      schema_version: CURRENT_OPTIONS_SCHEMA_VERSION,
       vencord_repo_url: DEFAULT_VENCORD_REPO_URL.to_string(),
       vencord_repo_url_default: Some(DEFAULT_VENCORD_REPO_URL.to_string()),
       vencord_repo_dir: default_repo_base_dir(),
@@ -191,15 +475,46 @@ impl Default for UserOptions {
         })
         .collect(),
       close_discord_on_backup: default_true(),
+      aggressive_close: false,
+      always_restart_discord: false,
+      reopen_discord_after_patch: default_true(),
+      confirm_before_reopen: false,
+      offline_mode: false,
       selected_discord_clients: default_selected_discord_clients(),
       max_backup_count: default_max_backup_count(),
       max_backup_size_mb: default_max_backup_size_mb(),
       max_run_log_count: default_max_run_log_count(),
+      max_concurrent_network: default_max_concurrent_network(),
+      log_to_stdout: default_true(),
+      backup_include_node_modules: false,
+      backup_exclude: default_backup_exclude(),
+      backup_discord_settings: false,
+      backup_user_themes: default_true(),
+      backup_name_format: None,
+      backup_use_utc: false,
+      theme_dir_override: None,
+      write_themes_to_all_clients: false,
+      userplugins_dir: None,
+      git_reference_repo: None,
+      max_plugin_cache_mb: None,
+      node_path: None,
+      pnpm_path: None,
+      custom_discord_installs: Vec::new(),
+      detect_wine_installs: false,
     }
   }
 }
 
+/// Path of an alternate options file, e.g. a throwaway one for CI. When set
+/// it wins over the default `user-options.json`, and changes are saved back
+/// to it rather than to the user's real config.
+const OPTIONS_PATH_ENV: &str = "VENCORD_INSTALLER_OPTIONS";
+
 fn options_path() -> Result<PathBuf, String> {
+  if let Some(path) = std::env::var_os(OPTIONS_PATH_ENV).filter(|path| !path.is_empty()) {
+    return Ok(PathBuf::from(path));
+  }
+
   let dir = app_config_dir().map_err(|err| format!("Failed to create options directory: {err}"))?;
 
   Ok(dir.join("user-options.json"))
@@ -210,7 +525,15 @@ fn save_options(options: &UserOptions) -> Result<(), String> {
   let json = serde_json::to_string_pretty(options)
     .map_err(|err| format!("Failed to serialize options: {err}"))?;
 
-  fs::write(path, json).map_err(|err| format!("Failed to write options file: {err}"))
+  if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+    fs::create_dir_all(parent)
+      .map_err(|err| format!("Failed to create options directory {}: {err}", parent.display()))?;
+  }
+
+  fs::write(path, json).map_err(|err| format!("Failed to write options file: {err}"))?;
+  network::set_limit(options.max_concurrent_network);
+
+  Ok(())
 }
 
 fn reconcile_options(mut options: UserOptions) -> Result<UserOptions, String> {
@@ -231,14 +554,6 @@ fn reconcile_options(mut options: UserOptions) -> Result<UserOptions, String> {
     updated = true;
   }
 
-  let current_default_dir = default_repo_base_dir();
-  let legacy_default_dir = legacy_repo_base_dir();
-
-  if options.vencord_repo_dir == legacy_default_dir {
-    options.vencord_repo_dir = current_default_dir;
-    updated = true;
-  }
-
   let provided: Vec<ProvidedRepositoryState> = PROVIDED_REPOSITORIES
     .iter()
     .map(|repo| ProvidedRepositoryState {
@@ -252,7 +567,21 @@ fn reconcile_options(mut options: UserOptions) -> Result<UserOptions, String> {
     })
     .collect();
 
+  let mut reconciliation = OptionsReconciliation::default();
+
   if provided != options.provided_repositories {
+    let saved_ids: Vec<String> = options
+      .provided_repositories
+      .iter()
+      .map(|entry| entry.id.clone())
+      .collect();
+    let current_ids: Vec<String> = provided
+      .iter()
+      .map(|entry| entry.id.clone())
+      .collect();
+    (reconciliation.added_repositories, reconciliation.removed_repositories) =
+      diff_ids(&saved_ids, &current_ids);
+
     options.provided_repositories = provided;
     updated = true;
   }
@@ -271,10 +600,38 @@ fn reconcile_options(mut options: UserOptions) -> Result<UserOptions, String> {
     .collect();
 
   if themes != options.provided_themes {
+    let saved_ids: Vec<String> = options
+      .provided_themes
+      .iter()
+      .map(|entry| entry.id.clone())
+      .collect();
+    let current_ids: Vec<String> = themes
+      .iter()
+      .map(|entry| entry.id.clone())
+      .collect();
+    (reconciliation.added_themes, reconciliation.removed_themes) =
+      diff_ids(&saved_ids, &current_ids);
+
     options.provided_themes = themes;
     updated = true;
   }
 
+  match normalize_repo_dir(&options.vencord_repo_dir) {
+    Ok(dir) if dir != options.vencord_repo_dir => {
+      options.vencord_repo_dir = dir;
+      updated = true;
+    }
+    Ok(_) => {}
+    Err(err) => {
+      warn!("Resetting Vencord repository folder {}: {err}", options.vencord_repo_dir);
+      reconciliation.repo_dir_reset = Some(RepoDirReset {
+        previous: std::mem::replace(&mut options.vencord_repo_dir, default_repo_base_dir()),
+        reason: err,
+      });
+      updated = true;
+    }
+  }
+
   if options.max_backup_count.is_none() {
     options.max_backup_count = default_max_backup_count();
     updated = true;
@@ -289,16 +646,75 @@ fn reconcile_options(mut options: UserOptions) -> Result<UserOptions, String> {
     save_options(&options)?;
   }
 
+  if !reconciliation.is_empty() {
+    log::info!("[options] Options reconciled: {reconciliation:?}");
+
+    if let Ok(mut pending) = PENDING_RECONCILIATION.lock() {
+      *pending = Some(reconciliation);
+    }
+  }
+
   Ok(options)
 }
 
+fn migrate_options(mut value: serde_json::Value) -> Result<(serde_json::Value, bool), String> {
+  let object = value
+    .as_object_mut()
+    .ok_or_else(|| "Options file is not a JSON object".to_string())?;
+
+  let saved_version = object
+    .get("schemaVersion")
+    .and_then(|version| version.as_u64())
+    .unwrap_or(0) as u32;
+
+  if saved_version >= CURRENT_OPTIONS_SCHEMA_VERSION {
+    return Ok((value, false));
+  }
+
+  for (version, migration) in OPTIONS_MIGRATIONS
+    .iter()
+    .enumerate()
+    .skip(saved_version as usize)
+  {
+    log::info!(
+      "Migrating options file from schema {version} to {}",
+      version + 1
+    );
+    migration(object);
+  }
+
+  object.insert(
+    "schemaVersion".to_string(),
+    serde_json::Value::from(CURRENT_OPTIONS_SCHEMA_VERSION),
+  );
+
+  Ok((value, true))
+}
+
+fn parse_options(content: &str) -> Result<(UserOptions, bool), String> {
+  let value = serde_json::from_str::<serde_json::Value>(content).map_err(|err| err.to_string())?;
+  let (migrated, changed) = migrate_options(value)?;
+  let options = serde_json::from_value::<UserOptions>(migrated).map_err(|err| err.to_string())?;
+
+  Ok((options, changed))
+}
+
 fn load_options() -> Result<UserOptions, String> {
   let path = options_path()?;
 
   if path.exists() {
     match fs::read_to_string(&path) {
-      Ok(content) => match serde_json::from_str::<UserOptions>(&content) {
-        Ok(opts) => return reconcile_options(opts),
+      Ok(content) => match parse_options(&content) {
+        Ok((opts, migrated)) => {
+          if migrated {
+            save_options(&opts)?;
+          }
+
+          let opts = reconcile_options(opts)?;
+          network::set_limit(opts.max_concurrent_network);
+
+          return Ok(opts);
+        }
         Err(err) => warn!("Failed to parse options file, resetting to defaults: {err}"),
       },
       Err(err) => warn!("Failed to read options file, resetting to defaults: {err}"),
@@ -310,6 +726,16 @@ fn load_options() -> Result<UserOptions, String> {
   Ok(defaults)
 }
 
+/// Reads `log_to_stdout` straight from the options file, without migrating
+/// or reconciling it, since this runs before the logger exists.
+pub fn log_to_stdout_enabled() -> bool {
+  options_path()
+    .ok()
+    .and_then(|path| fs::read_to_string(path).ok())
+    .and_then(|content| parse_options(&content).ok())
+    .map_or(true, |(options, _)| options.log_to_stdout)
+}
+
 fn merge_provided_repositories(saved: &[ProvidedRepositoryState]) -> Vec<ProvidedRepositoryView> {
   let saved_map: HashMap<String, bool> = saved
     .iter()
@@ -362,6 +788,308 @@ fn merge_provided_themes(saved: &[ProvidedThemeState]) -> Vec<ProvidedThemeView>
     .collect()
 }
 
+/// Validates a git repository URL (HTTP(S), SSH, git:// or scp-style
+/// `user@host:path`) and returns it trimmed of whitespace and trailing slashes.
+pub fn normalize_repo_url(url: &str) -> Result<String, String> {
+  let trimmed = url.trim().trim_end_matches('/');
+
+  if trimmed.is_empty() {
+    return Err("Repository URL is empty".to_string());
+  }
+
+  if trimmed.chars().any(char::is_whitespace) {
+    return Err("Repository URL must not contain spaces".to_string());
+  }
+
+  let has_scheme = ["https://", "http://", "ssh://", "git://"]
+    .iter()
+    .any(|scheme| trimmed.starts_with(scheme) && trimmed.len() > scheme.len());
+  let is_scp_like = !trimmed.contains("://")
+    && trimmed
+      .split_once(':')
+      .map_or(false, |(host, path)| host.contains('@') && !path.is_empty());
+
+  if !has_scheme && !is_scp_like {
+    return Err("Repository URL must start with https://, ssh://, git:// or be user@host:path".to_string());
+  }
+
+  Ok(trimmed.to_string())
+}
+
+/// Validates a theme URL, which must be an absolute HTTP(S) URL.
+pub fn normalize_theme_url(url: &str) -> Result<String, String> {
+  let trimmed = url.trim();
+
+  if trimmed.is_empty() {
+    return Err("Theme URL is empty".to_string());
+  }
+
+  let parsed = reqwest::Url::parse(trimmed).map_err(|err| format!("Invalid theme URL: {err}"))?;
+
+  if !matches!(parsed.scheme(), "https" | "http") {
+    return Err(format!("Theme URL must use http or https, not {}", parsed.scheme()));
+  }
+
+  if parsed.host_str().map_or(true, str::is_empty) {
+    return Err("Theme URL is missing a host".to_string());
+  }
+
+  Ok(parsed.to_string())
+}
+
+/// Expands `$NAME`, `${NAME}` and `%NAME%`. Anything that is not a set
+/// variable, including an unclosed `${`, is kept as written, since `$` and
+/// `%` are valid in folder names.
+fn expand_env_vars(value: &str) -> String {
+  let mut expanded = String::with_capacity(value.len());
+  let mut rest = value;
+
+  while let Some(start) = rest.find(['$', '%']) {
+    expanded.push_str(&rest[..start]);
+    let marker = &rest[start..];
+
+    let (name, consumed) = if let Some(braced) = marker.strip_prefix("${") {
+      match braced.find('}') {
+        Some(end) => (&braced[..end], end + 3),
+        None => ("", 0),
+      }
+    } else if let Some(percent) = marker.strip_prefix('%') {
+      match percent.find('%') {
+        Some(end) if end > 0 => (&percent[..end], end + 2),
+        _ => {
+          expanded.push('%');
+          rest = percent;
+          continue;
+        }
+      }
+    } else {
+      let name_len = marker[1..]
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+        .unwrap_or(marker.len() - 1);
+      (&marker[1..1 + name_len], name_len + 1)
+    };
+
+    if name.is_empty() {
+      expanded.push_str(&marker[..1]);
+      rest = &marker[1..];
+      continue;
+    }
+
+    match std::env::var(name) {
+      Ok(resolved) => expanded.push_str(&resolved),
+      Err(_) => expanded.push_str(&marker[..consumed]),
+    }
+    rest = &marker[consumed..];
+  }
+
+  expanded.push_str(rest);
+  expanded
+}
+
+/// Expands `~` and environment variables in the Vencord repo folder, makes it
+/// canonical when it exists, and rejects folders that deleting or re-cloning
+/// the repo would damage: a filesystem root, the home folder itself, or
+/// anything inside the installer's own config folder.
+pub fn normalize_repo_dir(dir: &str) -> Result<String, String> {
+  let trimmed = dir.trim();
+
+  if trimmed.is_empty() {
+    return Err("Vencord repository folder is empty".to_string());
+  }
+
+  let home = dirs::home_dir();
+  let expanded = expand_env_vars(trimmed);
+  let expanded = match expanded.strip_prefix('~') {
+    Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+      let home = home
+        .clone()
+        .ok_or_else(|| "Could not determine the home folder to expand ~".to_string())?;
+      home.join(rest.trim_start_matches(['/', '\\']))
+    }
+    _ => PathBuf::from(&expanded),
+  };
+
+  if !expanded.is_absolute() {
+    return Err(format!(
+      "Vencord repository folder must be an absolute path, not {trimmed}"
+    ));
+  }
+
+  // Rebuilding from components drops trailing separators and `.` segments.
+  let path: PathBuf = expanded.components().collect();
+  let path = dunce::canonicalize(&path).unwrap_or(path);
+
+  if path.parent().is_none() {
+    return Err(format!(
+      "Vencord repository folder cannot be a filesystem root ({})",
+      path.display()
+    ));
+  }
+
+  if let Some(home) = home {
+    let home = dunce::canonicalize(&home).unwrap_or(home);
+
+    if path == home {
+      return Err("Vencord repository folder cannot be your home folder itself".to_string());
+    }
+  }
+
+  if let Ok(config_dir) = app_config_dir() {
+    let config_dir = dunce::canonicalize(&config_dir).unwrap_or(config_dir);
+
+    if path.starts_with(&config_dir) {
+      return Err(format!(
+        "Vencord repository folder cannot be inside the installer's config folder ({})",
+        config_dir.display()
+      ));
+    }
+  }
+
+  Ok(path.to_string_lossy().into_owned())
+}
+
+/// Normalizes valid entries and keeps invalid ones as typed (trimmed) so a
+/// save never silently discards user input.
+fn normalize_user_entries(
+  entries: Vec<String>,
+  normalize: fn(&str) -> Result<String, String>,
+) -> Vec<String> {
+  entries
+    .into_iter()
+    .map(|entry| normalize_user_entry(&entry, normalize))
+    .collect()
+}
+
+fn normalize_user_entry(entry: &str, normalize: fn(&str) -> Result<String, String>) -> String {
+  match normalize(entry) {
+    Ok(normalized) => normalized,
+    Err(err) => {
+      if !entry.trim().is_empty() {
+        warn!("Keeping invalid entry {}: {err}", entry.trim());
+      }
+      entry.trim().to_string()
+    }
+  }
+}
+
+fn normalize_user_themes(themes: Vec<UserTheme>) -> Vec<UserTheme> {
+  themes
+    .into_iter()
+    .map(|theme| UserTheme {
+      url: normalize_user_entry(&theme.url, normalize_theme_url),
+      auth_header: theme
+        .auth_header
+        .map(|header| header.trim().to_string())
+        .filter(|header| !header.is_empty()),
+    })
+    .collect()
+}
+
+fn validate_theme_dir_override(value: Option<String>) -> Option<String> {
+  let trimmed = value
+    .map(|dir| dir.trim().to_string())
+    .filter(|dir| !dir.is_empty())?;
+
+  if is_writable_dir(Path::new(&trimmed)) {
+    Some(trimmed)
+  } else {
+    warn!("Theme directory override {trimmed} is not a writable directory; using the default");
+    None
+  }
+}
+
+fn validate_backup_name_format(value: Option<String>) -> Option<String> {
+  let trimmed = value
+    .map(|format| format.trim().to_string())
+    .filter(|format| !format.is_empty())?;
+
+  match check_backup_name_format(&trimmed) {
+    Ok(()) => Some(trimmed),
+    Err(err) => {
+      warn!("Backup name format {trimmed:?} is unusable because {err}; using the default");
+      None
+    }
+  }
+}
+
+/// Checks a `backup_name_format` renders to a single, portable folder name.
+pub fn check_backup_name_format(format: &str) -> Result<(), String> {
+  if !format.contains('%') {
+    return Err("it has no date or time fields".to_string());
+  }
+
+  if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+    return Err("it is not a valid strftime format".to_string());
+  }
+
+  let sample = Local::now().format(format).to_string();
+
+  if sample.trim().is_empty()
+    || sample.ends_with(['.', ' '])
+    || sample
+      .chars()
+      .any(|ch| ch.is_control() || matches!(ch, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+  {
+    return Err(format!("it produces {sample:?}, which is not a safe folder name"));
+  }
+
+  Ok(())
+}
+
+/// Whether `path` can be passed to `git clone --reference`: either a work
+/// tree or a bare repository.
+pub fn is_valid_reference_repo(path: &str) -> bool {
+  let path = Path::new(path);
+
+  path.join(".git").exists() || (path.join("objects").is_dir() && path.join("HEAD").is_file())
+}
+
+fn validate_git_reference_repo(value: Option<String>) -> Result<Option<String>, String> {
+  let Some(trimmed) = value
+    .map(|path| path.trim().to_string())
+    .filter(|path| !path.is_empty())
+  else {
+    return Ok(None);
+  };
+
+  if is_valid_reference_repo(&trimmed) {
+    Ok(Some(trimmed))
+  } else {
+    Err(format!("Git reference repository {trimmed} is not a git repository"))
+  }
+}
+
+fn is_executable(path: &Path) -> bool {
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).map_or(false, |meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+  }
+
+  #[cfg(not(unix))]
+  {
+    path.is_file()
+  }
+}
+
+/// An empty path means the executable is looked up on PATH.
+fn validate_executable_path(name: &str, value: Option<String>) -> Result<Option<String>, String> {
+  let Some(trimmed) = value
+    .map(|path| path.trim().to_string())
+    .filter(|path| !path.is_empty())
+  else {
+    return Ok(None);
+  };
+  let path = Path::new(&trimmed);
+
+  if path.is_absolute() && is_executable(path) {
+    Ok(Some(trimmed))
+  } else {
+    Err(format!("{name} path {trimmed} is not an absolute path to an executable"))
+  }
+}
+
 fn to_response(options: UserOptions) -> OptionsResponse {
   OptionsResponse {
     vencord_repo_url: options.vencord_repo_url,
@@ -371,14 +1099,37 @@ fn to_response(options: UserOptions) -> OptionsResponse {
     provided_repositories: merge_provided_repositories(&options.provided_repositories),
     provided_themes: merge_provided_themes(&options.provided_themes),
     close_discord_on_backup: options.close_discord_on_backup,
+    aggressive_close: options.aggressive_close,
+    always_restart_discord: options.always_restart_discord,
+    reopen_discord_after_patch: options.reopen_discord_after_patch,
+    confirm_before_reopen: options.confirm_before_reopen,
+    offline_mode: options.offline_mode,
     selected_discord_clients: options.selected_discord_clients,
     max_backup_count: options.max_backup_count,
     max_backup_size_mb: options.max_backup_size_mb,
     max_run_log_count: options.max_run_log_count,
+    max_concurrent_network: options.max_concurrent_network,
+    log_to_stdout: options.log_to_stdout,
+    backup_include_node_modules: options.backup_include_node_modules,
+    backup_exclude: options.backup_exclude,
+    backup_discord_settings: options.backup_discord_settings,
+    backup_user_themes: options.backup_user_themes,
+    backup_name_format: options.backup_name_format,
+    backup_use_utc: options.backup_use_utc,
+    theme_dir_override: options.theme_dir_override,
+    write_themes_to_all_clients: options.write_themes_to_all_clients,
+    userplugins_dir: options.userplugins_dir,
+    git_reference_repo: options.git_reference_repo,
+    max_plugin_cache_mb: options.max_plugin_cache_mb,
+    node_path: options.node_path,
+    pnpm_path: options.pnpm_path,
+    custom_discord_installs: options.custom_discord_installs,
+    detect_wine_installs: options.detect_wine_installs,
+    reconciliation: None,
   }
 }
 
-fn to_storage(options: OptionsResponse) -> UserOptions {
+fn to_storage(options: OptionsResponse) -> Result<UserOptions, String> {
   let valid_ids: HashMap<_, _> = PROVIDED_REPOSITORIES
     .iter()
     .map(|repo| (repo.id.clone(), repo.default_enabled))
@@ -409,48 +1160,218 @@ fn to_storage(options: OptionsResponse) -> UserOptions {
     })
     .collect();
 
-  UserOptions {
+  Ok(UserOptions {
+    schema_version: CURRENT_OPTIONS_SCHEMA_VERSION,
     vencord_repo_url: options.vencord_repo_url,
     vencord_repo_url_default: Some(DEFAULT_VENCORD_REPO_URL.to_string()),
-    vencord_repo_dir: options.vencord_repo_dir,
-    user_repositories: options.user_repositories,
-    user_themes: options.user_themes,
+    vencord_repo_dir: normalize_repo_dir(&options.vencord_repo_dir)?,
+    user_repositories: normalize_user_entries(options.user_repositories, normalize_repo_url),
+    user_themes: normalize_user_themes(options.user_themes),
     provided_repositories,
     provided_themes,
     close_discord_on_backup: options.close_discord_on_backup,
+    aggressive_close: options.aggressive_close,
+    always_restart_discord: options.always_restart_discord,
+    reopen_discord_after_patch: options.reopen_discord_after_patch,
+    confirm_before_reopen: options.confirm_before_reopen,
+    offline_mode: options.offline_mode,
     selected_discord_clients: options.selected_discord_clients,
     max_backup_count: options.max_backup_count,
     max_backup_size_mb: options.max_backup_size_mb,
     max_run_log_count: options.max_run_log_count,
-  }
+    max_concurrent_network: options.max_concurrent_network,
+    log_to_stdout: options.log_to_stdout,
+    backup_include_node_modules: options.backup_include_node_modules,
+    backup_exclude: options.backup_exclude,
+    backup_discord_settings: options.backup_discord_settings,
+    backup_user_themes: options.backup_user_themes,
+    backup_name_format: validate_backup_name_format(options.backup_name_format),
+    backup_use_utc: options.backup_use_utc,
+    theme_dir_override: validate_theme_dir_override(options.theme_dir_override),
+    write_themes_to_all_clients: options.write_themes_to_all_clients,
+    userplugins_dir: options
+      .userplugins_dir
+      .map(|dir| dir.trim().to_string())
+      .filter(|dir| !dir.is_empty()),
+    git_reference_repo: validate_git_reference_repo(options.git_reference_repo)?,
+    max_plugin_cache_mb: options.max_plugin_cache_mb.filter(|mb| *mb > 0),
+    node_path: validate_executable_path("Node.js", options.node_path)?,
+    pnpm_path: validate_executable_path("pnpm", options.pnpm_path)?,
+    custom_discord_installs: options
+      .custom_discord_installs
+      .into_iter()
+      .filter(|install| !install.id.trim().is_empty() && !install.path.trim().is_empty())
+      .collect(),
+    detect_wine_installs: options.detect_wine_installs,
+  })
 }
 
 #[tauri::command]
-pub fn get_user_options() -> Result<OptionsResponse, String> {
-  let options = read_user_options()?;
-  Ok(to_response(options))
+pub fn get_user_options() -> Result<OptionsResponse, InstallerError> {
+  let options = read_user_options().map_err(InstallerError::filesystem)?;
+  let mut response = to_response(options);
+
+  response.reconciliation = PENDING_RECONCILIATION
+    .lock()
+    .ok()
+    .and_then(|mut pending| pending.take());
+
+  Ok(response)
 }
 
 #[tauri::command]
-pub fn update_user_options(options: OptionsResponse) -> Result<OptionsResponse, String> {
-  let storage = to_storage(options);
-  save_options(&storage)?;
+pub fn update_user_options(options: OptionsResponse) -> Result<OptionsResponse, InstallerError> {
+  let storage = to_storage(options).map_err(InstallerError::invalid_input)?;
+
+  try_update_options_with(|options| {
+    *options = storage;
+    Ok(())
+  })
+}
 
-  let refreshed = load_options()?;
+// Serializes every options write, so a full save and a quick toggle cannot
+// overwrite each other's change.
+static OPTIONS_UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+fn update_options_with(
+  update: impl FnOnce(&mut UserOptions),
+) -> Result<OptionsResponse, InstallerError> {
+  try_update_options_with(|options| {
+    update(options);
+    Ok(())
+  })
+}
+
+/// Like `update_options_with`, but `update` can reject the change based on
+/// the options it sees, in which case nothing is saved.
+fn try_update_options_with(
+  update: impl FnOnce(&mut UserOptions) -> Result<(), InstallerError>,
+) -> Result<OptionsResponse, InstallerError> {
+  let _guard = OPTIONS_UPDATE_LOCK
+    .lock()
+    .map_err(|_| "Options update lock was poisoned")?;
+
+  let mut options = read_user_options().map_err(InstallerError::filesystem)?;
+  update(&mut options)?;
+  save_options(&options).map_err(InstallerError::filesystem)?;
+
+  let refreshed = load_options().map_err(InstallerError::filesystem)?;
   Ok(to_response(refreshed))
 }
 
+#[tauri::command]
+pub fn set_provided_repository_enabled(
+  id: String,
+  enabled: bool,
+) -> Result<OptionsResponse, InstallerError> {
+  if !PROVIDED_REPOSITORIES.iter().any(|repo| repo.id == id) {
+    return Err(InstallerError::not_found(format!("Unknown provided repository {id}")));
+  }
+
+  update_options_with(|options| {
+    match options.provided_repositories.iter_mut().find(|repo| repo.id == id) {
+      Some(entry) => entry.enabled = enabled,
+      None => options
+        .provided_repositories
+        .push(ProvidedRepositoryState { id, enabled }),
+    }
+  })
+}
+
+#[tauri::command]
+pub fn set_provided_theme_enabled(
+  id: String,
+  enabled: bool,
+) -> Result<OptionsResponse, InstallerError> {
+  if !PROVIDED_THEMES.iter().any(|theme| theme.id == id) {
+    return Err(InstallerError::not_found(format!("Unknown provided theme {id}")));
+  }
+
+  update_options_with(|options| {
+    match options.provided_themes.iter_mut().find(|theme| theme.id == id) {
+      Some(entry) => entry.enabled = enabled,
+      None => options.provided_themes.push(ProvidedThemeState { id, enabled }),
+    }
+  })
+}
+
+#[tauri::command]
+pub fn set_close_discord_on_backup(enabled: bool) -> Result<OptionsResponse, InstallerError> {
+  update_options_with(|options| options.close_discord_on_backup = enabled)
+}
+
+fn check_user_repository_index(options: &UserOptions, index: usize) -> Result<(), InstallerError> {
+  let count = options.user_repositories.len();
+
+  if index >= count {
+    return Err(InstallerError::invalid_input(format!(
+      "Repository index {index} is out of range; there are {count} user repositories"
+    )));
+  }
+
+  Ok(())
+}
+
+/// Moves the user repository at `from` to `to`, shifting the ones between.
+/// This only changes how the list is shown; Vencord orders plugins by their
+/// folder under `src/userplugins`, not by this list.
+#[tauri::command]
+pub fn move_user_repository(from: usize, to: usize) -> Result<OptionsResponse, InstallerError> {
+  try_update_options_with(|options| {
+    check_user_repository_index(options, from)?;
+    check_user_repository_index(options, to)?;
+
+    let url = options.user_repositories.remove(from);
+    options.user_repositories.insert(to, url);
+    Ok(())
+  })
+}
+
+#[tauri::command]
+pub fn remove_user_repository(index: usize) -> Result<OptionsResponse, InstallerError> {
+  try_update_options_with(|options| {
+    check_user_repository_index(options, index)?;
+
+    options.user_repositories.remove(index);
+    Ok(())
+  })
+}
+
+/// The bundled plugin repository catalog, independent of the user's choices.
+#[tauri::command]
+pub fn list_provided_repositories() -> Vec<ProvidedRepository> {
+  PROVIDED_REPOSITORIES.clone()
+}
+
+/// The bundled theme catalog, independent of the user's choices.
+#[tauri::command]
+pub fn list_provided_themes() -> Vec<ProvidedTheme> {
+  PROVIDED_THEMES.clone()
+}
+
 pub fn read_user_options() -> Result<UserOptions, String> {
   load_options()
 }
 
 #[tauri::command]
-pub fn update_selected_discord_clients(selected: Vec<String>) -> Result<(), String> {
-  let mut options = read_user_options()?;
+pub fn update_selected_discord_clients(
+  selected: Vec<String>,
+) -> Result<OptionsResponse, InstallerError> {
+  let installs = discord::get_discord_installs();
+  let unknown: Vec<&str> = selected
+    .iter()
+    .filter(|id| !installs.iter().any(|install| &install.id == *id))
+    .map(String::as_str)
+    .collect();
 
-  options.selected_discord_clients = selected;
+  if !unknown.is_empty() {
+    return Err(InstallerError::not_found(format!(
+      "Discord client(s) not installed: {}",
+      unknown.join(", ")
+    )));
+  }
 
-  save_options(&options)
+  update_options_with(|options| options.selected_discord_clients = selected)
 }
 
 pub fn resolve_plugin_repositories(options: &UserOptions) -> Vec<String> {
@@ -482,6 +1403,64 @@ pub fn resolve_plugin_repositories(options: &UserOptions) -> Vec<String> {
   urls
 }
 
+/// A git remote URL reduced to a form where clone URLs for the same
+/// repository compare equal.
+pub fn normalize_remote_url(url: &str) -> String {
+  url
+    .trim()
+    .trim_end_matches('/')
+    .trim_end_matches(".git")
+    .to_lowercase()
+}
+
+fn same_repo_url(a: &str, b: &str) -> bool {
+  normalize_remote_url(a) == normalize_remote_url(b)
+}
+
+/// Fails when two enabled plugin repositories are declared as conflicting,
+/// naming both, so the flow stops before cloning them into one build. A
+/// conflict declared on either side counts.
+pub fn check_plugin_conflicts(options: &UserOptions) -> Result<(), String> {
+  let enabled_urls = resolve_plugin_repositories(options);
+  let enabled: Vec<&ProvidedRepository> = PROVIDED_REPOSITORIES
+    .iter()
+    .filter(|repo| enabled_urls.iter().any(|url| same_repo_url(url, &repo.url)))
+    .collect();
+
+  let mut conflicts = Vec::new();
+
+  for repo in &enabled {
+    for other in &repo.conflicts_with {
+      if let Some(provided) = enabled.iter().find(|candidate| &candidate.id == other) {
+        let pair = format!("{} and {}", repo.name, provided.name);
+        let reversed = format!("{} and {}", provided.name, repo.name);
+
+        if !conflicts.contains(&pair) && !conflicts.contains(&reversed) {
+          conflicts.push(pair);
+        }
+      } else if let Some(url) = options
+        .user_repositories
+        .iter()
+        .find(|url| same_repo_url(url, other))
+      {
+        conflicts.push(format!("{} and {url}", repo.name));
+      }
+    }
+  }
+
+  if conflicts.is_empty() {
+    Ok(())
+  } else {
+    Err(format!(
+      "Conflicting plugin repositories are enabled together: {}. Disable one of each pair and try again",
+      conflicts.join("; ")
+    ))
+  }
+}
+
+/// Id prefix `resolve_themes` gives themes added by URL in the options.
+pub const USER_THEME_ID_PREFIX: &str = "user-theme-";
+
 pub fn resolve_themes(options: &UserOptions) -> Vec<ProvidedThemeInfo> {
   let provided_enabled: HashMap<_, _> = options
     .provided_themes
@@ -501,6 +1480,7 @@ pub fn resolve_themes(options: &UserOptions) -> Vec<ProvidedThemeInfo> {
       id: theme.id.clone(),
       name: theme.name.clone(),
       url: theme.url.clone(),
+      auth_header: None,
     })
     .collect();
 
@@ -510,14 +1490,14 @@ pub fn resolve_themes(options: &UserOptions) -> Vec<ProvidedThemeInfo> {
     .user_themes
     .iter()
     .enumerate()
-    .filter_map(|(idx, url)| {
-      let trimmed = url.trim();
+    .filter_map(|(idx, theme)| {
+      let trimmed = theme.url.trim();
 
       if trimmed.is_empty() {
         return None;
       }
 
-      let id = format!("user-theme-{}", base_index + idx);
+      let id = format!("{USER_THEME_ID_PREFIX}{}", base_index + idx);
       let name = trimmed
         .rsplit('/')
         .next()
@@ -528,6 +1508,7 @@ pub fn resolve_themes(options: &UserOptions) -> Vec<ProvidedThemeInfo> {
         id,
         name: name.to_string(),
         url: trimmed.to_string(),
+        auth_header: theme.auth_header.clone(),
       })
     });
 
@@ -535,3 +1516,104 @@ pub fn resolve_themes(options: &UserOptions) -> Vec<ProvidedThemeInfo> {
 
   themes
 }
+
+/// Looks a theme up by the id `resolve_themes` gives it, also finding
+/// provided themes that are currently disabled.
+pub fn find_theme(options: &UserOptions, id: &str) -> Option<ProvidedThemeInfo> {
+  resolve_themes(options)
+    .into_iter()
+    .find(|theme| theme.id == id)
+    .or_else(|| {
+      PROVIDED_THEMES
+        .iter()
+        .find(|theme| theme.id == id)
+        .map(|theme| ProvidedThemeInfo {
+          id: theme.id.clone(),
+          name: theme.name.clone(),
+          url: theme.url.clone(),
+          auth_header: None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  // A saved options file from before `schemaVersion`, with `overrides` applied.
+  fn fixture(overrides: serde_json::Value) -> serde_json::Value {
+    let mut value = serde_json::to_value(UserOptions::default()).unwrap();
+    let object = value.as_object_mut().unwrap();
+    object.remove("schemaVersion");
+
+    for (key, entry) in overrides.as_object().unwrap() {
+      object.insert(key.clone(), entry.clone());
+    }
+
+    value
+  }
+
+  #[test]
+  fn migrates_schema_0_to_current() {
+    let saved = fixture(json!({
+      "vencordRepoDir": legacy_repo_base_dir(),
+      "userThemes": ["https://example.com/theme.css"],
+    }));
+
+    let (migrated, changed) = migrate_options(saved.clone()).unwrap();
+    assert!(changed);
+    assert_eq!(migrated["schemaVersion"], json!(CURRENT_OPTIONS_SCHEMA_VERSION));
+    assert_eq!(migrated["vencordRepoDir"], json!(default_repo_base_dir()));
+    assert_eq!(migrated["userThemes"], json!([{ "url": "https://example.com/theme.css" }]));
+
+    let (options, changed) = parse_options(&saved.to_string()).unwrap();
+    assert!(changed);
+    assert_eq!(options.schema_version, CURRENT_OPTIONS_SCHEMA_VERSION);
+    assert_eq!(options.user_themes[0].url, "https://example.com/theme.css");
+  }
+
+  #[test]
+  fn migrates_schema_1_to_current() {
+    // Schema 1 already moved off the legacy folder, so a folder that happens
+    // to match it is the user's choice and must be left alone.
+    let saved = fixture(json!({
+      "schemaVersion": 1,
+      "vencordRepoDir": legacy_repo_base_dir(),
+      "userThemes": [
+        "https://example.com/plain.css",
+        { "url": "https://example.com/private.css", "authHeader": "Bearer token" },
+      ],
+    }));
+
+    let (migrated, changed) = migrate_options(saved).unwrap();
+    assert!(changed);
+    assert_eq!(migrated["schemaVersion"], json!(CURRENT_OPTIONS_SCHEMA_VERSION));
+    assert_eq!(migrated["vencordRepoDir"], json!(legacy_repo_base_dir()));
+    assert_eq!(
+      migrated["userThemes"],
+      json!([
+        { "url": "https://example.com/plain.css" },
+        { "url": "https://example.com/private.css", "authHeader": "Bearer token" },
+      ])
+    );
+  }
+
+  #[test]
+  fn leaves_newer_schema_untouched() {
+    let saved = fixture(json!({
+      "schemaVersion": CURRENT_OPTIONS_SCHEMA_VERSION + 1,
+      "userThemes": [{ "url": "https://example.com/theme.css", "mirrors": ["https://mirror.example.com"] }],
+      "settingFromTheFuture": true,
+    }));
+
+    let (migrated, changed) = migrate_options(saved.clone()).unwrap();
+    assert!(!changed);
+    assert_eq!(migrated, saved);
+
+    let (options, changed) = parse_options(&saved.to_string()).unwrap();
+    assert!(!changed);
+    assert_eq!(options.schema_version, CURRENT_OPTIONS_SCHEMA_VERSION + 1);
+    assert_eq!(options.user_themes[0].url, "https://example.com/theme.css");
+  }
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf};
 use tauri_plugin_opener::OpenerExt;
 
-use crate::{config::app_config_dir, options};
+use crate::{command_utils::CommandError, config::app_config_dir, error::InstallerError, options};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -25,6 +25,12 @@ pub struct RunRecord {
   pub completed_at: Option<String>,
   pub overall_status: String,
   pub steps: Vec<RunStep>,
+  /// The git/pnpm invocation that failed the run, when that was the cause.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub command_error: Option<CommandError>,
+  /// `repo::build_fingerprint` of the build that was injected.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub build_fingerprint: Option<String>,
 }
 
 pub const FLOW_STEPS: &[(&str, &str)] = &[
@@ -45,6 +51,8 @@ pub fn new_record() -> RunRecord {
     completed_at: None,
     overall_status: "failed".to_string(),
     steps: Vec::new(),
+    command_error: None,
+    build_fingerprint: None,
   }
 }
 
@@ -131,11 +139,11 @@ fn prune_runs(dir: &PathBuf, max_count: u32) {
 }
 
 #[tauri::command]
-pub fn list_runs() -> Result<Vec<RunRecord>, String> {
-  let dir = runs_dir()?;
+pub fn list_runs() -> Result<Vec<RunRecord>, InstallerError> {
+  let dir = runs_dir().map_err(InstallerError::filesystem)?;
 
   let mut records: Vec<RunRecord> = fs::read_dir(&dir)
-    .map_err(|e| format!("Failed to read runs directory: {e}"))?
+    .map_err(|e| InstallerError::filesystem(format!("Failed to read runs directory: {e}")))?
     .filter_map(|entry| entry.ok())
     .filter(|entry| {
       entry
@@ -155,11 +163,11 @@ pub fn list_runs() -> Result<Vec<RunRecord>, String> {
 }
 
 #[tauri::command]
-pub fn open_runs_dir(app: tauri::AppHandle) -> Result<(), String> {
-  let dir = runs_dir()?;
+pub fn open_runs_dir(app: tauri::AppHandle) -> Result<(), InstallerError> {
+  let dir = runs_dir().map_err(InstallerError::filesystem)?;
   let dir_str = dir.to_string_lossy().into_owned();
   app
     .opener()
     .open_path(dir_str, None::<&str>)
-    .map_err(|e| format!("Failed to open runs directory: {e}"))
+    .map_err(|e| InstallerError::filesystem(format!("Failed to open runs directory: {e}")))
 }
@@ -1,9 +1,10 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke as tauriInvoke } from "@tauri-apps/api/core";
 
 export type DiscordInstall = {
   id: string;
   name: string;
   path: string;
+  lastUpdated?: string | null;
 };
 
 export type ProvidedRepository = {
@@ -13,6 +14,7 @@ export type ProvidedRepository = {
   description: string;
   defaultEnabled: string;
   enabled: boolean;
+  conflictsWith?: string[];
 };
 
 export type ProvidedTheme = {
@@ -24,18 +26,67 @@ export type ProvidedTheme = {
   enabled: boolean;
 }
 
+export type ProvidedCatalogEntry = {
+  id: string;
+  name: string;
+  url: string;
+  description: string;
+  defaultEnabled: boolean;
+};
+
+export type UserTheme = {
+  url: string;
+  authHeader?: string | null;
+};
+
 export type UserOptions = {
   vencordRepoUrl: string;
   vencordRepoDir: string;
   userRepositories: string[];
-  userThemes: string[];
+  userThemes: UserTheme[];
   providedRepositories: ProvidedRepository[];
   providedThemes: ProvidedTheme[];
   closeDiscordOnBackup: boolean;
+  aggressiveClose?: boolean;
+  alwaysRestartDiscord?: boolean;
+  reopenDiscordAfterPatch?: boolean;
+  confirmBeforeReopen?: boolean;
+  offlineMode?: boolean;
   selectedDiscordClients: string[];
   maxBackupCount?: number | null;
   maxBackupSizeMb?: number | null;
   maxRunLogCount?: number | null;
+  maxConcurrentNetwork?: number;
+  logToStdout?: boolean;
+  backupIncludeNodeModules?: boolean;
+  backupExclude?: string[];
+  backupDiscordSettings?: boolean;
+  backupUserThemes?: boolean;
+  backupNameFormat?: string | null;
+  backupUseUtc?: boolean;
+  themeDirOverride?: string | null;
+  writeThemesToAllClients?: boolean;
+  userpluginsDir?: string | null;
+  gitReferenceRepo?: string | null;
+  maxPluginCacheMb?: number | null;
+  nodePath?: string | null;
+  pnpmPath?: string | null;
+  customDiscordInstalls?: DiscordInstall[];
+  detectWineInstalls?: boolean;
+  reconciliation?: OptionsReconciliation | null;
+};
+
+export type OptionsReconciliation = {
+  addedRepositories: string[];
+  removedRepositories: string[];
+  addedThemes: string[];
+  removedThemes: string[];
+  repoDirReset?: RepoDirReset | null;
+};
+
+export type RepoDirReset = {
+  previous: string;
+  reason: string;
 };
 
 export type BackupResult = {
@@ -44,6 +95,7 @@ export type BackupResult = {
   closedClients: string[];
   restartedClients: string[];
   closingSkipped: boolean;
+  prunedBackups: number;
 };
 
 export type BackupInfo = {
@@ -53,11 +105,25 @@ export type BackupInfo = {
   createdAt?: string;
 }
 
+export type RestartOutcome = {
+  name: string;
+  restarted: boolean;
+  command: string;
+  error?: string | null;
+};
+
+export type BackupDeleteResult = {
+  name: string;
+  deleted: boolean;
+  error?: string | null;
+};
+
 export type DiscordProcess = {
   pid: string;
   name: string;
   exe?: string;
   cmd: string[];
+  cwd?: string;
 };
 
 export type FlowStepStatus = "running" | "completed" | "skipped" | "pending" | "failed";
@@ -69,30 +135,140 @@ export type FlowStepResult<T = unknown> = {
 };
 
 export type PatchFlowStepEvent<T = unknown> = FlowStepResult<T> & {
-  step: keyof PatchFlowResult;
+  step: PatchFlowStepId;
 }
 
+export type PatchFlowProgressEvent = {
+  completedSteps: number;
+  totalSteps: number;
+  percent: number;
+};
+
+export type ThemeDownloadProgressEvent = {
+  themeId: string;
+  name: string;
+  receivedBytes: number;
+  totalBytes?: number | null;
+  done: boolean;
+};
+
 export type DependencyStatus = {
   id: string;
   name: string;
   recommendedVersion: string;
+  minimumVersion?: string;
   installedVersion?: string;
-  status: 'installed' | 'missing' | 'outdated' | 'error';
+  status: 'installed' | 'missing' | 'outdated' | 'unsupported' | 'error';
   message?: string;
   canInstall: boolean;
   installLabel?: string;
 };
 
+export type BuildOutcome = {
+  install: string;
+  build: string;
+};
+
+export type ThemeDownloadResult = {
+  name: string;
+  url: string;
+  status: "downloaded" | "failed" | "skipped";
+  bytes: number | null;
+  error: string | null;
+  warnings: string[];
+};
+
 export type PatchFlowResult = {
   closeDiscord: FlowStepResult<string[]>;
   backup: FlowStepResult<BackupResult>;
   syncRepo: FlowStepResult<string>;
-  build: FlowStepResult<string>;
+  build: FlowStepResult<BuildOutcome>;
   inject: FlowStepResult<string>;
-  downloadThemes: FlowStepResult<string>;
-  reopenDiscord: FlowStepResult<string[]>;
+  downloadThemes: FlowStepResult<ThemeDownloadResult[]>;
+  reopenDiscord: FlowStepResult<RestartOutcome[]>;
+  runLogPath?: string | null;
+  reopenConfirmationTimedOut?: boolean;
 };
 
+export type PatchFlowStepId = Exclude<keyof PatchFlowResult, "runLogPath" | "reopenConfirmationTimedOut">;
+
+export type PatchFlowAwaitingConfirmationEvent = {
+  timeoutSecs: number;
+};
+
+export type PatchFlowCompleteEvent = {
+  result: PatchFlowResult;
+  succeeded: boolean;
+  durationMs: number;
+};
+
+export type CommandError = {
+  program: string;
+  args: string[];
+  statusCode?: number | null;
+  stdout: string;
+  stderr: string;
+  message: string;
+};
+
+export type PatchFlowFailedEvent = {
+  step?: string | null;
+  message: string;
+  durationMs: number;
+  commandError?: CommandError | null;
+};
+
+export type InstallerErrorKind =
+  | "network"
+  | "filesystem"
+  | "notFound"
+  | "invalidInput"
+  | "git"
+  | "build"
+  | "dependency"
+  | "other";
+
+export type InstallerErrorPayload = {
+  kind: InstallerErrorKind;
+  message: string;
+  details?: CommandError | null;
+};
+
+// Thrown by every command wrapper below. `String(err)` still gives the
+// message, so callers that only display errors need no changes.
+export class InstallerError extends Error {
+  kind: InstallerErrorKind;
+  details?: CommandError | null;
+
+  constructor(payload: InstallerErrorPayload) {
+    super(payload.message);
+    this.name = "InstallerError";
+    this.kind = payload.kind;
+    this.details = payload.details;
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+function isInstallerErrorPayload(value: unknown): value is InstallerErrorPayload {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    typeof (value as InstallerErrorPayload).kind === "string" &&
+    typeof (value as InstallerErrorPayload).message === "string"
+  );
+}
+
+async function invoke<T>(command: string, args?: Record<string, unknown>): Promise<T> {
+  try {
+    return await tauriInvoke<T>(command, args);
+  } catch (err) {
+    throw isInstallerErrorPayload(err) ? new InstallerError(err) : err;
+  }
+}
+
 export type DevTestStep = 
   | "closeDiscord"
   | "backup"
@@ -108,8 +284,8 @@ export type DevModuleResult =
   | { kind: "syncRepo"; path: string }
   | { kind: "build"; message?: string; path?: string }
   | { kind: "inject"; message?: string; path?: string }
-  | { kind: "downloadThemes"; message?: string }
-  | { kind: "reopenDiscord"; restarted: string[]; closedClients: string[]; closingSkipped: boolean; };
+  | { kind: "downloadThemes"; results?: ThemeDownloadResult[] }
+  | { kind: "reopenDiscord"; restarted: RestartOutcome[]; closedClients: string[]; closingSkipped: boolean; };
 
 export async function getDiscordInstalls(): Promise<DiscordInstall[]> {
   return await invoke<DiscordInstall[]>("get_discord_installs")
@@ -119,24 +295,174 @@ export async function getUserOptions(): Promise<UserOptions> {
   return await invoke<UserOptions>("get_user_options");
 }
 
+export async function listProvidedRepositories(): Promise<ProvidedCatalogEntry[]> {
+  return await invoke<ProvidedCatalogEntry[]>("list_provided_repositories");
+}
+
+export async function listProvidedThemes(): Promise<ProvidedCatalogEntry[]> {
+  return await invoke<ProvidedCatalogEntry[]>("list_provided_themes");
+}
+
+export async function setProvidedRepositoryEnabled(id: string, enabled: boolean): Promise<UserOptions> {
+  return await invoke<UserOptions>("set_provided_repository_enabled", { id, enabled });
+}
+
+export async function setProvidedThemeEnabled(id: string, enabled: boolean): Promise<UserOptions> {
+  return await invoke<UserOptions>("set_provided_theme_enabled", { id, enabled });
+}
+
+export async function setCloseDiscordOnBackup(enabled: boolean): Promise<UserOptions> {
+  return await invoke<UserOptions>("set_close_discord_on_backup", { enabled });
+}
+
+export async function moveUserRepository(from: number, to: number): Promise<UserOptions> {
+  return await invoke<UserOptions>("move_user_repository", { from, to });
+}
+
+export async function removeUserRepository(index: number): Promise<UserOptions> {
+  return await invoke<UserOptions>("remove_user_repository", { index });
+}
+
 export async function updateUserOptions(options: UserOptions): Promise<UserOptions> {
   return await invoke<UserOptions>("update_user_options", { options });
 }
 
-export async function listBackups(): Promise<BackupInfo[]> {
-  return await invoke<BackupInfo[]>("list_backups");
+export type BackupPage = {
+  backups: BackupInfo[];
+  total: number;
+};
+
+export async function listBackups(offset?: number, limit?: number): Promise<BackupPage> {
+  return await invoke<BackupPage>("list_backups", { offset, limit });
 }
 
-export async function deleteBackups(names: string[]): Promise<void> {
-  await invoke("delete_backups", { names });
+export async function deleteBackups(names: string[]): Promise<BackupDeleteResult[]> {
+  return await invoke<BackupDeleteResult[]>("delete_backups", { names });
 }
 
-export async function backupVencordInstall(sourcePath: string): Promise<BackupResult> {
-  return await invoke<BackupResult>("backup_vencord_install", { sourcePath });
+export type BackupsDiskUsage = {
+  totalBytes: number;
+  count: number;
+  oldest?: string | null;
+  newest?: string | null;
+};
+
+export async function backupsDiskUsage(): Promise<BackupsDiskUsage> {
+  return await invoke<BackupsDiskUsage>("backups_disk_usage");
 }
 
-export async function runPatchFlow(): Promise<PatchFlowResult> {
-  return await invoke<PatchFlowResult>("run_patch_flow");
+export type SettingsRestoreResult = {
+  restored: string[];
+  skipped: string[];
+};
+
+export async function restoreDiscordSettings(backupName: string): Promise<SettingsRestoreResult> {
+  return await invoke<SettingsRestoreResult>("restore_discord_settings", { backupName });
+}
+
+export async function backupVencordInstall(sourcePath: string, label?: string): Promise<BackupResult> {
+  return await invoke<BackupResult>("backup_vencord_install", { sourcePath, label });
+}
+
+export type BackupFile = {
+  path: string;
+  sizeBytes: number;
+};
+
+export type BackupContents = {
+  files: BackupFile[];
+  totalBytes: number;
+  hasThemes: boolean;
+  truncated: boolean;
+};
+
+export async function inspectBackup(name: string): Promise<BackupContents> {
+  return await invoke<BackupContents>("inspect_backup", { name });
+}
+
+export async function cancelBackup(): Promise<boolean> {
+  return await invoke<boolean>("cancel_backup");
+}
+
+export async function nextBackupName(label?: string): Promise<string> {
+  return await invoke<string>("next_backup_name", { label });
+}
+
+export async function runPatchFlow(offline?: boolean, buildOnly?: boolean): Promise<PatchFlowResult> {
+  return await invoke<PatchFlowResult>("run_patch_flow", { offline, buildOnly });
+}
+
+export async function resumePatchFlow(): Promise<PatchFlowResult> {
+  return await invoke<PatchFlowResult>("resume_patch_flow");
+}
+
+export type InterruptedFlow = {
+  runId: string;
+  step: PatchFlowStepId;
+  crashed: boolean;
+  closedClients: string[];
+  resumable: boolean;
+};
+
+export async function getInterruptedFlow(): Promise<InterruptedFlow | null> {
+  return await invoke<InterruptedFlow | null>("get_interrupted_flow");
+}
+
+export async function clearInterruptedFlow(): Promise<void> {
+  await invoke("clear_interrupted_flow");
+}
+
+export async function rollbackInterruptedFlow(): Promise<RestartOutcome[]> {
+  return await invoke<RestartOutcome[]>("rollback_interrupted_flow");
+}
+
+export async function confirmReopen(): Promise<void> {
+  await invoke("confirm_reopen");
+}
+
+export type ThemesFlowResult = {
+  downloadThemes: FlowStepResult<ThemeDownloadResult[]>;
+  removedThemes: string[];
+};
+
+export async function runThemesFlow(): Promise<ThemesFlowResult> {
+  return await invoke<ThemesFlowResult>("run_themes_flow");
+}
+
+export type BackupOnlyFlowResult = {
+  closeDiscord: FlowStepResult<string[]>;
+  backup: FlowStepResult<BackupResult>;
+  reopenDiscord: FlowStepResult<RestartOutcome[]>;
+};
+
+export async function runBackupOnlyFlow(): Promise<BackupOnlyFlowResult> {
+  return await invoke<BackupOnlyFlowResult>("run_backup_only_flow");
+}
+
+export type FactoryResetStepId =
+  | "closeDiscord"
+  | "unpatch"
+  | "backup"
+  | "deleteClone"
+  | "removeThemes"
+  | "reopenDiscord";
+
+export type FactoryResetStepEvent = {
+  step: FactoryResetStepId;
+  status: FlowStepStatus;
+  message?: string | null;
+};
+
+export type FactoryResetResult = {
+  unpatched: string[];
+  backupPath?: string | null;
+  deletedClone?: { path: string; freedBytes: number } | null;
+  removedThemes: string[];
+  restarted: RestartOutcome[];
+};
+
+export async function factoryResetVencord(keepBackup: boolean): Promise<FactoryResetResult> {
+  return await invoke<FactoryResetResult>("factory_reset_vencord", { keepBackup });
 }
 
 export async function runDevTest(
@@ -146,14 +472,60 @@ export async function runDevTest(
   return await invoke<DevModuleResult>("run_dev_test", { step, sourcePath });
 }
 
+export type InjectionPreview = {
+  targetFiles: string[];
+  willOverwrite: string[];
+};
+
+export async function previewInjection(id: string): Promise<InjectionPreview> {
+  return await invoke<InjectionPreview>("preview_injection", { id });
+}
+
+export type InjectReadiness = {
+  id: string;
+  injectable: boolean;
+  reason: string | null;
+};
+
+export async function checkInjectable(): Promise<InjectReadiness[]> {
+  return await invoke<InjectReadiness[]>("check_injectable");
+}
+
+export type ClientInjectResult = {
+  location: string;
+  injected: boolean;
+  error?: string | null;
+};
+
+export async function injectAllClients(): Promise<ClientInjectResult[]> {
+  return await invoke<ClientInjectResult[]>("inject_all_clients");
+}
+
 export async function listDiscordProcesses(): Promise<DiscordProcess[]> {
   return await invoke<DiscordProcess[]>("list_discord_processes");
 }
 
+export type DiscordRunningStatus = {
+  running: boolean;
+  clients: string[];
+};
+
+export async function isDiscordRunning(): Promise<DiscordRunningStatus> {
+  return await invoke<DiscordRunningStatus>("is_discord_running");
+}
+
+export async function restartClient(id: string): Promise<RestartOutcome> {
+  return await invoke<RestartOutcome>("restart_client", { id });
+}
+
+export async function wereClientsClosedByUs(): Promise<boolean> {
+  return await invoke<boolean>("were_clients_closed_by_us");
+}
+
 export async function updateSelectedDiscordClients(
   selected: string[],
-): Promise<void> {
-  await invoke("update_selected_discord_clients", { selected });
+): Promise<UserOptions> {
+  return await invoke<UserOptions>("update_selected_discord_clients", { selected });
 }
 
 export async function listDependencies(): Promise<DependencyStatus[]> {
@@ -179,12 +551,115 @@ export type RunRecord = {
   completedAt?: string;
   overallStatus: string;
   steps: RunStep[];
+  commandError?: CommandError;
+  buildFingerprint?: string;
+};
+
+export async function currentBuildFingerprint(): Promise<string | null> {
+  return await invoke<string | null>("current_build_fingerprint");
+}
+
+export type RepoStatus = {
+  exists: boolean;
+  isGit: boolean;
+  hasBuild: boolean;
+  currentRef: string | null;
+  remoteUrl: string | null;
 };
 
+export async function repoStatus(): Promise<RepoStatus> {
+  return await invoke<RepoStatus>("repo_status");
+}
+
 export async function listFlowRuns(): Promise<RunRecord[]> {
   return await invoke<RunRecord[]>("list_runs");
 }
 
 export async function openRunsDir(): Promise<void> {
   await invoke("open_runs_dir");
-}
\ No newline at end of file
+}
+
+export type RunLogInfo = {
+  name: string;
+  path: string;
+  createdAt?: string | null;
+  sizeBytes: number;
+};
+
+export async function listRunLogs(): Promise<RunLogInfo[]> {
+  return await invoke<RunLogInfo[]>("list_run_logs");
+}
+
+export async function clearRunLogs(): Promise<number> {
+  return await invoke<number>("clear_run_logs");
+}
+
+export async function openVencordSettingsDir(): Promise<void> {
+  await invoke("open_vencord_settings_dir");
+}
+
+export async function openThemeSource(id: string): Promise<void> {
+  await invoke("open_theme_source", { id });
+}
+
+export async function deleteInstalledTheme(name: string, force = false): Promise<void> {
+  await invoke("delete_installed_theme", { name, force });
+}
+
+export type SourceCheck = {
+  url: string;
+  kind: "repository" | "theme";
+  reachable: boolean;
+  status?: number | null;
+  error?: string | null;
+};
+
+export async function testSources(): Promise<SourceCheck[]> {
+  return await invoke<SourceCheck[]>("test_sources");
+}
+
+export type UrlValidation = {
+  valid: boolean;
+  normalized: string;
+  reason?: string | null;
+};
+
+export async function validateRepoUrl(url: string, checkRemote = false): Promise<UrlValidation> {
+  return await invoke<UrlValidation>("validate_repo_url", { url, checkRemote });
+}
+
+export async function validateThemeUrl(url: string): Promise<UrlValidation> {
+  return await invoke<UrlValidation>("validate_theme_url", { url });
+}
+
+export type EnvironmentCheck = {
+  check: string;
+  ok: boolean;
+  detail: string;
+};
+
+export async function runEnvironmentCheck(): Promise<EnvironmentCheck[]> {
+  return await invoke<EnvironmentCheck[]>("run_environment_check");
+}
+
+export type AppPaths = {
+  configDir: string;
+  unwritableConfigDir?: string | null;
+  backupsDir: string;
+  logsDir: string;
+  themeDir: string;
+  repoDir: string;
+};
+
+export async function getPaths(): Promise<AppPaths> {
+  return await invoke<AppPaths>("get_paths");
+}
+
+export type MaintenanceResult = {
+  removedPaths: string[];
+  freedBytes: number;
+};
+
+export async function runMaintenance(): Promise<MaintenanceResult> {
+  return await invoke<MaintenanceResult>("run_maintenance");
+}
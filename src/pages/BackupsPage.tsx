@@ -35,7 +35,7 @@ export default function BackupsPage() {
     setLoading(true);
     setError(null);
     try {
-      const items = await listBackups();
+      const { backups: items } = await listBackups();
       setBackups(items);
       setSelected((prev) => {
         const next = new Set<string>();
@@ -73,9 +73,13 @@ export default function BackupsPage() {
     setDeleting(true);
     setError(null);
     try {
-      await deleteBackups(Array.from(selected));
+      const results = await deleteBackups(Array.from(selected));
+      const failed = results.filter((r) => r.error);
       await loadBackups();
-      setSelected(new Set());
+      setSelected(new Set(failed.map((r) => r.name)));
+      if (failed.length > 0) {
+        setError(failed.map((r) => `${r.name}: ${r.error}`).join("\n"));
+      }
     } catch (err) {
       setError(String(err));
     } finally {
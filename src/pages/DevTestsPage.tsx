@@ -88,18 +88,28 @@ function summarizeResult(result: DevModuleResult): string {
     case "build":
     case "inject":
       return result.message || result.path || `${result.kind} step completed`;
-    case "downloadThemes":
-      return result.message || "Themes downloaded";
+    case "downloadThemes": {
+      const results = result.results ?? [];
+      if (!results.length) return "No themes enabled; skipping download";
+      const failed = results.filter((r) => r.status !== "downloaded");
+      if (failed.length) return `Failed to download ${failed.map((r) => `${r.name}: ${r.error}`).join("; ")}`;
+      return `Downloaded ${results.length} theme(s)`;
+    }
     case "reopenDiscord": {
       if (result.closingSkipped) {
         return "Discord restart skipped because closing is disabled in settings";
       }
 
-      const restarted = result.restarted?.length ?? 0;
+      const restarted = result.restarted?.filter((o) => o.restarted) ?? [];
+      const failed = result.restarted?.filter((o) => !o.restarted) ?? [];
       const closed = result.closedClients?.length ?? 0;
 
-      if (restarted > 0) {
-        return `Restarted ${restarted} Discord process(es): ${result.restarted.join(", ")}`;
+      if (failed.length > 0) {
+        return `Failed to restart: ${failed.map((o) => `${o.name} via ${o.command || "<unknown>"}: ${o.error}`).join("; ")}`;
+      }
+
+      if (restarted.length > 0) {
+        return `Restarted ${restarted.length} Discord process(es): ${restarted.map((o) => o.name).join(", ")}`;
       }
 
       if (closed > 0) {
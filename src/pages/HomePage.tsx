@@ -64,7 +64,7 @@ export default function HomePage({ onUpdateClick }: HomePageProps) {
   useEffect(() => {
     getVersion().then(setAppVersion).catch(() => setAppVersion("dev"));
     listFlowRuns().then(setRuns).catch(() => {});
-    listBackups().then(setBackups).catch(() => {});
+    listBackups().then((page) => setBackups(page.backups)).catch(() => {});
     getDiscordInstalls().then(setDiscordInstalls).catch(() => {});
     listDiscordProcesses().then(setProcesses).catch(() => {});
   }, []);
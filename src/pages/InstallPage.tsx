@@ -8,19 +8,23 @@ import {
   updateUserOptions,
 } from "../api";
 import type {
+  BuildOutcome,
   DiscordInstall,
   DiscordProcess,
   FlowStepResult,
   FlowStepStatus,
   PatchFlowStepEvent,
   PatchFlowResult,
+  PatchFlowStepId,
+  RestartOutcome,
+  ThemeDownloadResult,
   UserOptions,
 } from "../api";
 import "../css/InstallPage.css";
 
 const DISCORD_PROCESS_ORDER = ["discord", "discordptb", "discordcanary"] as const;
 
-const FLOW_STEPS: { id: keyof PatchFlowResult; title: string; description: string }[] = [
+const FLOW_STEPS: { id: PatchFlowStepId; title: string; description: string }[] = [
   { id: "closeDiscord",    title: "Close Discord",    description: "Stops running clients so files can be updated safely" },
   { id: "backup",          title: "Backup Vencord",   description: "Copies your current Vencord files to a backup folder" },
   { id: "syncRepo",        title: "Sync repository",  description: "Clones or updates the configured Vencord repository" },
@@ -32,7 +36,7 @@ const FLOW_STEPS: { id: keyof PatchFlowResult; title: string; description: strin
 
 type StepVisualStatus = FlowStepStatus | "idle";
 type StepState = { status: StepVisualStatus; message: string };
-type StepStateMap = Record<keyof PatchFlowResult, StepState>;
+type StepStateMap = Record<PatchFlowStepId, StepState>;
 
 const normalizeProcessName = (name: string) => name.toLowerCase().replace(/\.exe$/, "");
 
@@ -46,7 +50,7 @@ const buildRunningSteps = (): StepStateMap => {
   return base;
 };
 
-const describeStep = (stepId: keyof PatchFlowResult, result?: FlowStepResult<unknown>): string => {
+const describeStep = (stepId: PatchFlowStepId, result?: FlowStepResult<unknown>): string => {
   if (!result) return "Not started";
   if (result.message) return result.message;
   switch (stepId) {
@@ -61,12 +65,24 @@ const describeStep = (stepId: keyof PatchFlowResult, result?: FlowStepResult<unk
       return detail?.backupPath ? `Saved to ${detail.backupPath}` : "Backup completed";
     }
     case "syncRepo": return (result.detail as string | undefined) ? `Synced at ${result.detail}` : "Repository synced";
-    case "build":
+    case "build":          return (result.detail as BuildOutcome | undefined)?.build || "build completed";
     case "inject":         return (result.detail as string | undefined) || `${stepId} completed`;
-    case "downloadThemes": return (result.detail as string | undefined) || "Themes downloaded";
+    case "downloadThemes": {
+      const detail = result.detail as ThemeDownloadResult[] | undefined;
+      if (!detail?.length) return "Themes downloaded";
+      const downloaded = detail.filter((r) => r.status === "downloaded").length;
+      const failed = detail.filter((r) => r.status !== "downloaded");
+      const warnings = [...new Set(detail.flatMap((r) => r.warnings))];
+      const parts = [`Downloaded ${downloaded} of ${detail.length} theme(s)`];
+      if (failed.length) parts.push(`failed: ${failed.map((r) => `${r.name}: ${r.error}`).join("; ")}`);
+      if (warnings.length) parts.push(warnings.join("; "));
+      return parts.join("; ");
+    }
     case "reopenDiscord": {
-      const detail = result.detail as string[] | undefined;
+      const detail = result.detail as RestartOutcome[] | undefined;
       if (result.status === "skipped") return "Skipped";
+      const failed = detail?.filter((o) => !o.restarted) ?? [];
+      if (failed.length) return `Failed to restart ${failed.map((o) => `${o.name} (${o.command}): ${o.error}`).join("; ")}`;
       return detail?.length ? `Restarted ${detail.length} client(s)` : "Restarted";
     }
     default: return "Completed";
@@ -2,7 +2,7 @@ import { open } from "@tauri-apps/plugin-dialog";
 import { useEffect, useState } from "react";
 import { FolderOpen, GitBranch, Palette, Archive, FileText, Monitor } from "lucide-react";
 import { getUserOptions, updateUserOptions } from "../api";
-import type { UserOptions } from "../api";
+import type { UserOptions, UserTheme } from "../api";
 
 const appendVencordFolder = (basePath: string) => {
   const trimmed = basePath.replace(/[\\/]+$/, "");
@@ -90,7 +90,7 @@ export default function SettingsPage({
       .then((data) => {
         setOptions(data);
         setUserReposText(data.userRepositories.join("\n"));
-        setUserThemesText(data.userThemes.join("\n"));
+        setUserThemesText(data.userThemes.map((theme) => theme.url).join("\n"));
       })
       .catch((err) => setError(String(err)))
       .finally(() => setLoading(false));
@@ -116,7 +116,7 @@ export default function SettingsPage({
       const updated = await updateUserOptions(nextOptions);
       setOptions(updated);
       if (syncUserReposText) setUserReposText(updated.userRepositories.join("\n"));
-      if (syncUserThemesText) setUserThemesText(updated.userThemes.join("\n"));
+      if (syncUserThemesText) setUserThemesText(updated.userThemes.map((theme) => theme.url).join("\n"));
       return true;
     } catch (err) {
       setError(String(err));
@@ -129,6 +129,13 @@ export default function SettingsPage({
   const parseLines = (text: string) =>
     text.split("\n").map((l) => l.trim()).filter((l) => l.length > 0);
 
+  // Keeps the auth header of entries whose URL did not change.
+  const parseThemeLines = (text: string, previous: UserTheme[]): UserTheme[] =>
+    parseLines(text).map((url) => ({
+      url,
+      authHeader: previous.find((theme) => theme.url === url)?.authHeader ?? null,
+    }));
+
   const parseNumberInput = (value: string) => {
     if (value.trim() === "") return null;
     const parsed = Number(value);
@@ -289,7 +296,7 @@ export default function SettingsPage({
                         onChange={(e) => { setUserThemesText(e.target.value); setDirtyFields((p) => ({ ...p, userThemes: true })); }}
                         onBlur={async () => {
                           if (!options || saving || !dirtyFields.userThemes) return;
-                          const saved = await saveOptions({ ...options, userThemes: parseLines(userThemesText) }, { syncUserReposText: false, syncUserThemesText: true });
+                          const saved = await saveOptions({ ...options, userThemes: parseThemeLines(userThemesText, options.userThemes) }, { syncUserReposText: false, syncUserThemesText: true });
                           if (saved) setDirtyFields((p) => ({ ...p, userThemes: false }));
                         }}
                         placeholder="One theme URL per line"
@@ -388,6 +395,7 @@ export default function SettingsPage({
                 )}
 
                 {activeCategory === "discord" && (
+                  <>
                   <button
                     type="button"
                     className={`selectable-item${options.closeDiscordOnBackup ? " selected" : ""}`}
@@ -407,6 +415,45 @@ export default function SettingsPage({
                       </div>
                     </div>
                   </button>
+                  <button
+                    type="button"
+                    className={`selectable-item${options.reopenDiscordAfterPatch !== false ? " selected" : ""}`}
+                    disabled={saving}
+                    onClick={async () => {
+                      if (!options || saving) return;
+                      await saveOptions({ ...options, reopenDiscordAfterPatch: options.reopenDiscordAfterPatch === false }, { syncUserReposText: false });
+                    }}
+                  >
+                    <div className="selectable-check">
+                      <div className="selectable-check-mark" />
+                    </div>
+                    <div className="toggle-info">
+                      <div className="toggle-name">Reopen Discord after patching</div>
+                      <div className="toggle-desc">
+                        Restarts the Discord clients that were closed for patching. Turn off if another tool launches Discord for you
+                      </div>
+                    </div>
+                  </button>
+                  <button
+                    type="button"
+                    className={`selectable-item${options.alwaysRestartDiscord ? " selected" : ""}`}
+                    disabled={saving}
+                    onClick={async () => {
+                      if (!options || saving) return;
+                      await saveOptions({ ...options, alwaysRestartDiscord: !options.alwaysRestartDiscord }, { syncUserReposText: false });
+                    }}
+                  >
+                    <div className="selectable-check">
+                      <div className="selectable-check-mark" />
+                    </div>
+                    <div className="toggle-info">
+                      <div className="toggle-name">Always start Discord after patching</div>
+                      <div className="toggle-desc">
+                        Starts the selected Discord clients after patching even when they were not closed beforehand
+                      </div>
+                    </div>
+                  </button>
+                  </>
                 )}
               </>
             )}